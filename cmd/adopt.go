@@ -0,0 +1,55 @@
+package cmd
+
+import (
+	"os"
+	"path/filepath"
+
+	"log/slog"
+
+	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/app"
+)
+
+var adoptCmd = &cobra.Command{
+	Use:   "adopt [package-dir]",
+	Short: "Register existing implementations as generated without calling the AI",
+	Long: `Adopt existing code into mantra. For every function with a // mantra comment
+whose body is already implemented, the body is written to the output directory
+with a checksum, as if it had been generated.
+
+Functions still containing panic("not implemented") and functions that are
+already up-to-date are left unchanged, so running adopt again has no effect.`,
+	Args: cobra.MaximumNArgs(1),
+	Run: func(cmd *cobra.Command, args []string) {
+		pkgDir := "."
+		if len(args) > 0 {
+			pkgDir = args[0]
+		}
+
+		cfg, err := loadConfig(pkgDir)
+		if err != nil {
+			slog.Error("failed to load configuration", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		setupLogging(cfg)
+
+		absPkgDir, err := filepath.Abs(pkgDir)
+		if err != nil {
+			slog.Error("failed to get absolute path", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		generateApp := app.NewGenerateApp()
+		if err := generateApp.Adopt(absPkgDir, cfg); err != nil {
+			slog.Error("adopt failed", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+	},
+}
+
+func init() {
+	adoptCmd.Flags().StringVar(&logLevel, "log-level", "", "Override log level (error, warn, info, debug, trace)")
+	rootCmd.AddCommand(adoptCmd)
+}
@@ -0,0 +1,49 @@
+package cmd
+
+import (
+	"os"
+
+	"log/slog"
+
+	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/app"
+)
+
+var applyCmd = &cobra.Command{
+	Use:   "apply <plan-file>",
+	Short: "Write the implementations of a saved plan",
+	Long: `Write the implementations saved by "mantra plan" to the destination directory.
+
+Each target is checked against its current declaration first. Targets whose
+declaration or instruction changed since planning are skipped with a warning.`,
+	Args: cobra.ExactArgs(1),
+	Run: func(cmd *cobra.Command, args []string) {
+		plan, err := app.ReadPlan(args[0])
+		if err != nil {
+			slog.Error("failed to load plan", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		cfg, err := loadConfig(plan.Package)
+		if err != nil {
+			slog.Error("failed to load configuration", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		setupLogging(cfg)
+		cfg.FailOnDiagnostics = failOnDiagnostics
+
+		generateApp := app.NewGenerateApp()
+		if err := generateApp.Apply(plan, cfg); err != nil {
+			slog.Error("apply failed", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+	},
+}
+
+func init() {
+	applyCmd.Flags().StringVar(&logLevel, "log-level", "", "Override log level (error, warn, info, debug, trace)")
+	applyCmd.Flags().BoolVar(&failOnDiagnostics, "fail-on-diagnostics", false, "Exit with an error if the generated package has type errors")
+	rootCmd.AddCommand(applyCmd)
+}
@@ -0,0 +1,93 @@
+package cmd
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"os"
+	"path/filepath"
+
+	"log/slog"
+
+	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+var checksumFunction string
+
+var checksumCmd = &cobra.Command{
+	Use:   "checksum <file>",
+	Short: "Print the checksums of the targets in a file without generating",
+	Long: `Print the current checksum of each mantra target in a file, and whether the
+generated file in the output directory contains a matching // mantra:checksum line.
+
+Useful to find out why a target is regenerated. No AI calls are made.`,
+	Args: cobra.ExactArgs(1),
+	Run: func(cmd *cobra.Command, args []string) {
+		file := args[0]
+
+		cfg, err := loadConfig(filepath.Dir(file))
+		if err != nil {
+			slog.Error("failed to load configuration", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		fileInfo, err := parser.ParseFileInfoWithPrefix(file, cfg.InstructionPrefix)
+		if err != nil {
+			slog.Error("failed to parse file", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		generatedFile := filepath.Join(cfg.Dest, filepath.Base(file))
+		existing, err := detector.ExistingChecksums(generatedFile)
+		if err != nil && !errors.Is(err, fs.ErrNotExist) {
+			slog.Warn("failed to read generated file",
+				slog.String("file", generatedFile),
+				slog.String("error", err.Error()))
+		}
+
+		found := false
+		for _, target := range fileInfo.Targets {
+			if checksumFunction != "" && target.Name != checksumFunction && target.GetDisplayName() != checksumFunction {
+				continue
+			}
+			found = true
+
+			current := checksum.Calculate(target)
+			fmt.Printf("%s: %s (%s)\n", target.GetDisplayName(), current, checksumState(current, existing[target.GetDisplayName()]))
+		}
+
+		for _, name := range fileInfo.Skipped {
+			if checksumFunction != "" && name != checksumFunction {
+				continue
+			}
+			found = true
+			fmt.Printf("%s: skipped (mantra:skip)\n", name)
+		}
+
+		if checksumFunction != "" && !found {
+			slog.Error("target not found", slog.String("function", checksumFunction))
+			os.Exit(1)
+		}
+	},
+}
+
+// checksumState describes how the generated checksum relates to the current one
+func checksumState(current, generated string) string {
+	switch generated {
+	case "":
+		return "not generated"
+	case current:
+		return "generated"
+	default:
+		return fmt.Sprintf("outdated, generated from %s", generated)
+	}
+}
+
+func init() {
+	checksumCmd.Flags().StringVar(&checksumFunction, "function", "", "Only print the checksum of this function")
+	rootCmd.AddCommand(checksumCmd)
+}
@@ -0,0 +1,59 @@
+package cmd
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"log/slog"
+
+	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/app"
+)
+
+var compareModels string
+
+var compareCmd = &cobra.Command{
+	Use:   "compare <file>",
+	Short: "Generate the targets of a file with several models and compare the results",
+	Long: `Generate every mantra target of a file with each of the given models and
+print the implementations side by side, with whether each one parses as Go
+and the tokens each model used.
+
+Nothing is written to the output directory.`,
+	Args: cobra.ExactArgs(1),
+	Run: func(cmd *cobra.Command, args []string) {
+		file := args[0]
+
+		var models []string
+		for _, model := range strings.Split(compareModels, ",") {
+			if model = strings.TrimSpace(model); model != "" {
+				models = append(models, model)
+			}
+		}
+		if len(models) < 2 {
+			slog.Error("invalid flag", slog.String("error", "--models needs at least two comma-separated models"))
+			os.Exit(1)
+		}
+
+		cfg, err := loadConfig(filepath.Dir(file))
+		if err != nil {
+			slog.Error("failed to load configuration", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+		setupLogging(cfg)
+
+		generateApp := app.NewGenerateApp()
+		if err := generateApp.Compare(context.Background(), file, models, cfg, os.Stdout); err != nil {
+			slog.Error("comparison failed", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+	},
+}
+
+func init() {
+	compareCmd.Flags().StringVar(&compareModels, "models", "", "Comma-separated models to compare, e.g. a,b")
+	rootCmd.AddCommand(compareCmd)
+}
@@ -0,0 +1,77 @@
+package cmd
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+
+	"log/slog"
+
+	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/parser"
+	"github.com/rail44/mantra/internal/prompt"
+)
+
+var contextOnly string
+
+var contextCmd = &cobra.Command{
+	Use:   "context <file>",
+	Short: "Print the type information mantra would send for each target",
+	Long: `Print the signature and the type information collected for each mantra target
+in a file, as it would be included in the prompt. No AI calls are made.
+
+Useful to find out why generated code lacks type context.`,
+	Args: cobra.ExactArgs(1),
+	Run: func(cmd *cobra.Command, args []string) {
+		file := args[0]
+
+		// Configuration only affects optional sections, so it is not required
+		var options prompt.Options
+		var instructionPrefix string
+		if cfg, err := loadConfig(filepath.Dir(file)); err == nil {
+			options.PackageContext = cfg.IncludePackageContext
+			options.MaxContextTokens = cfg.MaxContextTokens
+			instructionPrefix = cfg.InstructionPrefix
+		} else {
+			slog.Debug("configuration not loaded", slog.String("error", err.Error()))
+		}
+
+		fileInfo, err := parser.ParseFileInfoWithPrefix(file, instructionPrefix)
+		if err != nil {
+			slog.Error("failed to parse file", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		builder := prompt.NewBuilder(slog.Default())
+		builder.SetOptions(options)
+
+		found := false
+		for _, target := range fileInfo.Targets {
+			if contextOnly != "" && target.Name != contextOnly {
+				continue
+			}
+			found = true
+
+			typeInfo, err := builder.TypeInfoForTarget(target)
+			if err != nil {
+				slog.Error("failed to collect type information",
+					slog.String("function", target.GetDisplayName()),
+					slog.String("error", err.Error()))
+				os.Exit(1)
+			}
+
+			fmt.Printf("=== %s\n%s\n\n%s\n", target.GetDisplayName(), target.GetFunctionSignature(), typeInfo)
+		}
+
+		if contextOnly != "" && !found {
+			slog.Error("target not found", slog.String("function", contextOnly))
+			os.Exit(1)
+		}
+	},
+}
+
+func init() {
+	contextCmd.Flags().StringVar(&contextOnly, "only", "", "Only print the context of this function")
+	rootCmd.AddCommand(contextCmd)
+}
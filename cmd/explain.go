@@ -0,0 +1,43 @@
+package cmd
+
+import (
+	"context"
+	"os"
+	"path/filepath"
+
+	"log/slog"
+
+	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/app"
+)
+
+var explainCmd = &cobra.Command{
+	Use:   "explain <file> <function>",
+	Short: "Ask the AI to explain a generated implementation",
+	Long: `Send the instruction, signature and generated body of a function to the AI
+and print a brief explanation of the implementation.
+
+The implementation is read from the generated file in the output directory.`,
+	Args: cobra.ExactArgs(2),
+	Run: func(cmd *cobra.Command, args []string) {
+		file, function := args[0], args[1]
+
+		cfg, err := loadConfig(filepath.Dir(file))
+		if err != nil {
+			slog.Error("failed to load configuration", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+		setupLogging(cfg)
+
+		generateApp := app.NewGenerateApp()
+		if err := generateApp.Explain(context.Background(), file, function, cfg, os.Stdout); err != nil {
+			slog.Error("explanation failed", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+	},
+}
+
+func init() {
+	rootCmd.AddCommand(explainCmd)
+}
@@ -2,8 +2,12 @@ package cmd
 
 import (
 	"context"
+	"fmt"
 	"os"
+	"os/signal"
 	"path/filepath"
+	"slices"
+	"strings"
 
 	"log/slog"
 
@@ -17,60 +21,224 @@ import (
 var (
 	plain    bool
 	logLevel string
+	tempRoot string
+
+	failOnDiagnostics bool
+	strictConfig      bool
+	reportPath        string
+	force             bool
+	allowLargeBatch   bool
+	explain           bool
+	incrementalWrite  bool
+	outputMode        string
+	maxCost           float64
+	stats             bool
+	seed              int
 )
 
 var generateCmd = &cobra.Command{
-	Use:   "generate [package-dir]",
+	Use:   "generate [package-dir...]",
 	Short: "Generate implementations for all pending targets in a package",
 	Long: `Generate implementations for all mantra targets in a package that are either:
 - Not yet generated (new targets)
 - Outdated (declaration or instruction changed)
 
 The command looks for functions marked with // mantra comments and generates
-their implementations based on the natural language instructions provided.`,
-	Args: cobra.MaximumNArgs(1),
+their implementations based on the natural language instructions provided.
+
+Several package directories or glob patterns (e.g. "./pkg/*") generate each
+package in turn with its own mantra.toml. Directories that are the output of
+another package are skipped.
+
+Pass "-" as the package directory to read a single Go file from stdin and
+write the generated result to stdout.`,
+	Args: cobra.ArbitraryArgs,
 	Run: func(cmd *cobra.Command, args []string) {
-		// Get package directory (default to current directory)
-		pkgDir := "."
-		if len(args) > 0 {
-			pkgDir = args[0]
+		if slices.Contains(args, "-") {
+			if len(args) > 1 {
+				slog.Error("invalid package directory", slog.String("error", `"-" cannot be combined with other package directories`))
+				os.Exit(1)
+			}
+			runStdin(cmd)
+			return
 		}
 
-		// Load configuration
-		cfg, err := config.Load(pkgDir)
+		// Get package directories (default to current directory)
+		pkgDirs, err := expandPackageDirs(args)
 		if err != nil {
-			slog.Error("failed to load configuration", slog.String("error", err.Error()))
+			slog.Error("invalid package directory", slog.String("error", err.Error()))
 			os.Exit(1)
 		}
 
-		// Set up logging
-		setupLogging(cfg)
+		// Cancel generation on Ctrl-C so that the lock of the destination is released
+		ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt)
+		defer stop()
+
+		generateApp := app.NewGenerateApp()
+		load := func(pkgDir string) (*config.Config, error) {
+			return loadGenerateConfig(cmd, pkgDir)
+		}
+
+		if len(pkgDirs) > 1 {
+			if err := app.ValidateBatchOutput(reportPath, outputMode); err != nil {
+				slog.Error("invalid flags", slog.String("error", err.Error()))
+				os.Exit(1)
+			}
+			result := generateApp.RunBatch(ctx, pkgDirs, load)
+			result.Write(os.Stderr)
+			if result.Failed() > 0 {
+				os.Exit(1)
+			}
+			return
+		}
 
-		// Ensure absolute path
-		absPkgDir, err := filepath.Abs(pkgDir)
+		cfg, err := load(pkgDirs[0])
 		if err != nil {
-			slog.Error("failed to get absolute path", slog.String("error", err.Error()))
+			slog.Error("failed to load configuration", slog.String("error", err.Error()))
 			os.Exit(1)
 		}
 
-		// Set plain output flag in config
-		cfg.Plain = plain
-
 		// Run generation
-		generateApp := app.NewGenerateApp()
-		if err := generateApp.Run(context.Background(), absPkgDir, cfg); err != nil {
+		if err := generateApp.Run(ctx, pkgDirs[0], cfg); err != nil {
 			slog.Error("generation failed", slog.String("error", err.Error()))
 			os.Exit(1)
 		}
 	},
 }
 
+// expandPackageDirs resolves package arguments to absolute directories.
+// Glob patterns match directories only. Without arguments, the current
+// directory is used.
+func expandPackageDirs(args []string) ([]string, error) {
+	if len(args) == 0 {
+		args = []string{"."}
+	}
+
+	var dirs []string
+	seen := make(map[string]bool)
+	for _, arg := range args {
+		matches := []string{arg}
+		if strings.ContainsAny(arg, "*?[") {
+			globbed, err := filepath.Glob(arg)
+			if err != nil {
+				return nil, fmt.Errorf("invalid pattern %q: %w", arg, err)
+			}
+			matches = matches[:0]
+			for _, match := range globbed {
+				if info, err := os.Stat(match); err == nil && info.IsDir() {
+					matches = append(matches, match)
+				}
+			}
+			if len(matches) == 0 {
+				return nil, fmt.Errorf("no directories match %q", arg)
+			}
+		}
+
+		for _, match := range matches {
+			absDir, err := filepath.Abs(match)
+			if err != nil {
+				return nil, fmt.Errorf("failed to get absolute path: %w", err)
+			}
+			if !seen[absDir] {
+				seen[absDir] = true
+				dirs = append(dirs, absDir)
+			}
+		}
+	}
+
+	return dirs, nil
+}
+
+// loadGenerateConfig loads the configuration of a package and applies the
+// command line flags to it
+func loadGenerateConfig(cmd *cobra.Command, pkgDir string) (*config.Config, error) {
+	cfg, err := loadConfig(pkgDir)
+	if err != nil {
+		return nil, err
+	}
+
+	// Set up logging
+	setupLogging(cfg)
+
+	if strictConfig {
+		if err := cfg.ValidateStrict(); err != nil {
+			return nil, err
+		}
+	}
+
+	// Set CLI flags in config
+	cfg.Plain = plain
+	cfg.FailOnDiagnostics = failOnDiagnostics
+	cfg.ReportPath = reportPath
+	cfg.Force = force
+	cfg.AllowLargeBatch = allowLargeBatch
+	cfg.Explain = explain
+	cfg.IncrementalWrite = incrementalWrite
+	cfg.OutputMode = outputMode
+	if err := app.ValidateOutputMode(outputMode); err != nil {
+		return nil, err
+	}
+	// The TUI also draws on stdout
+	if outputMode != "" {
+		cfg.Plain = true
+	}
+	cfg.Stats = stats
+	cfg.MaxCost = maxCost
+	if maxCost > 0 && cfg.Pricing == nil {
+		return nil, fmt.Errorf("--max-cost requires [pricing] in mantra.toml")
+	}
+	if cmd.Flags().Changed("seed") {
+		cfg.Seed = &seed
+	}
+
+	return cfg, nil
+}
+
 func init() {
 	generateCmd.Flags().BoolVar(&plain, "plain", false, "Use plain text output instead of interactive TUI")
 	generateCmd.Flags().StringVar(&logLevel, "log-level", "", "Override log level (error, warn, info, debug, trace)")
+	generateCmd.Flags().StringVar(&tempRoot, "temp-root", "", "Directory for temporary files when reading from stdin (default: current directory)")
+	generateCmd.Flags().BoolVar(&failOnDiagnostics, "fail-on-diagnostics", false, "Exit with an error if the generated package has type errors")
+	generateCmd.Flags().IntVar(&seed, "seed", 0, "Sampling seed for reproducible output (uses temperature 0)")
+	generateCmd.Flags().BoolVar(&explain, "explain", false, "Print an explanation of each generated implementation")
+	generateCmd.Flags().BoolVar(&incrementalWrite, "incremental-write", false, "Write files as targets complete, so an interrupted run can resume")
+	generateCmd.Flags().BoolVar(&force, "force", false, "Regenerate all targets, including up-to-date ones")
+	generateCmd.Flags().BoolVar(&allowLargeBatch, "allow-large-batch", false, "Generate regardless of max_targets_per_file and max_file_size_bytes")
+	generateCmd.Flags().Float64Var(&maxCost, "max-cost", 0, "Stop starting new generations once the estimated cost reaches this many dollars")
+	generateCmd.Flags().StringVar(&outputMode, "output-mode", "", "What to print after generation: full (written files), diff (unified diff of the destination), snippets (generated bodies as JSON) or json-report")
+	generateCmd.Flags().BoolVar(&stats, "stats", false, "Print request counts, rate limiting, latency and cache hit rate at the end")
+	generateCmd.Flags().StringVar(&reportPath, "report", "", "Write a JSON summary of the run to this path")
+	generateCmd.Flags().BoolVar(&strictConfig, "strict-config", false, "Treat configuration warnings as errors")
 	rootCmd.AddCommand(generateCmd)
 }
 
+// runStdin generates from source read on stdin and writes the result to stdout
+func runStdin(cmd *cobra.Command) {
+	// The generated file is the only output on stdout
+	for _, name := range []string{"output-mode", "report", "explain"} {
+		if cmd.Flags().Changed(name) {
+			slog.Error("invalid flags", slog.String("error", fmt.Sprintf("--%s cannot be used when reading from stdin", name)))
+			os.Exit(1)
+		}
+	}
+
+	// Configuration is resolved relative to the current directory
+	cfg, err := loadGenerateConfig(cmd, ".")
+	if err != nil {
+		slog.Error("failed to load configuration", slog.String("error", err.Error()))
+		os.Exit(1)
+	}
+
+	ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt)
+	defer stop()
+
+	generateApp := app.NewGenerateApp()
+	if err := generateApp.RunStdin(ctx, os.Stdin, os.Stdout, cfg, tempRoot); err != nil {
+		slog.Error("generation failed", slog.String("error", err.Error()))
+		os.Exit(1)
+	}
+}
+
 func setupLogging(cfg *config.Config) {
 	// Command line flag takes precedence over config file
 	level := logLevel
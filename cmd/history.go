@@ -0,0 +1,85 @@
+package cmd
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+
+	"log/slog"
+
+	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/history"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+var historyLimit int
+
+var historyCmd = &cobra.Command{
+	Use:   "history <file>",
+	Short: "Show previous generations for the targets in a file",
+	Long: `Show the most recent generations recorded for each mantra target in a file.
+
+History is kept per declaration checksum, so changing a function's signature or
+instruction starts a new history.`,
+	Args: cobra.ExactArgs(1),
+	Run: func(cmd *cobra.Command, args []string) {
+		// Configuration only affects the instruction prefix, so it is not required
+		var instructionPrefix string
+		if cfg, err := loadConfig(filepath.Dir(args[0])); err == nil {
+			instructionPrefix = cfg.InstructionPrefix
+		} else {
+			slog.Debug("configuration not loaded", slog.String("error", err.Error()))
+		}
+
+		fileInfo, err := parser.ParseFileInfoWithPrefix(args[0], instructionPrefix)
+		if err != nil {
+			slog.Error("failed to parse file", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		store, err := history.DefaultStore()
+		if err != nil {
+			slog.Error("failed to open history", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		for _, target := range fileInfo.Targets {
+			entries, err := store.Load(target.FilePath, checksum.Calculate(target))
+			if err != nil {
+				slog.Error("failed to load history",
+					slog.String("function", target.GetDisplayName()),
+					slog.String("error", err.Error()))
+				os.Exit(1)
+			}
+			printHistory(target, entries)
+		}
+	},
+}
+
+// printHistory prints the last generations of a target, newest first
+func printHistory(target *parser.Target, entries []history.Entry) {
+	fmt.Printf("%s (%d generations)\n", target.GetDisplayName(), len(entries))
+
+	shown := 0
+	for i := len(entries) - 1; i >= 0 && shown < historyLimit; i-- {
+		entry := entries[i]
+		fmt.Printf("  %s  %s  tokens: %d prompt, %d completion\n",
+			entry.Timestamp.Local().Format("2006-01-02 15:04:05"),
+			entry.Model,
+			entry.PromptTokens,
+			entry.CompletionTokens)
+		for _, line := range strings.Split(entry.GeneratedBody, "\n") {
+			fmt.Printf("    %s\n", line)
+		}
+		shown++
+	}
+	fmt.Println()
+}
+
+func init() {
+	historyCmd.Flags().IntVarP(&historyLimit, "number", "n", 3, "Number of generations to show per target")
+	rootCmd.AddCommand(historyCmd)
+}
@@ -0,0 +1,80 @@
+package cmd
+
+import (
+	"context"
+	"io"
+	"os"
+	"os/signal"
+	"path/filepath"
+
+	"log/slog"
+
+	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/app"
+)
+
+var planOutput string
+
+var planCmd = &cobra.Command{
+	Use:   "plan [package-dir]",
+	Short: "Generate implementations into a plan file without writing generated files",
+	Long: `Generate implementations for all pending targets in a package and save them,
+together with the checksum of each declaration, to a JSON plan.
+
+The plan can be reviewed and later written with "mantra apply".`,
+	Args: cobra.MaximumNArgs(1),
+	Run: func(cmd *cobra.Command, args []string) {
+		pkgDir := "."
+		if len(args) > 0 {
+			pkgDir = args[0]
+		}
+
+		cfg, err := loadConfig(pkgDir)
+		if err != nil {
+			slog.Error("failed to load configuration", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		setupLogging(cfg)
+
+		absPkgDir, err := filepath.Abs(pkgDir)
+		if err != nil {
+			slog.Error("failed to get absolute path", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		cfg.Plain = plain
+
+		// Write to stdout unless an output file is given.
+		// The TUI also draws on stdout, so plain output is used in that case.
+		var w io.Writer = os.Stdout
+		if planOutput == "" {
+			cfg.Plain = true
+		} else {
+			f, err := os.Create(planOutput)
+			if err != nil {
+				slog.Error("failed to create plan file", slog.String("error", err.Error()))
+				os.Exit(1)
+			}
+			defer f.Close()
+			w = f
+		}
+
+		ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt)
+		defer stop()
+
+		generateApp := app.NewGenerateApp()
+		if err := generateApp.Plan(ctx, absPkgDir, cfg, w); err != nil {
+			slog.Error("planning failed", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+	},
+}
+
+func init() {
+	planCmd.Flags().StringVarP(&planOutput, "output", "o", "", "Write the plan to a file instead of stdout")
+	planCmd.Flags().BoolVar(&plain, "plain", false, "Use plain text output instead of interactive TUI")
+	planCmd.Flags().StringVar(&logLevel, "log-level", "", "Override log level (error, warn, info, debug, trace)")
+	rootCmd.AddCommand(planCmd)
+}
@@ -5,6 +5,13 @@ import (
 	"os"
 
 	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/config"
+)
+
+var (
+	envOnly   bool
+	overrides config.Overrides
 )
 
 var rootCmd = &cobra.Command{
@@ -15,6 +22,19 @@ AI-powered Go code implementations from natural language instructions.`,
 	CompletionOptions: cobra.CompletionOptions{
 		DisableDefaultCmd: true,
 	},
+	PersistentPreRun: func(cmd *cobra.Command, args []string) {
+		// --env is an alias for MANTRA_NO_CONFIG_FILE=1
+		if envOnly {
+			os.Setenv(config.EnvNoConfigFile, "1")
+		}
+	},
+}
+
+func init() {
+	rootCmd.PersistentFlags().BoolVar(&envOnly, "env", false, "Read configuration from MANTRA_* environment variables instead of mantra.toml")
+	rootCmd.PersistentFlags().StringVar(&overrides.Model, "model", "", "Override the configured model")
+	rootCmd.PersistentFlags().StringVar(&overrides.URL, "url", "", "Override the configured API URL")
+	rootCmd.PersistentFlags().StringVar(&overrides.APIKey, "api-key", "", "Override the configured API key")
 }
 
 func Execute() {
@@ -23,3 +43,16 @@ func Execute() {
 		os.Exit(1)
 	}
 }
+
+// loadConfig loads the configuration for path and applies the command line
+// overrides
+func loadConfig(path string) (*config.Config, error) {
+	cfg, err := config.Load(path)
+	if err != nil {
+		return nil, err
+	}
+	if err := cfg.ApplyOverrides(overrides); err != nil {
+		return nil, err
+	}
+	return cfg, nil
+}
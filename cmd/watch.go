@@ -0,0 +1,68 @@
+package cmd
+
+import (
+	"context"
+	"os"
+	"os/signal"
+	"path/filepath"
+
+	"log/slog"
+
+	"github.com/spf13/cobra"
+
+	"github.com/rail44/mantra/internal/app"
+	"github.com/rail44/mantra/internal/config"
+)
+
+var watchConfig bool
+
+var watchCmd = &cobra.Command{
+	Use:   "watch [package-dir]",
+	Short: "Regenerate targets whenever the package's files change",
+	Long: `Generate the package, then keep watching its Go files and regenerate
+targets whose declaration or instruction changed after each save.
+
+Stop watching with Ctrl-C.`,
+	Args: cobra.MaximumNArgs(1),
+	Run: func(cmd *cobra.Command, args []string) {
+		pkgDir := "."
+		if len(args) > 0 {
+			pkgDir = args[0]
+		}
+
+		cfg, err := loadConfig(pkgDir)
+		if err != nil {
+			slog.Error("failed to load configuration", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+		setupLogging(cfg)
+
+		absPkgDir, err := filepath.Abs(pkgDir)
+		if err != nil {
+			slog.Error("failed to get absolute path", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+
+		ctx, stop := signal.NotifyContext(context.Background(), os.Interrupt)
+		defer stop()
+
+		// Reload mantra.toml on change, e.g. while tuning prompts and models
+		var reload func() (*config.Config, error)
+		if watchConfig {
+			reload = func() (*config.Config, error) {
+				return loadConfig(pkgDir)
+			}
+		}
+
+		generateApp := app.NewGenerateApp()
+		if err := generateApp.Watch(ctx, absPkgDir, cfg, reload); err != nil {
+			slog.Error("watch failed", slog.String("error", err.Error()))
+			os.Exit(1)
+		}
+	},
+}
+
+func init() {
+	watchCmd.Flags().BoolVar(&watchConfig, "watch-config", false, "Also watch mantra.toml and use the new configuration for the next run")
+	rootCmd.AddCommand(watchCmd)
+}
@@ -26,6 +26,20 @@ func ExtractTypeString(expr ast.Expr) string {
 		return "qualified.Type"
 	case *ast.ChanType:
 		return "chan " + ExtractTypeString(t.Value)
+	case *ast.Ellipsis:
+		// Variadic parameter. Formerly rendered as "any", which changed the
+		// checksums of variadic targets.
+		return "..." + ExtractTypeString(t.Elt)
+	case *ast.IndexExpr:
+		// Generic type with one type argument
+		return ExtractTypeString(t.X) + "[" + ExtractTypeString(t.Index) + "]"
+	case *ast.IndexListExpr:
+		// Generic type with several type arguments
+		args := make([]string, len(t.Indices))
+		for i, index := range t.Indices {
+			args[i] = ExtractTypeString(index)
+		}
+		return ExtractTypeString(t.X) + "[" + strings.Join(args, ", ") + "]"
 	case *ast.InterfaceType:
 		return "any"
 	case *ast.FuncType:
@@ -38,6 +52,7 @@ func ExtractTypeString(expr ast.Expr) string {
 // CleanTypeName removes pointers, slices, and other modifiers from type name
 func CleanTypeName(typeStr string) string {
 	// Remove common prefixes
+	typeStr = strings.TrimPrefix(typeStr, "...")
 	typeStr = strings.TrimPrefix(typeStr, "*")
 	typeStr = strings.TrimPrefix(typeStr, "[]")
 	typeStr = strings.TrimPrefix(typeStr, "chan ")
@@ -49,6 +64,11 @@ func CleanTypeName(typeStr string) string {
 		}
 	}
 
+	// Drop type arguments of generic types, e.g. the receiver type *Stack[T]
+	if idx := strings.Index(typeStr, "["); idx > 0 {
+		typeStr = typeStr[:idx]
+	}
+
 	// Skip qualified types (package.Type)
 	if strings.Contains(typeStr, ".") {
 		return ""
@@ -0,0 +1,96 @@
+package app
+
+import (
+	"fmt"
+	"log/slog"
+	"path/filepath"
+	"strings"
+
+	"github.com/rail44/mantra/internal/codegen"
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// Adopt registers existing implementations as generated without calling the AI.
+// Targets whose source body is already implemented are written to the
+// destination with a checksum, so later runs consider them up-to-date.
+// Targets that are current or still unimplemented are left as they are.
+func (a *GenerateApp) Adopt(pkgDir string, cfg *config.Config) error {
+	release, err := acquireLock(cfg.Dest)
+	if err != nil {
+		return err
+	}
+	defer release()
+
+	results, err := a.detectTargets(pkgDir, cfg)
+	if err != nil {
+		return err
+	}
+
+	gen := codegen.New(&codegen.Config{
+		Dest:            cfg.Dest,
+		PackageName:     cfg.GetPackageName(),
+		SourcePackage:   filepath.Base(pkgDir),
+		PlaceholderBody: cfg.PlaceholderBody,
+	})
+
+	adopted := 0
+	for _, result := range results {
+		fileInfo := result.FileInfo
+
+		if len(result.Statuses) == 0 {
+			a.copyFileWithoutTargets(fileInfo, gen)
+			continue
+		}
+
+		var fileResults []*parser.GenerationResult
+		fileAdopted := 0
+		for _, status := range result.Statuses {
+			if status.Status == detector.StatusCurrent || status.Target.HasPanic {
+				fileResults = append(fileResults, a.existingResult(status))
+				continue
+			}
+
+			fileAdopted++
+			a.logger.Info("adopting existing implementation",
+				slog.String("function", status.Target.GetDisplayName()),
+				slog.String("file", filepath.Base(fileInfo.FilePath)))
+			fileResults = append(fileResults, &parser.GenerationResult{
+				Target:         status.Target,
+				Success:        true,
+				Implementation: sourceBody(fileInfo, status.Target),
+			})
+		}
+
+		// Nothing changes for this file, keep it untouched
+		if fileAdopted == 0 {
+			continue
+		}
+
+		if err := gen.GenerateFile(fileInfo, fileResults); err != nil {
+			return fmt.Errorf("failed to generate %s: %w", filepath.Base(fileInfo.FilePath), err)
+		}
+		adopted += fileAdopted
+	}
+	gen.WarnMissingImports()
+
+	a.logger.Info(fmt.Sprintf("Adopted %d implementations", adopted))
+	return nil
+}
+
+// sourceBody returns the body of a target as written in its source file, without braces
+func sourceBody(fileInfo *parser.FileInfo, target *parser.Target) string {
+	body := target.FuncDecl.Body
+	if body == nil {
+		return ""
+	}
+
+	start := target.TokenSet.Position(body.Lbrace).Offset + 1
+	end := target.TokenSet.Position(body.Rbrace).Offset
+	if start > end || end > len(fileInfo.SourceContent) {
+		return ""
+	}
+
+	return strings.Trim(fileInfo.SourceContent[start:end], "\n")
+}
@@ -0,0 +1,95 @@
+package app
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"path/filepath"
+
+	"github.com/rail44/mantra/internal/config"
+)
+
+// PackageResult is the outcome of generating one package of a batch
+type PackageResult struct {
+	Dir string
+	Err error // nil if generation succeeded
+}
+
+// BatchResult is the outcome of generating several packages
+type BatchResult struct {
+	Packages []PackageResult
+}
+
+// Failed returns the number of packages whose generation failed
+func (r *BatchResult) Failed() int {
+	failed := 0
+	for _, pkg := range r.Packages {
+		if pkg.Err != nil {
+			failed++
+		}
+	}
+	return failed
+}
+
+// Write prints a summary of the batch, listing the failed packages
+func (r *BatchResult) Write(w io.Writer) {
+	fmt.Fprintf(w, "Packages: %d total, %d succeeded, %d failed\n",
+		len(r.Packages), len(r.Packages)-r.Failed(), r.Failed())
+	for _, pkg := range r.Packages {
+		if pkg.Err != nil {
+			fmt.Fprintf(w, "  %s: %v\n", pkg.Dir, pkg.Err)
+		}
+	}
+}
+
+// RunBatch generates each package with the configuration returned by load for
+// it, one package after another, and continues after failures. Directories
+// that are the output directory of another package in the batch are skipped,
+// and packages sharing an output directory fail instead of overwriting each
+// other's files.
+func (a *GenerateApp) RunBatch(ctx context.Context, pkgDirs []string, load func(pkgDir string) (*config.Config, error)) *BatchResult {
+	result := &BatchResult{}
+
+	// Load all configurations first to know every output directory
+	configs := make(map[string]*config.Config)
+	loadErrs := make(map[string]error)
+	dests := make(map[string]bool)
+	for _, pkgDir := range pkgDirs {
+		cfg, err := load(pkgDir)
+		if err != nil {
+			loadErrs[pkgDir] = err
+			continue
+		}
+		configs[pkgDir] = cfg
+		dests[filepath.Clean(cfg.Dest)] = true
+	}
+
+	destOwners := make(map[string]string)
+	for _, pkgDir := range pkgDirs {
+		if err, ok := loadErrs[pkgDir]; ok {
+			result.Packages = append(result.Packages, PackageResult{Dir: pkgDir, Err: err})
+			continue
+		}
+		cfg := configs[pkgDir]
+
+		// Generated packages also carry mantra comments
+		if dests[filepath.Clean(pkgDir)] {
+			a.logger.Info(fmt.Sprintf("Skipping %s, it is an output directory", pkgDir))
+			continue
+		}
+
+		dest := filepath.Clean(cfg.Dest)
+		if owner, ok := destOwners[dest]; ok {
+			err := fmt.Errorf("output directory %s is also used by %s, set a separate dest for each package", cfg.Dest, owner)
+			result.Packages = append(result.Packages, PackageResult{Dir: pkgDir, Err: err})
+			continue
+		}
+		destOwners[dest] = pkgDir
+
+		a.logger.Info(fmt.Sprintf("Generating %s", pkgDir))
+		err := a.Run(ctx, pkgDir, cfg)
+		result.Packages = append(result.Packages, PackageResult{Dir: pkgDir, Err: err})
+	}
+
+	return result
+}
@@ -0,0 +1,62 @@
+package app
+
+import (
+	"context"
+	"errors"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/config"
+)
+
+func TestRunBatch(t *testing.T) {
+	root := t.TempDir()
+	dest := filepath.Join(root, "generated")
+	pkgA := filepath.Join(root, "a")
+	pkgB := filepath.Join(root, "b")
+	broken := filepath.Join(root, "broken")
+
+	for _, dir := range []string{pkgA, pkgB} {
+		if err := os.MkdirAll(dir, 0755); err != nil {
+			t.Fatal(err)
+		}
+		source := "package " + filepath.Base(dir) + "\n\nfunc Helper() {}\n"
+		if err := os.WriteFile(filepath.Join(dir, "helper.go"), []byte(source), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	load := func(pkgDir string) (*config.Config, error) {
+		if pkgDir == broken {
+			return nil, errors.New("mantra.toml not found")
+		}
+		return &config.Config{Model: "test-model", URL: "http://localhost", Dest: dest, Plain: true}, nil
+	}
+
+	result := NewGenerateApp().RunBatch(context.Background(), []string{pkgA, pkgB, broken, dest}, load)
+
+	// The output directory is not generated as a package of its own
+	if len(result.Packages) != 3 {
+		t.Fatalf("Expected 3 package results, got %+v", result.Packages)
+	}
+	if result.Packages[0].Dir != pkgA || result.Packages[0].Err != nil {
+		t.Errorf("Expected %s to succeed, got %+v", pkgA, result.Packages[0])
+	}
+	if err := result.Packages[1].Err; err == nil || !strings.Contains(err.Error(), "also used by") {
+		t.Errorf("Expected %s to fail on the shared output directory, got %v", pkgB, err)
+	}
+	if result.Packages[2].Err == nil {
+		t.Errorf("Expected %s to fail to load", broken)
+	}
+	if result.Failed() != 2 {
+		t.Errorf("Failed() = %d, want 2", result.Failed())
+	}
+
+	var summary strings.Builder
+	result.Write(&summary)
+	if !strings.HasPrefix(summary.String(), "Packages: 3 total, 1 succeeded, 2 failed\n") {
+		t.Errorf("Unexpected summary:\n%s", summary.String())
+	}
+}
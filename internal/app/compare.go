@@ -0,0 +1,133 @@
+package app
+
+import (
+	"context"
+	"fmt"
+	goparser "go/parser"
+	"go/token"
+	"io"
+	"os"
+	"strings"
+	"time"
+
+	"github.com/rail44/mantra/internal/coder"
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// ModelRun holds the results of generating the targets of a file with one model
+type ModelRun struct {
+	Model   string
+	Results map[*parser.Target]*parser.GenerationResult
+}
+
+// Compare generates every target of file with each of the models and writes a
+// report comparing their implementations, validity and token usage to w.
+// No files are written.
+func (a *GenerateApp) Compare(ctx context.Context, file string, models []string, cfg *config.Config, w io.Writer) error {
+	fileInfo, err := parser.ParseFileInfoWithPrefix(file, cfg.InstructionPrefix)
+	if err != nil {
+		return err
+	}
+	if len(fileInfo.Targets) == 0 {
+		return fmt.Errorf("%s has no mantra targets", file)
+	}
+	content, err := os.ReadFile(file)
+	if err != nil {
+		return fmt.Errorf("failed to read file: %w", err)
+	}
+
+	var targets []coder.TargetContext
+	for i, target := range fileInfo.Targets {
+		targets = append(targets, coder.TargetContext{
+			Target:      target,
+			FileContent: string(content),
+			FileInfo:    fileInfo,
+			Index:       i + 1,
+		})
+	}
+
+	// The interactive UI cannot be restarted for every model
+	cfg.Plain = true
+
+	var runs []ModelRun
+	for _, model := range models {
+		a.logger.Info(fmt.Sprintf("Generating %d targets with %s", len(targets), model))
+
+		clientConfig := newClientConfig(cfg)
+		clientConfig.Model = model
+		results, err := coder.NewParallelCoder(clientConfig, cfg).ExecuteTargets(ctx, targets)
+		if err != nil {
+			return fmt.Errorf("failed to generate implementations with %s: %w", model, err)
+		}
+
+		run := ModelRun{Model: model, Results: make(map[*parser.Target]*parser.GenerationResult)}
+		for _, result := range results {
+			run.Results[result.Target] = result
+		}
+		runs = append(runs, run)
+	}
+
+	return writeComparison(w, fileInfo.Targets, runs)
+}
+
+// writeComparison writes the implementation of each target by each model,
+// followed by a summary line per model
+func writeComparison(w io.Writer, targets []*parser.Target, runs []ModelRun) error {
+	var sb strings.Builder
+
+	for _, target := range targets {
+		fmt.Fprintf(&sb, "=== %s\n", target.GetDisplayName())
+		for _, run := range runs {
+			result := run.Results[target]
+			switch {
+			case result == nil:
+				fmt.Fprintf(&sb, "--- %s: not generated\n", run.Model)
+			case !result.Success:
+				message := "unknown error"
+				if result.FailureReason != nil {
+					message = result.FailureReason.Message
+				}
+				fmt.Fprintf(&sb, "--- %s: failed: %s\n", run.Model, message)
+			default:
+				validity := "valid"
+				if err := parseBody(result.Implementation); err != nil {
+					validity = "invalid Go: " + err.Error()
+				}
+				fmt.Fprintf(&sb, "--- %s (%s, %d prompt / %d completion tokens, %s)\n%s\n",
+					run.Model, result.Duration, result.PromptTokens, result.CompletionTokens, validity,
+					result.Implementation)
+			}
+		}
+		sb.WriteString("\n")
+	}
+
+	sb.WriteString("Summary:\n")
+	for _, run := range runs {
+		var generated, valid, promptTokens, completionTokens int
+		var duration time.Duration
+		for _, result := range run.Results {
+			promptTokens += result.PromptTokens
+			completionTokens += result.CompletionTokens
+			duration += result.Duration
+			if !result.Success {
+				continue
+			}
+			generated++
+			if parseBody(result.Implementation) == nil {
+				valid++
+			}
+		}
+		fmt.Fprintf(&sb, "  %s: %d/%d generated, %d valid, %d prompt / %d completion tokens, %s\n",
+			run.Model, generated, len(targets), valid, promptTokens, completionTokens, duration)
+	}
+
+	_, err := io.WriteString(w, sb.String())
+	return err
+}
+
+// parseBody checks that a generated body parses as the body of a function
+func parseBody(body string) error {
+	_, err := goparser.ParseFile(token.NewFileSet(), "", "package p\nfunc _() {\n"+body+"\n}\n", 0)
+	return err
+}
@@ -0,0 +1,48 @@
+package app
+
+import (
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestWriteComparison(t *testing.T) {
+	add := &parser.Target{Name: "Add"}
+	sub := &parser.Target{Name: "Sub"}
+	runs := []ModelRun{
+		{
+			Model: "model-a",
+			Results: map[*parser.Target]*parser.GenerationResult{
+				add: {Target: add, Success: true, Implementation: "return a + b", Duration: time.Second, PromptTokens: 100, CompletionTokens: 10},
+				sub: {Target: sub, Success: true, Implementation: "return a -", Duration: time.Second, PromptTokens: 120, CompletionTokens: 12},
+			},
+		},
+		{
+			Model: "model-b",
+			Results: map[*parser.Target]*parser.GenerationResult{
+				add: {Target: add, Success: true, Implementation: "return b + a", Duration: 2 * time.Second, PromptTokens: 90, CompletionTokens: 8},
+				sub: {Target: sub, Success: false, FailureReason: &parser.FailureReason{Message: "timeout"}},
+			},
+		},
+	}
+
+	var out strings.Builder
+	if err := writeComparison(&out, []*parser.Target{add, sub}, runs); err != nil {
+		t.Fatal(err)
+	}
+	report := out.String()
+
+	for _, want := range []string{
+		"=== Add\n--- model-a (1s, 100 prompt / 10 completion tokens, valid)\nreturn a + b\n--- model-b (2s, 90 prompt / 8 completion tokens, valid)\nreturn b + a\n",
+		"--- model-a (1s, 120 prompt / 12 completion tokens, invalid Go:",
+		"--- model-b: failed: timeout\n",
+		"  model-a: 2/2 generated, 1 valid, 220 prompt / 22 completion tokens, 2s\n",
+		"  model-b: 1/2 generated, 1 valid, 90 prompt / 8 completion tokens, 2s\n",
+	} {
+		if !strings.Contains(report, want) {
+			t.Errorf("Expected report to contain %q, got:\n%s", want, report)
+		}
+	}
+}
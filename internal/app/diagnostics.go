@@ -0,0 +1,54 @@
+package app
+
+import (
+	"fmt"
+	"log/slog"
+
+	"golang.org/x/tools/go/packages"
+)
+
+// verifyGeneratedPackage type-checks the generated package and fails if any
+// errors are reported
+func (a *GenerateApp) verifyGeneratedPackage(destDir string) error {
+	diagnostics, err := checkGeneratedPackage(destDir)
+	if err != nil {
+		return err
+	}
+
+	if len(diagnostics) == 0 {
+		a.logger.Info("generated package has no diagnostics")
+		return nil
+	}
+
+	for _, d := range diagnostics {
+		a.logger.Error("diagnostic",
+			slog.String("position", d.Pos),
+			slog.String("message", d.Msg))
+	}
+
+	return fmt.Errorf("generated package has %d error diagnostic(s)", len(diagnostics))
+}
+
+// checkGeneratedPackage loads the package in destDir and returns its errors
+func checkGeneratedPackage(destDir string) ([]packages.Error, error) {
+	cfg := &packages.Config{
+		Mode: packages.NeedName |
+			packages.NeedFiles |
+			packages.NeedSyntax |
+			packages.NeedTypes |
+			packages.NeedTypesInfo,
+		Dir: destDir,
+	}
+
+	pkgs, err := packages.Load(cfg, ".")
+	if err != nil {
+		return nil, fmt.Errorf("failed to load generated package: %w", err)
+	}
+
+	var diagnostics []packages.Error
+	for _, pkg := range pkgs {
+		diagnostics = append(diagnostics, pkg.Errors...)
+	}
+
+	return diagnostics, nil
+}
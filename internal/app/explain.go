@@ -0,0 +1,102 @@
+package app
+
+import (
+	"context"
+	"fmt"
+	"io"
+	"log/slog"
+	"path/filepath"
+
+	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/llm"
+	"github.com/rail44/mantra/internal/parser"
+	"github.com/rail44/mantra/internal/prompt"
+)
+
+// explainTemperature keeps explanations close to the code
+const explainTemperature = 0.2
+
+// Explain asks the AI to explain the generated implementation of a function
+// in file and writes the explanation to w
+func (a *GenerateApp) Explain(ctx context.Context, file, function string, cfg *config.Config, w io.Writer) error {
+	fileInfo, err := parser.ParseFileInfoWithPrefix(file, cfg.InstructionPrefix)
+	if err != nil {
+		return fmt.Errorf("failed to parse %s: %w", file, err)
+	}
+
+	var target *parser.Target
+	for _, t := range fileInfo.Targets {
+		if t.Name == function || t.GetDisplayName() == function {
+			target = t
+			break
+		}
+	}
+	if target == nil {
+		return fmt.Errorf("target %s not found in %s", function, file)
+	}
+
+	generatedFile := filepath.Join(cfg.Dest, filepath.Base(file))
+	impls, err := detector.ExistingImplementations(generatedFile)
+	if err != nil {
+		return fmt.Errorf("failed to read generated file: %w", err)
+	}
+	impl, ok := impls[target.GetDisplayName()]
+	if !ok {
+		return fmt.Errorf("%s has no generated implementation of %s, run generate first", generatedFile, target.GetDisplayName())
+	}
+	if impl.Checksum != checksum.Calculate(target) {
+		a.logger.Warn("generated implementation is outdated", slog.String("function", target.GetDisplayName()))
+	}
+
+	client, err := llm.NewClient(newClientConfig(cfg), nil, a.logger)
+	if err != nil {
+		return err
+	}
+
+	explanation, err := explainGeneration(ctx, client, target, impl.Body)
+	if err != nil {
+		return err
+	}
+
+	fmt.Fprintln(w, explanation)
+	return nil
+}
+
+// explainResults writes an explanation of each successful generation result to w.
+// Failures to explain are logged, as the implementations are already written.
+func (a *GenerateApp) explainResults(ctx context.Context, clientConfig *llm.ClientConfig, results []*parser.GenerationResult, w io.Writer) {
+	client, err := llm.NewClient(clientConfig, nil, a.logger)
+	if err != nil {
+		a.logger.Warn("failed to create client for explanations", slog.String("error", err.Error()))
+		return
+	}
+
+	for _, result := range results {
+		if !result.Success {
+			continue
+		}
+
+		explanation, err := explainGeneration(ctx, client, result.Target, result.Implementation)
+		if err != nil {
+			a.logger.Warn("failed to explain implementation",
+				slog.String("function", result.Target.GetDisplayName()),
+				slog.String("error", err.Error()))
+			continue
+		}
+		fmt.Fprintf(w, "=== %s\n%s\n\n", result.Target.GetDisplayName(), explanation)
+	}
+}
+
+// explainGeneration asks the AI why body implements the target the way it does
+func explainGeneration(ctx context.Context, client *llm.Client, target *parser.Target, body string) (string, error) {
+	client.SetSystemPrompt(prompt.ExplainSystemPrompt)
+	client.SetTemperature(explainTemperature)
+
+	explanation, err := client.Generate(ctx, prompt.BuildExplainPrompt(target, body))
+	if err != nil {
+		return "", fmt.Errorf("failed to explain %s: %w", target.GetDisplayName(), err)
+	}
+	return explanation, nil
+}
@@ -0,0 +1,94 @@
+package app
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/llm"
+	"github.com/rail44/mantra/internal/parser"
+	"github.com/rail44/mantra/internal/prompt"
+)
+
+func TestExplain(t *testing.T) {
+	var received llm.OpenAIRequest
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		if err := json.NewDecoder(r.Body).Decode(&received); err != nil {
+			t.Errorf("failed to decode request: %v", err)
+		}
+		fmt.Fprint(w, `{"choices": [{"message": {"role": "assistant", "content": "It adds the numbers."}}]}`)
+	}))
+	defer server.Close()
+
+	pkgDir := t.TempDir()
+	destDir := filepath.Join(t.TempDir(), "generated")
+	sourceFile := filepath.Join(pkgDir, "math.go")
+	source := `package math
+
+// mantra: add two numbers
+func Add(a, b int) int {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+	targets, err := parser.ParseFile(sourceFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	generated := fmt.Sprintf(`package generated
+
+// mantra: add two numbers
+%s
+func Add(a, b int) int {
+	return a + b
+}
+`, checksum.FormatComment(checksum.Calculate(targets[0])))
+	if err := os.MkdirAll(destDir, 0755); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(destDir, "math.go"), []byte(generated), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	cfg := &config.Config{Model: "test-model", URL: server.URL, Dest: destDir}
+	var out strings.Builder
+	if err := NewGenerateApp().Explain(context.Background(), sourceFile, "Add", cfg, &out); err != nil {
+		t.Fatalf("Explain failed: %v", err)
+	}
+
+	if out.String() != "It adds the numbers.\n" {
+		t.Errorf("Unexpected explanation %q", out.String())
+	}
+	if len(received.Messages) != 2 || received.Messages[0].Content != prompt.ExplainSystemPrompt {
+		t.Fatalf("Expected the explain system prompt and one user message, got %+v", received.Messages)
+	}
+	if user := received.Messages[1].Content; !strings.Contains(user, "add two numbers") || !strings.Contains(user, "return a + b") {
+		t.Errorf("Expected the prompt to include the instruction and body, got %q", user)
+	}
+}
+
+func TestExplain_TargetNotFound(t *testing.T) {
+	pkgDir := t.TempDir()
+	sourceFile := filepath.Join(pkgDir, "math.go")
+	source := "package math\n\n// mantra: add two numbers\nfunc Add(a, b int) int {\n\tpanic(\"not implemented\")\n}\n"
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	cfg := &config.Config{Model: "test-model", URL: "http://localhost", Dest: filepath.Join(pkgDir, "generated")}
+	err := NewGenerateApp().Explain(context.Background(), sourceFile, "Sub", cfg, &strings.Builder{})
+	if err == nil || !strings.Contains(err.Error(), "target Sub not found") {
+		t.Errorf("Expected target not found error, got %v", err)
+	}
+}
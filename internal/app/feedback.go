@@ -0,0 +1,125 @@
+package app
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"log/slog"
+	"os/exec"
+	"strings"
+
+	"github.com/rail44/mantra/internal/codegen"
+	"github.com/rail44/mantra/internal/coder"
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/llm"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// maxTestOutput limits the test output included in prompts
+const maxTestOutput = 4000
+
+// refineWithFeedback runs the feedback test command over the generated package
+// and regenerates the targets of this run with the test output, until the tests
+// pass or the configured number of iterations is used up. It returns the final
+// generation results.
+func (a *GenerateApp) refineWithFeedback(ctx context.Context, results []*detector.FileDetectionResult, targets []coder.TargetContext, allResults []*parser.GenerationResult, clientConfig *llm.ClientConfig, gen *codegen.Generator, cfg *config.Config) ([]*parser.GenerationResult, error) {
+	feedback := cfg.Feedback
+	for iteration := 0; ; iteration++ {
+		output, err := runTestCommand(ctx, feedback.Command(), cfg.Dest)
+		if err == nil {
+			a.logger.Info("tests passed", slog.Int("iterations", iteration))
+			return allResults, nil
+		}
+		if iteration == feedback.Iterations() {
+			a.logger.Warn("tests still failing, giving up", slog.Int("iterations", iteration))
+			return allResults, nil
+		}
+		a.logger.Warn("tests failed, regenerating with test output",
+			slog.Int("iteration", iteration+1),
+			slog.String("error", err.Error()))
+
+		// Retry the targets that were generated in this run
+		byTarget := make(map[string]*parser.GenerationResult)
+		for _, result := range allResults {
+			byTarget[planKey(result.Target.FilePath, result.Target.GetDisplayName())] = result
+		}
+		var retry []coder.TargetContext
+		for _, tc := range targets {
+			result := byTarget[planKey(tc.Target.FilePath, tc.Target.GetDisplayName())]
+			if result == nil || !result.Success {
+				continue
+			}
+			tc.TestFailure = &coder.TestFailure{Code: result.Implementation, Output: output}
+			retry = append(retry, tc)
+		}
+		if len(retry) == 0 {
+			return allResults, nil
+		}
+
+		// The retries share the budget with the earlier attempts
+		var spent float64
+		for _, result := range allResults {
+			spent += cfg.EstimateCost(result.PromptTokens, result.CompletionTokens)
+		}
+		parallelCoder := coder.NewParallelCoder(clientConfig, cfg)
+		parallelCoder.SetSpent(spent)
+		retried, err := parallelCoder.ExecuteTargets(ctx, retry)
+		if err != nil {
+			return nil, fmt.Errorf("failed to regenerate implementations: %w", err)
+		}
+
+		// Keep the previous implementation of targets that failed to regenerate,
+		// counting the usage of every attempt
+		for _, result := range retried {
+			key := planKey(result.Target.FilePath, result.Target.GetDisplayName())
+			previous := byTarget[key]
+			if result.Success {
+				addUsage(result, previous)
+				byTarget[key] = result
+			} else {
+				addUsage(previous, result)
+			}
+		}
+		allResults = allResults[:0:0]
+		for _, tc := range targets {
+			if result := byTarget[planKey(tc.Target.FilePath, tc.Target.GetDisplayName())]; result != nil {
+				allResults = append(allResults, result)
+			}
+		}
+
+		if err := a.writeGeneratedFiles(results, allResults, gen); err != nil {
+			return nil, err
+		}
+	}
+}
+
+// addUsage adds the token, request and timing counts of from onto to
+func addUsage(to, from *parser.GenerationResult) {
+	to.PromptTokens += from.PromptTokens
+	to.CompletionTokens += from.CompletionTokens
+	to.Requests += from.Requests
+	to.RateLimited += from.RateLimited
+	to.RequestTime += from.RequestTime
+}
+
+// runTestCommand runs command in dir and returns its combined output,
+// truncated to maxTestOutput bytes from the end
+func runTestCommand(ctx context.Context, command []string, dir string) (string, error) {
+	if len(command) == 0 {
+		return "", fmt.Errorf("test command is empty")
+	}
+
+	var output bytes.Buffer
+	cmd := exec.CommandContext(ctx, command[0], command[1:]...)
+	cmd.Dir = dir
+	cmd.Stdout = &output
+	cmd.Stderr = &output
+
+	err := cmd.Run()
+	text := strings.TrimSpace(output.String())
+	if len(text) > maxTestOutput {
+		text = "..." + text[len(text)-maxTestOutput:]
+	}
+	return text, err
+}
@@ -0,0 +1,133 @@
+package app
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"strings"
+	"sync"
+	"testing"
+
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/llm"
+)
+
+func TestRefineWithFeedback(t *testing.T) {
+	tests := []struct {
+		name          string
+		passOnAttempt int // Implementation request that returns the passing body (0 for never)
+		maxIterations int
+		wantAttempts  int
+	}{
+		{name: "stops once tests pass", passOnAttempt: 2, maxIterations: 3, wantAttempts: 2},
+		{name: "passes on first attempt", passOnAttempt: 1, maxIterations: 3, wantAttempts: 1},
+		{name: "gives up after max iterations", passOnAttempt: 0, maxIterations: 2, wantAttempts: 3},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			t.Setenv("XDG_DATA_HOME", t.TempDir())
+
+			var mu sync.Mutex
+			var prompts []string // User messages of the implementation requests
+			server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+				var req llm.OpenAIRequest
+				if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+					t.Errorf("failed to decode request: %v", err)
+				}
+
+				// Only the implementation phase can check code
+				code := "return 0"
+				for _, tool := range req.Tools {
+					if tool.Function.Name != "check_code" {
+						continue
+					}
+					var user []string
+					for _, message := range req.Messages {
+						if message.Role == llm.RoleUser {
+							user = append(user, message.Content)
+						}
+					}
+					mu.Lock()
+					prompts = append(prompts, strings.Join(user, "\n"))
+					if len(prompts) == tt.passOnAttempt {
+						code = "return 1"
+					}
+					mu.Unlock()
+				}
+
+				args, _ := json.Marshal(map[string]any{"success": true, "constants": []any{}, "code": code})
+				fmt.Fprintf(w, `{"choices": [{"message": {"role": "assistant", "tool_calls": [{"id": "call_1", "type": "function", "function": {"name": "result", "arguments": %s}}]}}]}`, args)
+			}))
+			defer server.Close()
+
+			pkgDir := t.TempDir()
+			destDir := filepath.Join(pkgDir, "generated")
+			if err := os.WriteFile(filepath.Join(pkgDir, "go.mod"), []byte("module numbers\n\ngo 1.21\n"), 0644); err != nil {
+				t.Fatal(err)
+			}
+			source := "package numbers\n\n// mantra: return one\nfunc One() int {\n\tpanic(\"not implemented\")\n}\n"
+			if err := os.WriteFile(filepath.Join(pkgDir, "numbers.go"), []byte(source), 0644); err != nil {
+				t.Fatal(err)
+			}
+
+			cfg := &config.Config{
+				Model: "test-model",
+				URL:   server.URL,
+				Dest:  destDir,
+				Plain: true,
+				Feedback: &config.FeedbackConfig{
+					Enabled:       true,
+					TestCommand:   []string{"sh", "-c", `grep -q "return 1" numbers.go || { echo "FAIL: One() = 0, want 1"; exit 1; }`},
+					MaxIterations: tt.maxIterations,
+				},
+			}
+			if err := NewGenerateApp().Run(context.Background(), pkgDir, cfg); err != nil {
+				t.Fatalf("Run failed: %v", err)
+			}
+
+			if len(prompts) != tt.wantAttempts {
+				t.Fatalf("Expected %d implementation attempts, got %d", tt.wantAttempts, len(prompts))
+			}
+			if strings.Contains(prompts[0], "want 1") {
+				t.Error("Expected no test output in the first attempt")
+			}
+			for i, prompt := range prompts[1:] {
+				if !strings.Contains(prompt, "FAIL: One() = 0, want 1") || !strings.Contains(prompt, "return 0") {
+					t.Errorf("Expected attempt %d to include the failing code and test output, got:\n%s", i+2, prompt)
+				}
+			}
+
+			generated, err := os.ReadFile(filepath.Join(destDir, "numbers.go"))
+			if err != nil {
+				t.Fatal(err)
+			}
+			if passed := strings.Contains(string(generated), "return 1"); passed != (tt.passOnAttempt > 0) {
+				t.Errorf("Expected passing body written: %v, got:\n%s", tt.passOnAttempt > 0, generated)
+			}
+		})
+	}
+}
+
+func TestRunTestCommand(t *testing.T) {
+	dir := t.TempDir()
+
+	output, err := runTestCommand(context.Background(), []string{"sh", "-c", "echo ok"}, dir)
+	if err != nil || output != "ok" {
+		t.Errorf("Expected passing command with output ok, got %q, %v", output, err)
+	}
+
+	output, err = runTestCommand(context.Background(), []string{"sh", "-c", "echo 'FAIL: TestAdd' >&2; exit 1"}, dir)
+	if err == nil || output != "FAIL: TestAdd" {
+		t.Errorf("Expected failing command with its stderr, got %q, %v", output, err)
+	}
+
+	output, _ = runTestCommand(context.Background(), []string{"sh", "-c", "yes x | head -c 5000; echo end"}, dir)
+	if len(output) != maxTestOutput+len("...") || !strings.HasSuffix(output, "end") {
+		t.Errorf("Expected output truncated to the last %d bytes, got %d bytes", maxTestOutput, len(output))
+	}
+}
@@ -32,33 +32,138 @@ func NewGenerateApp() *GenerateApp {
 
 // Run executes the generate command
 func (a *GenerateApp) Run(ctx context.Context, pkgDir string, cfg *config.Config) error {
+	// Prevent concurrent runs from clobbering the generated files
+	release, err := acquireLock(cfg.Dest)
+	if err != nil {
+		return err
+	}
+	defer release()
+
 	// Detect targets
-	results, err := a.detectTargets(pkgDir, cfg.Dest)
+	results, err := a.detectTargets(pkgDir, cfg)
 	if err != nil {
 		return err
 	}
 
+	// Regenerate up-to-date targets as well when forced
+	if cfg.Force {
+		forceRegeneration(results)
+	}
+
+	// Refuse unexpectedly large batches, e.g. from a stray mantra comment
+	if !cfg.AllowLargeBatch {
+		if err := checkTargetCounts(results, cfg.GetMaxTargetsPerFile()); err != nil {
+			return err
+		}
+	}
+
+	// Keep the destination files to print what the run changed
+	var before map[string]string
+	if cfg.OutputMode == OutputFull || cfg.OutputMode == OutputDiff {
+		if before, err = readGoSources(cfg.Dest); err != nil {
+			return err
+		}
+	}
+
 	// Check if processing is needed
+	var generated []*parser.GenerationResult
 	if !a.needsProcessing(results) {
 		a.logger.Info("all files are up-to-date, nothing to generate")
-		return nil
+	} else {
+		// Setup AI client configuration and generator
+		clientConfig, gen, err := a.setupAIClient(cfg, pkgDir)
+		if err != nil {
+			return err
+		}
+
+		// Process all targets
+		generated, err = a.processAllTargets(ctx, results, clientConfig, gen, cfg)
+		if err != nil {
+			return err
+		}
+		gen.WarnMissingImports()
+
+		a.logger.Info("package generation complete")
+
+		// Explain the new implementations, keeping stdout for the output mode
+		if cfg.Explain {
+			explainOut := os.Stdout
+			if cfg.OutputMode != "" {
+				explainOut = os.Stderr
+			}
+			a.explainResults(ctx, clientConfig, generated, explainOut)
+		}
 	}
 
-	// Setup AI client configuration and generator
-	clientConfig, gen, err := a.setupAIClient(cfg, pkgDir)
-	if err != nil {
-		return err
+	summary := summarizeMetrics(results, generated)
+	summary.Write(os.Stderr)
+	if cfg.Stats {
+		summary.WriteStats(os.Stderr)
 	}
 
-	// Process all targets
-	if err := a.processAllTargets(ctx, results, clientConfig, gen, cfg); err != nil {
+	// Write the summary report
+	if cfg.ReportPath != "" {
+		if err := a.writeReport(cfg.ReportPath, pkgDir, cfg.Dest, results, generated); err != nil {
+			return err
+		}
+	}
+
+	// Print what was generated instead of progress only
+	if err := a.writeOutput(os.Stdout, cfg.OutputMode, pkgDir, cfg.Dest, before, results, generated); err != nil {
 		return err
 	}
 
-	a.logger.Info("package generation complete")
+	// Verify the generated package compiles
+	if cfg.FailOnDiagnostics {
+		return a.verifyGeneratedPackage(cfg.Dest)
+	}
+
+	return nil
+}
+
+// TooManyTargetsError is returned when a file has more pending targets than
+// the configured limit
+type TooManyTargetsError struct {
+	FilePath string
+	Count    int
+	Limit    int
+}
+
+func (e *TooManyTargetsError) Error() string {
+	return fmt.Sprintf("%s has %d targets to generate (limit %d); pass --allow-large-batch to generate them anyway or raise max_targets_per_file in mantra.toml",
+		e.FilePath, e.Count, e.Limit)
+}
+
+// checkTargetCounts returns a TooManyTargetsError for the first file with
+// more than limit new or outdated targets
+func checkTargetCounts(results []*detector.FileDetectionResult, limit int) error {
+	for _, result := range results {
+		pending := 0
+		for _, status := range result.Statuses {
+			if status.Status != detector.StatusCurrent {
+				pending++
+			}
+		}
+		if pending > limit {
+			return &TooManyTargetsError{FilePath: result.FileInfo.FilePath, Count: pending, Limit: limit}
+		}
+	}
 	return nil
 }
 
+// forceRegeneration marks up-to-date targets as outdated so they are generated again
+func forceRegeneration(results []*detector.FileDetectionResult) {
+	for _, result := range results {
+		for _, status := range result.Statuses {
+			if status.Status == detector.StatusCurrent {
+				status.Status = detector.StatusOutdated
+				status.ExistingImpl = ""
+				status.ExistingImports = nil
+			}
+		}
+	}
+}
+
 // needsProcessing checks if any targets need generation or files need copying
 func (a *GenerateApp) needsProcessing(results []*detector.FileDetectionResult) bool {
 	for _, result := range results {
@@ -77,16 +182,27 @@ func (a *GenerateApp) needsProcessing(results []*detector.FileDetectionResult) b
 }
 
 // detectTargets detects targets and provides logging summary
-func (a *GenerateApp) detectTargets(pkgDir, destDir string) ([]*detector.FileDetectionResult, error) {
+func (a *GenerateApp) detectTargets(pkgDir string, cfg *config.Config) ([]*detector.FileDetectionResult, error) {
 	a.logger.Info("detecting targets in package", slog.String("package", filepath.Base(pkgDir)))
-	results, err := detector.DetectPackageTargets(pkgDir, destDir)
+	maxFileSize := cfg.GetMaxFileSizeBytes()
+	if cfg.AllowLargeBatch {
+		maxFileSize = 0
+	}
+	results, err := detector.DetectPackageTargets(pkgDir, cfg.Dest, maxFileSize, cfg.InstructionPrefix)
 	if err != nil {
 		return nil, fmt.Errorf("failed to detect targets: %w", err)
 	}
 
 	// Summary of detection
-	var ungenerated, outdated, current, filesWithoutTargets int
+	var ungenerated, outdated, current, skipped, filesWithoutTargets int
 	for _, result := range results {
+		for _, name := range result.FileInfo.Skipped {
+			skipped++
+			a.logger.Info("target skipped by mantra:skip",
+				slog.String("function", name),
+				slog.String("file", filepath.Base(result.FileInfo.FilePath)))
+		}
+
 		if len(result.Statuses) == 0 {
 			filesWithoutTargets++
 			continue
@@ -123,12 +239,15 @@ func (a *GenerateApp) detectTargets(pkgDir, destDir string) ([]*detector.FileDet
 	if current > 0 {
 		summaryParts = append(summaryParts, fmt.Sprintf("%d current", current))
 	}
+	if skipped > 0 {
+		summaryParts = append(summaryParts, fmt.Sprintf("%d skipped", skipped))
+	}
 	if filesWithoutTargets > 0 {
 		summaryParts = append(summaryParts, fmt.Sprintf("%d files without targets", filesWithoutTargets))
 	}
 
 	summary := fmt.Sprintf("Found: %s", strings.Join(summaryParts, ", "))
-	if ungenerated == 0 && outdated == 0 && filesWithoutTargets == 0 {
+	if ungenerated == 0 && outdated == 0 && skipped == 0 && filesWithoutTargets == 0 {
 		summary = "All targets up-to-date"
 	}
 
@@ -140,18 +259,7 @@ func (a *GenerateApp) detectTargets(pkgDir, destDir string) ([]*detector.FileDet
 
 // setupAIClient initializes AI client configuration and code generator
 func (a *GenerateApp) setupAIClient(cfg *config.Config, pkgDir string) (*llm.ClientConfig, *codegen.Generator, error) {
-	// Initialize AI client configuration
-	clientConfig := &llm.ClientConfig{
-		URL:     cfg.URL,
-		APIKey:  cfg.GetAPIKey(),
-		Model:   cfg.Model,
-		Timeout: 5 * time.Minute,
-	}
-
-	// Set OpenRouter providers if configured
-	if cfg.OpenRouter != nil && len(cfg.OpenRouter.Providers) > 0 {
-		clientConfig.Provider = cfg.OpenRouter.Providers
-	}
+	clientConfig := newClientConfig(cfg)
 
 	// Log which provider we're using
 	a.logger.Info("using AI provider",
@@ -159,19 +267,47 @@ func (a *GenerateApp) setupAIClient(cfg *config.Config, pkgDir string) (*llm.Cli
 		slog.String("model", cfg.Model))
 
 	gen := codegen.New(&codegen.Config{
-		Dest:          cfg.Dest,
-		PackageName:   cfg.GetPackageName(),
-		SourcePackage: filepath.Base(pkgDir),
+		Dest:            cfg.Dest,
+		PackageName:     cfg.GetPackageName(),
+		SourcePackage:   filepath.Base(pkgDir),
+		PlaceholderBody: cfg.PlaceholderBody,
 	})
 
 	return clientConfig, gen, nil
 }
 
+// newClientConfig creates the AI client configuration from the mantra configuration
+func newClientConfig(cfg *config.Config) *llm.ClientConfig {
+	clientConfig := &llm.ClientConfig{
+		URL:     cfg.URL,
+		APIKey:  cfg.GetAPIKey(),
+		Model:   cfg.Model,
+		Timeout: 5 * time.Minute,
+		Stop:    cfg.Stop,
+		Seed:    cfg.Seed,
+	}
+
+	// Set OpenRouter routing if configured
+	if openRouter := cfg.OpenRouter; openRouter != nil {
+		if len(openRouter.Providers) > 0 || len(openRouter.Order) > 0 || openRouter.AllowFallbacks != nil {
+			clientConfig.Provider = &llm.ProviderSpec{
+				Only:           openRouter.Providers,
+				Order:          openRouter.Order,
+				AllowFallbacks: openRouter.AllowFallbacks,
+			}
+		}
+		clientConfig.Transforms = openRouter.Transforms
+	}
+
+	return clientConfig
+}
+
 // processAllTargets processes all files, generating implementations for targets and copying files without targets
-func (a *GenerateApp) processAllTargets(ctx context.Context, results []*detector.FileDetectionResult, clientConfig *llm.ClientConfig, gen *codegen.Generator, cfg *config.Config) error {
+// and returns the generation results
+func (a *GenerateApp) processAllTargets(ctx context.Context, results []*detector.FileDetectionResult, clientConfig *llm.ClientConfig, gen *codegen.Generator, cfg *config.Config) ([]*parser.GenerationResult, error) {
 	// Prepare stub files for all targets before generation
 	if err := a.prepareStubFiles(results, gen); err != nil {
-		return fmt.Errorf("failed to prepare stub files: %w", err)
+		return nil, fmt.Errorf("failed to prepare stub files: %w", err)
 	}
 
 	// Collect targets and copy files without targets
@@ -179,19 +315,72 @@ func (a *GenerateApp) processAllTargets(ctx context.Context, results []*detector
 
 	// Skip if no targets need generation
 	if len(targets) == 0 {
-		return nil
+		return nil, nil
 	}
 
 	// Create and execute target executor
 	// Now PackageLoader will see the prepared files with correct structure
 	parallelCoder := coder.NewParallelCoder(clientConfig, cfg)
+	if cfg.IncrementalWrite {
+		parallelCoder.SetResultHandler(a.incrementalWriter(results, gen))
+	}
 	allResults, err := parallelCoder.ExecuteTargets(ctx, targets)
 	if err != nil {
-		return fmt.Errorf("failed to generate implementations: %w", err)
+		return nil, fmt.Errorf("failed to generate implementations: %w", err)
 	}
 
+	a.recordHistory(results, allResults)
+	a.warnSkippedForBudget(allResults)
+
 	// Write generated files
-	return a.writeGeneratedFiles(results, allResults, gen)
+	if err := a.writeGeneratedFiles(results, allResults, gen); err != nil {
+		return nil, err
+	}
+
+	// Regenerate with the output of failing tests
+	if cfg.Feedback != nil && cfg.Feedback.Enabled {
+		return a.refineWithFeedback(ctx, results, targets, allResults, clientConfig, gen, cfg)
+	}
+	return allResults, nil
+}
+
+// warnSkippedForBudget lists the targets left ungenerated because the cost budget was spent
+func (a *GenerateApp) warnSkippedForBudget(allResults []*parser.GenerationResult) {
+	var skipped []string
+	for _, result := range allResults {
+		if result.FailureReason != nil && result.FailureReason.Phase == coder.PhaseBudget {
+			skipped = append(skipped, result.Target.GetDisplayName())
+		}
+	}
+	if len(skipped) > 0 {
+		fmt.Fprintf(os.Stderr, "Warning: cost budget reached, %d target(s) left ungenerated: %s\n", len(skipped), strings.Join(skipped, ", "))
+	}
+}
+
+// incrementalWriter returns a result handler that rewrites the generated file of
+// each completed target, so that an interrupted run keeps finished targets.
+// Targets still pending are written as failed and generated again on the next run.
+func (a *GenerateApp) incrementalWriter(results []*detector.FileDetectionResult, gen *codegen.Generator) func(*parser.GenerationResult) {
+	detectionByFile := make(map[string]*detector.FileDetectionResult)
+	for _, result := range results {
+		detectionByFile[result.FileInfo.FilePath] = result
+	}
+	completed := make(map[string][]*parser.GenerationResult)
+
+	return func(result *parser.GenerationResult) {
+		filePath := result.Target.FilePath
+		completed[filePath] = append(completed[filePath], result)
+
+		detection, ok := detectionByFile[filePath]
+		if !ok {
+			return
+		}
+		if err := gen.GenerateFile(detection.FileInfo, a.collectFileGenerationResults(detection, completed[filePath])); err != nil {
+			a.logger.Warn("failed to write generated file incrementally",
+				slog.String("file", filepath.Base(filePath)),
+				slog.String("error", err.Error()))
+		}
+	}
 }
 
 // prepareStubFiles prepares stub files for all targets before generation
@@ -335,6 +524,7 @@ func (a *GenerateApp) collectFileGenerationResults(detectionResult *detector.Fil
 				Target:         status.Target,
 				Success:        true,
 				Implementation: status.ExistingImpl,
+				Imports:        status.ExistingImports,
 				Duration:       0, // No generation time for existing implementations
 			})
 		}
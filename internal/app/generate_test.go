@@ -0,0 +1,181 @@
+package app
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"testing"
+
+	"github.com/rail44/mantra/internal/codegen"
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestForceRegeneration(t *testing.T) {
+	current := &detector.TargetStatus{
+		Target:       &parser.Target{Name: "Add"},
+		Status:       detector.StatusCurrent,
+		ExistingImpl: "return a + b",
+	}
+	ungenerated := &detector.TargetStatus{
+		Target: &parser.Target{Name: "Sub"},
+		Status: detector.StatusUngenerated,
+	}
+	results := []*detector.FileDetectionResult{
+		{Statuses: []*detector.TargetStatus{current, ungenerated}},
+	}
+
+	a := NewGenerateApp()
+	if !a.needsProcessing(results) {
+		t.Fatal("Expected processing to be needed")
+	}
+
+	forceRegeneration(results)
+
+	if current.Status != detector.StatusOutdated || current.ExistingImpl != "" {
+		t.Errorf("Expected current target to be regenerated, got %+v", current)
+	}
+	if ungenerated.Status != detector.StatusUngenerated {
+		t.Errorf("Expected ungenerated target to be unchanged, got %+v", ungenerated)
+	}
+}
+
+func TestCheckTargetCounts(t *testing.T) {
+	statuses := func(pending, current int) []*detector.TargetStatus {
+		var s []*detector.TargetStatus
+		for i := 0; i < pending; i++ {
+			s = append(s, &detector.TargetStatus{Status: detector.StatusUngenerated})
+		}
+		for i := 0; i < current; i++ {
+			s = append(s, &detector.TargetStatus{Status: detector.StatusCurrent})
+		}
+		return s
+	}
+
+	results := []*detector.FileDetectionResult{
+		{FileInfo: &parser.FileInfo{FilePath: "small.go"}, Statuses: statuses(2, 10)},
+		{FileInfo: &parser.FileInfo{FilePath: "large.go"}, Statuses: statuses(4, 0)},
+	}
+
+	if err := checkTargetCounts(results, 4); err != nil {
+		t.Errorf("Expected no error at the limit, got %v", err)
+	}
+
+	err := checkTargetCounts(results, 3)
+	var tooMany *TooManyTargetsError
+	if !errors.As(err, &tooMany) {
+		t.Fatalf("Expected TooManyTargetsError, got %v", err)
+	}
+	if tooMany.FilePath != "large.go" || tooMany.Count != 4 || tooMany.Limit != 3 {
+		t.Errorf("Unexpected error details: %+v", tooMany)
+	}
+}
+
+func TestRegenerationIsIdempotent(t *testing.T) {
+	pkgDir := t.TempDir()
+	destDir := filepath.Join(t.TempDir(), "generated")
+
+	source := `package dice
+
+// mantra: roll a die with the given number of sides
+func Roll(sides int) int {
+	panic("not implemented")
+}
+
+type Cup struct{ dice []int }
+
+// mantra: sum of all dice in the cup
+func (c *Cup) Total() int {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(filepath.Join(pkgDir, "dice.go"), []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	gen := codegen.New(&codegen.Config{Dest: destDir, PackageName: "generated", SourcePackage: "dice"})
+	a := NewGenerateApp()
+
+	// First run generates both targets
+	results, err := detector.DetectPackageTargets(pkgDir, destDir, 1<<20, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	roll, total := results[0].Statuses[0].Target, results[0].Statuses[1].Target
+	generated := []*parser.GenerationResult{
+		{Target: roll, Success: true, Implementation: "return rand.IntN(sides) + 1", Imports: []string{"math/rand/v2"}},
+		{Target: total, Success: true, Implementation: "sum := 0\nfor _, d := range c.dice {\n\tsum += d\n}\nreturn sum"},
+	}
+	if err := gen.GenerateFile(results[0].FileInfo, generated); err != nil {
+		t.Fatal(err)
+	}
+	first, err := os.ReadFile(filepath.Join(destDir, "dice.go"))
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// Second run finds both targets up-to-date and rewrites the file from them
+	results, err = detector.DetectPackageTargets(pkgDir, destDir, 1<<20, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if a.needsProcessing(results) {
+		t.Fatalf("Expected all targets to be current after the first run:\n%s", first)
+	}
+	if err := gen.GenerateFile(results[0].FileInfo, a.collectFileGenerationResults(results[0], nil)); err != nil {
+		t.Fatal(err)
+	}
+	second, err := os.ReadFile(filepath.Join(destDir, "dice.go"))
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	if string(first) != string(second) {
+		t.Errorf("Expected identical output on regeneration\nfirst:\n%s\nsecond:\n%s", first, second)
+	}
+}
+
+func TestIncrementalWriter(t *testing.T) {
+	pkgDir := t.TempDir()
+	destDir := filepath.Join(t.TempDir(), "generated")
+
+	source := `package math
+
+// mantra: add two numbers
+func Add(a, b int) int {
+	panic("not implemented")
+}
+
+// mantra: subtract b from a
+func Sub(a, b int) int {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(filepath.Join(pkgDir, "math.go"), []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	results, err := detector.DetectPackageTargets(pkgDir, destDir, 1<<20, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	gen := codegen.New(&codegen.Config{Dest: destDir, PackageName: "generated", SourcePackage: "math"})
+	write := NewGenerateApp().incrementalWriter(results, gen)
+
+	// Only Add completes before the run is interrupted
+	add := results[0].Statuses[0].Target
+	write(&parser.GenerationResult{Target: add, Success: true, Implementation: "return a + b"})
+
+	// The next run resumes with Sub only
+	results, err = detector.DetectPackageTargets(pkgDir, destDir, 1<<20, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	statuses := results[0].Statuses
+	if statuses[0].Status != detector.StatusCurrent || statuses[0].ExistingImpl == "" {
+		t.Errorf("Expected Add to be current, got %+v", statuses[0])
+	}
+	if statuses[1].Status != detector.StatusUngenerated {
+		t.Errorf("Expected Sub to be pending, got %+v", statuses[1])
+	}
+}
@@ -0,0 +1,49 @@
+package app
+
+import (
+	"log/slog"
+	"time"
+
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/history"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// recordHistory appends successful generations to the generation history.
+// History is informational, so failures are only logged.
+func (a *GenerateApp) recordHistory(results []*detector.FileDetectionResult, allResults []*parser.GenerationResult) {
+	store, err := history.DefaultStore()
+	if err != nil {
+		a.logger.Warn("failed to open generation history", slog.String("error", err.Error()))
+		return
+	}
+
+	checksums := make(map[*parser.Target]string)
+	for _, result := range results {
+		for _, status := range result.Statuses {
+			checksums[status.Target] = status.CurrentChecksum
+		}
+	}
+
+	now := time.Now()
+	for _, genResult := range allResults {
+		if !genResult.Success {
+			continue
+		}
+
+		target := genResult.Target
+		err := store.Append(target.FilePath, checksums[target], history.Entry{
+			Timestamp:        now,
+			Model:            genResult.Model,
+			Instruction:      target.Instruction,
+			GeneratedBody:    genResult.Implementation,
+			PromptTokens:     genResult.PromptTokens,
+			CompletionTokens: genResult.CompletionTokens,
+		})
+		if err != nil {
+			a.logger.Warn("failed to record generation history",
+				slog.String("function", target.GetDisplayName()),
+				slog.String("error", err.Error()))
+		}
+	}
+}
@@ -0,0 +1,78 @@
+package app
+
+import (
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"strconv"
+	"strings"
+	"syscall"
+)
+
+// lockFileName is the name of the lock file created in the destination directory
+const lockFileName = ".mantra.lock"
+
+// acquireLock creates the lock file of the destination directory so that
+// concurrent runs do not overwrite each other's output. It fails fast when
+// another run holds the lock. A lock left behind by a process that no longer
+// exists, e.g. after a crash, is taken over. The returned function releases it.
+func acquireLock(destDir string) (func(), error) {
+	if err := os.MkdirAll(destDir, 0755); err != nil {
+		return nil, fmt.Errorf("failed to create destination directory: %w", err)
+	}
+
+	path := filepath.Join(destDir, lockFileName)
+	file, err := os.OpenFile(path, os.O_CREATE|os.O_EXCL|os.O_WRONLY, 0644)
+	if errors.Is(err, os.ErrExist) && removeStaleLock(path) {
+		file, err = os.OpenFile(path, os.O_CREATE|os.O_EXCL|os.O_WRONLY, 0644)
+	}
+	if errors.Is(err, os.ErrExist) {
+		holder := "another process"
+		if pid, readErr := os.ReadFile(path); readErr == nil && len(pid) > 0 {
+			holder = fmt.Sprintf("process %s", strings.TrimSpace(string(pid)))
+		}
+		return nil, fmt.Errorf("%s is locked by %s; wait for it to finish, or remove %s if no other mantra run is active",
+			destDir, holder, path)
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to create lock file: %w", err)
+	}
+
+	_, writeErr := fmt.Fprintf(file, "%d\n", os.Getpid())
+	closeErr := file.Close()
+	if err := errors.Join(writeErr, closeErr); err != nil {
+		os.Remove(path)
+		return nil, fmt.Errorf("failed to write lock file: %w", err)
+	}
+
+	return func() { os.Remove(path) }, nil
+}
+
+// removeStaleLock removes the lock file at path if the process recorded in it
+// is no longer running. It reports whether the lock was removed.
+func removeStaleLock(path string) bool {
+	content, err := os.ReadFile(path)
+	if err != nil {
+		return false
+	}
+
+	pid, err := strconv.Atoi(strings.TrimSpace(string(content)))
+	if err != nil || pid <= 0 || processAlive(pid) {
+		return false
+	}
+
+	return os.Remove(path) == nil
+}
+
+// processAlive reports whether a process with pid is running. Platforms that
+// cannot probe processes report true, so that locks are never taken over there.
+func processAlive(pid int) bool {
+	process, err := os.FindProcess(pid)
+	if err != nil {
+		return false
+	}
+
+	err = process.Signal(syscall.Signal(0))
+	return !errors.Is(err, os.ErrProcessDone) && !errors.Is(err, syscall.ESRCH)
+}
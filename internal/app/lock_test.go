@@ -0,0 +1,71 @@
+package app
+
+import (
+	"fmt"
+	"os"
+	"os/exec"
+	"path/filepath"
+	"strings"
+	"testing"
+)
+
+func TestAcquireLock(t *testing.T) {
+	destDir := filepath.Join(t.TempDir(), "generated")
+
+	release, err := acquireLock(destDir)
+	if err != nil {
+		t.Fatalf("acquireLock failed: %v", err)
+	}
+
+	// A second run fails while the lock is held
+	_, err = acquireLock(destDir)
+	if err == nil {
+		t.Fatal("Expected error while the lock is held")
+	}
+	if !strings.Contains(err.Error(), "locked by process") {
+		t.Errorf("Expected error to name the holding process, got %q", err.Error())
+	}
+
+	release()
+	if _, err := os.Stat(filepath.Join(destDir, lockFileName)); !os.IsNotExist(err) {
+		t.Errorf("Expected lock file to be removed, got %v", err)
+	}
+
+	// The lock can be acquired again after release
+	release, err = acquireLock(destDir)
+	if err != nil {
+		t.Fatalf("acquireLock after release failed: %v", err)
+	}
+	release()
+}
+
+func TestAcquireLock_Stale(t *testing.T) {
+	destDir := filepath.Join(t.TempDir(), "generated")
+	if err := os.MkdirAll(destDir, 0755); err != nil {
+		t.Fatal(err)
+	}
+
+	// Record a process that has already exited, as left behind by a crashed run
+	cmd := exec.Command(os.Args[0], "-test.run=^$")
+	if err := cmd.Run(); err != nil {
+		t.Fatalf("failed to run child process: %v", err)
+	}
+	lockPath := filepath.Join(destDir, lockFileName)
+	if err := os.WriteFile(lockPath, []byte(fmt.Sprintf("%d\n", cmd.Process.Pid)), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	release, err := acquireLock(destDir)
+	if err != nil {
+		t.Fatalf("Expected stale lock to be taken over, got %v", err)
+	}
+	defer release()
+
+	content, err := os.ReadFile(lockPath)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if got, want := strings.TrimSpace(string(content)), fmt.Sprint(os.Getpid()); got != want {
+		t.Errorf("Expected lock to record pid %s, got %s", want, got)
+	}
+}
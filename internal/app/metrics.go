@@ -0,0 +1,98 @@
+package app
+
+import (
+	"fmt"
+	"io"
+	"time"
+
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// MetricsSummary aggregates the outcome of the targets of a run
+type MetricsSummary struct {
+	TotalTargets     int
+	Generated        int
+	Failed           int
+	Cached           int
+	GenerationTime   time.Duration // Sum over generated and failed targets
+	PromptTokens     int
+	CompletionTokens int
+	Requests         int           // LLM requests of generated and failed targets
+	RateLimited      int           // Requests rejected by rate limiting
+	RequestTime      time.Duration // Sum of request latencies
+}
+
+// summarizeMetrics counts targets by outcome and totals time and tokens of generation
+func summarizeMetrics(results []*detector.FileDetectionResult, generated []*parser.GenerationResult) MetricsSummary {
+	var summary MetricsSummary
+	for _, result := range results {
+		summary.TotalTargets += len(result.Statuses)
+		for _, status := range result.Statuses {
+			if status.Status == detector.StatusCurrent {
+				summary.Cached++
+			}
+		}
+	}
+
+	for _, result := range generated {
+		if result.Success {
+			summary.Generated++
+		} else {
+			summary.Failed++
+		}
+		summary.GenerationTime += result.Duration
+		summary.PromptTokens += result.PromptTokens
+		summary.CompletionTokens += result.CompletionTokens
+		summary.Requests += result.Requests
+		summary.RateLimited += result.RateLimited
+		summary.RequestTime += result.RequestTime
+	}
+
+	return summary
+}
+
+// AverageGenerationTime returns the mean time spent per processed target
+func (s MetricsSummary) AverageGenerationTime() time.Duration {
+	processed := s.Generated + s.Failed
+	if processed == 0 {
+		return 0
+	}
+	return s.GenerationTime / time.Duration(processed)
+}
+
+// AverageRequestTime returns the mean latency of LLM requests
+func (s MetricsSummary) AverageRequestTime() time.Duration {
+	if s.Requests == 0 {
+		return 0
+	}
+	return s.RequestTime / time.Duration(s.Requests)
+}
+
+// CacheHitRate returns the fraction of targets that were already up-to-date
+func (s MetricsSummary) CacheHitRate() float64 {
+	if s.TotalTargets == 0 {
+		return 0
+	}
+	return float64(s.Cached) / float64(s.TotalTargets)
+}
+
+// WriteStats prints request statistics as a compact table, to help tune
+// max_concurrency for a provider
+func (s MetricsSummary) WriteStats(w io.Writer) {
+	fmt.Fprintf(w, "%-14s%d\n", "Requests", s.Requests)
+	fmt.Fprintf(w, "%-14s%d\n", "Rate limited", s.RateLimited)
+	fmt.Fprintf(w, "%-14s%s\n", "Avg latency", s.AverageRequestTime().Round(time.Millisecond))
+	fmt.Fprintf(w, "%-14s%d/%d (%.0f%%)\n", "Cache hits", s.Cached, s.TotalTargets, s.CacheHitRate()*100)
+}
+
+// Write prints the summary as a single line
+func (s MetricsSummary) Write(w io.Writer) {
+	fmt.Fprintf(w, "Targets: %d total, %d generated, %d failed, %d cached",
+		s.TotalTargets, s.Generated, s.Failed, s.Cached)
+	if s.Generated+s.Failed > 0 {
+		fmt.Fprintf(w, " (avg %s per target, %d prompt / %d completion tokens)",
+			s.AverageGenerationTime().Round(time.Millisecond), s.PromptTokens, s.CompletionTokens)
+	}
+	fmt.Fprintln(w)
+}
@@ -0,0 +1,80 @@
+package app
+
+import (
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestSummarizeMetrics(t *testing.T) {
+	add, sub, mul := &parser.Target{Name: "Add"}, &parser.Target{Name: "Sub"}, &parser.Target{Name: "Mul"}
+	results := []*detector.FileDetectionResult{
+		{
+			FileInfo: &parser.FileInfo{FilePath: "/pkg/math.go"},
+			Statuses: []*detector.TargetStatus{
+				{Target: add, Status: detector.StatusCurrent},
+				{Target: sub, Status: detector.StatusUngenerated},
+				{Target: mul, Status: detector.StatusOutdated},
+			},
+		},
+		{FileInfo: &parser.FileInfo{FilePath: "/pkg/doc.go"}},
+	}
+	generated := []*parser.GenerationResult{
+		{Target: sub, Success: true, PromptTokens: 10, CompletionTokens: 5, Duration: 3 * time.Second, Requests: 3, RequestTime: 2 * time.Second},
+		{Target: mul, Success: false, PromptTokens: 4, Duration: time.Second, Requests: 1, RateLimited: 1, RequestTime: 400 * time.Millisecond},
+	}
+
+	summary := summarizeMetrics(results, generated)
+
+	expected := MetricsSummary{
+		TotalTargets:     3,
+		Generated:        1,
+		Failed:           1,
+		Cached:           1,
+		GenerationTime:   4 * time.Second,
+		PromptTokens:     14,
+		CompletionTokens: 5,
+		Requests:         4,
+		RateLimited:      1,
+		RequestTime:      2400 * time.Millisecond,
+	}
+	if summary != expected {
+		t.Errorf("Expected %+v, got %+v", expected, summary)
+	}
+	if avg := summary.AverageGenerationTime(); avg != 2*time.Second {
+		t.Errorf("Expected average of 2s, got %s", avg)
+	}
+
+	var out strings.Builder
+	summary.Write(&out)
+	want := "Targets: 3 total, 1 generated, 1 failed, 1 cached (avg 2s per target, 14 prompt / 5 completion tokens)\n"
+	if out.String() != want {
+		t.Errorf("Expected %q, got %q", want, out.String())
+	}
+
+	out.Reset()
+	summary.WriteStats(&out)
+	want = "Requests      4\n" +
+		"Rate limited  1\n" +
+		"Avg latency   600ms\n" +
+		"Cache hits    1/3 (33%)\n"
+	if out.String() != want {
+		t.Errorf("Expected %q, got %q", want, out.String())
+	}
+}
+
+func TestSummarizeMetrics_NothingGenerated(t *testing.T) {
+	summary := summarizeMetrics(nil, nil)
+	if avg := summary.AverageGenerationTime(); avg != 0 {
+		t.Errorf("Expected zero average without generated targets, got %s", avg)
+	}
+
+	var out strings.Builder
+	summary.Write(&out)
+	if out.String() != "Targets: 0 total, 0 generated, 0 failed, 0 cached\n" {
+		t.Errorf("Unexpected summary %q", out.String())
+	}
+}
@@ -0,0 +1,171 @@
+package app
+
+import (
+	"encoding/json"
+	"fmt"
+	"io"
+	"os"
+	"path/filepath"
+	"sort"
+
+	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// Output modes of the generate command. Without a mode, generated files are
+// only written.
+const (
+	OutputFull       = "full"        // Print the written files
+	OutputDiff       = "diff"        // Print a unified diff of the destination before and after the run
+	OutputSnippets   = "snippets"    // Print the generated bodies as JSON
+	OutputJSONReport = "json-report" // Print the run report as JSON
+)
+
+// ValidateOutputMode checks that mode is a supported output mode
+func ValidateOutputMode(mode string) error {
+	switch mode {
+	case "", OutputFull, OutputDiff, OutputSnippets, OutputJSONReport:
+		return nil
+	default:
+		return fmt.Errorf("unsupported output mode %q (expected %s, %s, %s or %s)", mode, OutputFull, OutputDiff, OutputSnippets, OutputJSONReport)
+	}
+}
+
+// ValidateBatchOutput checks that the report and the output mode can be used
+// when several packages are generated. A report or a JSON output per package
+// would overwrite or follow each other, so only the file based modes are
+// allowed; their output is printed package by package.
+func ValidateBatchOutput(reportPath, mode string) error {
+	if reportPath != "" {
+		return fmt.Errorf("--report is not supported when generating several packages")
+	}
+	switch mode {
+	case OutputSnippets, OutputJSONReport:
+		return fmt.Errorf("output mode %q is not supported when generating several packages (use %s or %s)", mode, OutputFull, OutputDiff)
+	}
+	return nil
+}
+
+// readGoSources reads the Go files of dir by name, so that the files written
+// by a run can be compared with their previous content. A missing dir is empty.
+func readGoSources(dir string) (map[string]string, error) {
+	paths, err := filepath.Glob(filepath.Join(dir, "*.go"))
+	if err != nil {
+		return nil, err
+	}
+
+	files := make(map[string]string)
+	for _, path := range paths {
+		content, err := os.ReadFile(path)
+		if err != nil {
+			return nil, fmt.Errorf("failed to read %s: %w", path, err)
+		}
+		files[filepath.Base(path)] = string(content)
+	}
+	return files, nil
+}
+
+// Snippet is a body generated in a run
+type Snippet struct {
+	File     string `json:"file"`
+	Function string `json:"function"`
+	Checksum string `json:"checksum"`
+	Body     string `json:"body"`
+}
+
+// writeOutput prints the written files, the generated bodies or the report of
+// a run in the given output mode. before holds the Go files of destDir from
+// before the run, as returned by readGoSources.
+func (a *GenerateApp) writeOutput(w io.Writer, mode, pkgDir, destDir string, before map[string]string, results []*detector.FileDetectionResult, generated []*parser.GenerationResult) error {
+	var output any
+	switch mode {
+	case OutputFull, OutputDiff:
+		return writeChangedFiles(w, mode, destDir, before)
+	case OutputSnippets:
+		snippets, err := collectSnippets(destDir, generated)
+		if err != nil {
+			return err
+		}
+		output = snippets
+	case OutputJSONReport:
+		output = a.newReport(pkgDir, destDir, results, generated)
+	default:
+		return nil
+	}
+
+	data, err := json.MarshalIndent(output, "", "  ")
+	if err != nil {
+		return fmt.Errorf("failed to encode output: %w", err)
+	}
+	_, err = fmt.Fprintf(w, "%s\n", data)
+	return err
+}
+
+// collectSnippets reads the bodies of successfully generated targets back from
+// the generated files, so that they match what was written
+func collectSnippets(destDir string, generated []*parser.GenerationResult) ([]Snippet, error) {
+	snippets := []Snippet{}
+	implsByFile := make(map[string]map[string]*detector.ImplementationInfo)
+
+	for _, result := range generated {
+		if !result.Success {
+			continue
+		}
+
+		file := filepath.Base(result.Target.FilePath)
+		impls, ok := implsByFile[file]
+		if !ok {
+			var err error
+			impls, err = detector.ExistingImplementations(filepath.Join(destDir, file))
+			if err != nil {
+				return nil, fmt.Errorf("failed to read generated file: %w", err)
+			}
+			implsByFile[file] = impls
+		}
+
+		cs := checksum.Calculate(result.Target)
+		impl, ok := impls[result.Target.GetDisplayName()]
+		if !ok || impl.Checksum != cs {
+			continue
+		}
+		snippets = append(snippets, Snippet{
+			File:     file,
+			Function: result.Target.GetDisplayName(),
+			Checksum: cs,
+			Body:     impl.Body,
+		})
+	}
+
+	return snippets, nil
+}
+
+// writeChangedFiles prints the Go files of destDir that differ from before,
+// either in full or as a unified diff
+func writeChangedFiles(w io.Writer, mode, destDir string, before map[string]string) error {
+	after, err := readGoSources(destDir)
+	if err != nil {
+		return err
+	}
+
+	names := make([]string, 0, len(after))
+	for name := range after {
+		if content, ok := before[name]; !ok || content != after[name] {
+			names = append(names, name)
+		}
+	}
+	sort.Strings(names)
+
+	for _, name := range names {
+		path := filepath.Join(destDir, name)
+		if mode == OutputDiff {
+			_, err = io.WriteString(w, unifiedDiff(path, before[name], after[name]))
+		} else {
+			_, err = fmt.Fprintf(w, "// %s\n%s", path, after[name])
+		}
+		if err != nil {
+			return err
+		}
+	}
+	return nil
+}
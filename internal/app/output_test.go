@@ -0,0 +1,166 @@
+package app
+
+import (
+	"bytes"
+	"encoding/json"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/codegen"
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestWriteOutput_Snippets(t *testing.T) {
+	pkgDir := t.TempDir()
+	destDir := filepath.Join(t.TempDir(), "generated")
+
+	source := `package greet
+
+// mantra: greet the given name
+func Hello(name string) string {
+	panic("not implemented")
+}
+
+// mantra: say goodbye to the given name
+func Bye(name string) string {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(filepath.Join(pkgDir, "greet.go"), []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	results, err := detector.DetectPackageTargets(pkgDir, destDir, 1<<20, "")
+	if err != nil {
+		t.Fatal(err)
+	}
+	hello, bye := results[0].Statuses[0].Target, results[0].Statuses[1].Target
+	generated := []*parser.GenerationResult{
+		{Target: hello, Success: true, Implementation: `return "Hello, " + name`},
+		{Target: bye, Success: false},
+	}
+	gen := codegen.New(&codegen.Config{Dest: destDir, PackageName: "generated", SourcePackage: "greet"})
+	if err := gen.GenerateFile(results[0].FileInfo, generated); err != nil {
+		t.Fatal(err)
+	}
+
+	var buf bytes.Buffer
+	a := NewGenerateApp()
+	if err := a.writeOutput(&buf, OutputSnippets, pkgDir, destDir, nil, results, generated); err != nil {
+		t.Fatal(err)
+	}
+
+	var snippets []Snippet
+	if err := json.Unmarshal(buf.Bytes(), &snippets); err != nil {
+		t.Fatalf("Expected JSON output, got %q: %v", buf.String(), err)
+	}
+	if len(snippets) != 1 {
+		t.Fatalf("Expected only the successful target, got %+v", snippets)
+	}
+	if snippets[0].File != "greet.go" || snippets[0].Function != "Hello" {
+		t.Errorf("Unexpected snippet location: %+v", snippets[0])
+	}
+	if strings.TrimSpace(snippets[0].Body) != `return "Hello, " + name` {
+		t.Errorf("Expected the generated body, got %q", snippets[0].Body)
+	}
+}
+
+func TestWriteOutput_ChangedFiles(t *testing.T) {
+	destDir := t.TempDir()
+	files := map[string]string{
+		"same.go":    "package generated\n",
+		"changed.go": "package generated\n\nfunc A() {}\n",
+	}
+	for name, content := range files {
+		if err := os.WriteFile(filepath.Join(destDir, name), []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	before, err := readGoSources(destDir)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// The run changes one file and adds another
+	if err := os.WriteFile(filepath.Join(destDir, "changed.go"), []byte("package generated\n\nfunc B() {}\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(destDir, "new.go"), []byte("package generated\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	a := NewGenerateApp()
+
+	var full bytes.Buffer
+	if err := a.writeOutput(&full, OutputFull, "", destDir, before, nil, nil); err != nil {
+		t.Fatal(err)
+	}
+	expectedFull := "// " + filepath.Join(destDir, "changed.go") + "\npackage generated\n\nfunc B() {}\n" +
+		"// " + filepath.Join(destDir, "new.go") + "\npackage generated\n"
+	if full.String() != expectedFull {
+		t.Errorf("Expected written files:\n%s\ngot:\n%s", expectedFull, full.String())
+	}
+
+	var diff bytes.Buffer
+	if err := a.writeOutput(&diff, OutputDiff, "", destDir, before, nil, nil); err != nil {
+		t.Fatal(err)
+	}
+	changed, added := filepath.Join(destDir, "changed.go"), filepath.Join(destDir, "new.go")
+	expectedDiff := "--- " + changed + "\n+++ " + changed + "\n" +
+		"@@ -1,3 +1,3 @@\n package generated\n \n-func A() {}\n+func B() {}\n" +
+		"--- /dev/null\n+++ " + added + "\n" +
+		"@@ -0,0 +1 @@\n+package generated\n"
+	if diff.String() != expectedDiff {
+		t.Errorf("Expected diff:\n%s\ngot:\n%s", expectedDiff, diff.String())
+	}
+}
+
+func TestValidateOutputMode(t *testing.T) {
+	tests := []struct {
+		mode    string
+		wantErr bool
+	}{
+		{"", false},
+		{OutputFull, false},
+		{OutputSnippets, false},
+		{OutputJSONReport, false},
+		{OutputDiff, false},
+		{"patch", true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.mode, func(t *testing.T) {
+			if err := ValidateOutputMode(tt.mode); (err != nil) != tt.wantErr {
+				t.Errorf("ValidateOutputMode(%q) error = %v, wantErr %v", tt.mode, err, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestValidateBatchOutput(t *testing.T) {
+	tests := []struct {
+		name       string
+		reportPath string
+		mode       string
+		wantErr    bool
+	}{
+		{name: "defaults"},
+		{name: "report", reportPath: "report.json", wantErr: true},
+		{name: "full", mode: OutputFull},
+		{name: "diff", mode: OutputDiff},
+		{name: "snippets", mode: OutputSnippets, wantErr: true},
+		{name: "json-report", mode: OutputJSONReport, wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if err := ValidateBatchOutput(tt.reportPath, tt.mode); (err != nil) != tt.wantErr {
+				t.Errorf("ValidateBatchOutput(%q, %q) error = %v, wantErr %v", tt.reportPath, tt.mode, err, tt.wantErr)
+			}
+		})
+	}
+}
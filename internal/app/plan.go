@@ -0,0 +1,298 @@
+package app
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"io"
+	"log/slog"
+	"os"
+	"path/filepath"
+
+	"github.com/rail44/mantra/internal/codegen"
+	"github.com/rail44/mantra/internal/coder"
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// Plan holds implementations generated by "mantra plan" until they are
+// written by "mantra apply"
+type Plan struct {
+	Package string        `json:"package"` // Absolute path of the source package
+	Entries []PlanEntry   `json:"entries"`
+	Failed  []PlanFailure `json:"failed,omitempty"` // Targets that could not be generated
+}
+
+// PlanEntry is a generated implementation for a single target
+type PlanEntry struct {
+	File           string   `json:"file"`              // Source file path
+	Target         string   `json:"target"`            // Target display name
+	Checksum       string   `json:"checksum"`          // Checksum of the declaration at planning time
+	Start          int      `json:"start"`             // Byte offset of the body's opening brace
+	End            int      `json:"end"`               // Byte offset of the body's closing brace
+	Implementation string   `json:"implementation"`    // Generated function body
+	Imports        []string `json:"imports,omitempty"` // Import paths declared with the body
+}
+
+// PlanFailure is a target whose generation failed during planning
+type PlanFailure struct {
+	File    string `json:"file"`    // Source file path
+	Target  string `json:"target"`  // Target display name
+	Phase   string `json:"phase"`   // Phase in which generation failed
+	Message string `json:"message"` // Reason of the failure
+}
+
+// ReadPlan loads a plan from a JSON file
+func ReadPlan(path string) (*Plan, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read plan: %w", err)
+	}
+
+	var plan Plan
+	if err := json.Unmarshal(data, &plan); err != nil {
+		return nil, fmt.Errorf("failed to parse plan %s: %w", path, err)
+	}
+
+	return &plan, nil
+}
+
+// Plan generates implementations for all pending targets and writes them to w
+// as JSON instead of generating files. Stub files are still prepared in the
+// destination so that the package can be analyzed during generation.
+func (a *GenerateApp) Plan(ctx context.Context, pkgDir string, cfg *config.Config, w io.Writer) error {
+	// Stub files are written to the destination as well
+	release, err := acquireLock(cfg.Dest)
+	if err != nil {
+		return err
+	}
+	defer release()
+
+	results, err := a.detectTargets(pkgDir, cfg)
+	if err != nil {
+		return err
+	}
+
+	plan := &Plan{
+		Package: pkgDir,
+		Entries: []PlanEntry{},
+	}
+
+	if a.needsProcessing(results) {
+		clientConfig, gen, err := a.setupAIClient(cfg, pkgDir)
+		if err != nil {
+			return err
+		}
+
+		if err := a.prepareStubFiles(results, gen); err != nil {
+			return fmt.Errorf("failed to prepare stub files: %w", err)
+		}
+
+		targets := a.collectPendingTargets(results)
+		if len(targets) > 0 {
+			parallelCoder := coder.NewParallelCoder(clientConfig, cfg)
+			allResults, err := parallelCoder.ExecuteTargets(ctx, targets)
+			if err != nil {
+				return fmt.Errorf("failed to generate implementations: %w", err)
+			}
+			plan.Entries, plan.Failed = a.planEntries(results, allResults)
+		}
+	}
+
+	a.logger.Info(fmt.Sprintf("Planned %d implementations, %d failed", len(plan.Entries), len(plan.Failed)))
+
+	encoder := json.NewEncoder(w)
+	encoder.SetIndent("", "  ")
+	return encoder.Encode(plan)
+}
+
+// collectPendingTargets collects targets that need generation without copying any files
+func (a *GenerateApp) collectPendingTargets(results []*detector.FileDetectionResult) []coder.TargetContext {
+	var targets []coder.TargetContext
+
+	index := 0
+	for _, result := range results {
+		if len(result.Statuses) == 0 {
+			continue
+		}
+
+		content, err := os.ReadFile(result.FileInfo.FilePath)
+		if err != nil {
+			a.logger.Error("failed to read file",
+				slog.String("file", result.FileInfo.FilePath),
+				slog.String("error", err.Error()))
+			continue
+		}
+
+		for _, status := range result.Statuses {
+			if status.Status != detector.StatusCurrent {
+				index += 1
+				targets = append(targets, coder.TargetContext{
+					Target:      status.Target,
+					FileContent: string(content),
+					FileInfo:    result.FileInfo,
+					Index:       index,
+				})
+			}
+		}
+	}
+
+	return targets
+}
+
+// planEntries converts successful generation results into plan entries and
+// records the failed ones
+func (a *GenerateApp) planEntries(results []*detector.FileDetectionResult, allResults []*parser.GenerationResult) ([]PlanEntry, []PlanFailure) {
+	// Checksums were calculated during detection
+	checksums := make(map[*parser.Target]string)
+	for _, result := range results {
+		for _, status := range result.Statuses {
+			checksums[status.Target] = status.CurrentChecksum
+		}
+	}
+
+	entries := []PlanEntry{}
+	var failures []PlanFailure
+	for _, genResult := range allResults {
+		target := genResult.Target
+		if !genResult.Success {
+			failure := PlanFailure{File: target.FilePath, Target: target.GetDisplayName()}
+			if reason := genResult.FailureReason; reason != nil {
+				failure.Phase = reason.Phase
+				failure.Message = reason.Message
+			}
+			a.logger.Warn("generation failed, target not planned",
+				slog.String("function", failure.Target),
+				slog.String("file", filepath.Base(failure.File)),
+				slog.String("phase", failure.Phase),
+				slog.String("reason", failure.Message))
+			failures = append(failures, failure)
+			continue
+		}
+
+		entry := PlanEntry{
+			File:           target.FilePath,
+			Target:         target.GetDisplayName(),
+			Checksum:       checksums[target],
+			Implementation: genResult.Implementation,
+			Imports:        genResult.Imports,
+		}
+		if target.FuncDecl != nil && target.FuncDecl.Body != nil && target.TokenSet != nil {
+			entry.Start = target.TokenSet.Position(target.FuncDecl.Body.Lbrace).Offset
+			entry.End = target.TokenSet.Position(target.FuncDecl.Body.Rbrace).Offset
+		}
+		entries = append(entries, entry)
+	}
+
+	return entries, failures
+}
+
+// Apply writes the implementations of a plan to the destination directory.
+// Each entry is checked against the current declaration and skipped with a
+// warning if the target changed since planning.
+func (a *GenerateApp) Apply(plan *Plan, cfg *config.Config) error {
+	release, err := acquireLock(cfg.Dest)
+	if err != nil {
+		return err
+	}
+	defer release()
+
+	results, err := a.detectTargets(plan.Package, cfg)
+	if err != nil {
+		return err
+	}
+
+	entries := make(map[string]PlanEntry)
+	for _, entry := range plan.Entries {
+		entries[planKey(entry.File, entry.Target)] = entry
+	}
+	for _, failure := range plan.Failed {
+		a.logger.Warn("target failed during planning, not applied",
+			slog.String("function", failure.Target),
+			slog.String("file", filepath.Base(failure.File)),
+			slog.String("reason", failure.Message))
+	}
+
+	gen := codegen.New(&codegen.Config{
+		Dest:            cfg.Dest,
+		PackageName:     cfg.GetPackageName(),
+		SourcePackage:   filepath.Base(plan.Package),
+		PlaceholderBody: cfg.PlaceholderBody,
+	})
+
+	var applied, skipped int
+	for _, result := range results {
+		fileInfo := result.FileInfo
+
+		if len(result.Statuses) == 0 {
+			a.copyFileWithoutTargets(fileInfo, gen)
+			continue
+		}
+
+		var fileResults []*parser.GenerationResult
+		for _, status := range result.Statuses {
+			entry, planned := entries[planKey(fileInfo.FilePath, status.Target.GetDisplayName())]
+
+			switch {
+			case planned && entry.Checksum == status.CurrentChecksum:
+				applied++
+				fileResults = append(fileResults, &parser.GenerationResult{
+					Target:         status.Target,
+					Success:        true,
+					Implementation: entry.Implementation,
+					Imports:        entry.Imports,
+				})
+			case planned:
+				skipped++
+				a.logger.Warn("target changed since planning, skipping",
+					slog.String("function", status.Target.GetDisplayName()),
+					slog.String("file", filepath.Base(fileInfo.FilePath)),
+					slog.String("planned_checksum", entry.Checksum),
+					slog.String("current_checksum", status.CurrentChecksum))
+				fileResults = append(fileResults, a.existingResult(status))
+			default:
+				fileResults = append(fileResults, a.existingResult(status))
+			}
+		}
+
+		if err := gen.GenerateFile(fileInfo, fileResults); err != nil {
+			a.logger.Error("failed to generate file",
+				slog.String("file", fileInfo.FilePath),
+				slog.String("error", err.Error()))
+		} else {
+			a.logger.Info(fmt.Sprintf("Generated: %s", filepath.Base(fileInfo.FilePath)))
+		}
+	}
+	gen.WarnMissingImports()
+
+	a.logger.Info(fmt.Sprintf("Applied %d implementations, skipped %d", applied, skipped))
+
+	if cfg.FailOnDiagnostics {
+		return a.verifyGeneratedPackage(cfg.Dest)
+	}
+
+	return nil
+}
+
+// existingResult keeps the current implementation of a target, or a stub if it has none
+func (a *GenerateApp) existingResult(status *detector.TargetStatus) *parser.GenerationResult {
+	if status.Status != detector.StatusCurrent {
+		return &parser.GenerationResult{
+			Target:  status.Target,
+			Success: false,
+		}
+	}
+
+	return &parser.GenerationResult{
+		Target:         status.Target,
+		Success:        true,
+		Implementation: status.ExistingImpl,
+		Imports:        status.ExistingImports,
+	}
+}
+
+// planKey identifies a target within a plan
+func planKey(file, target string) string {
+	return file + "#" + target
+}
@@ -0,0 +1,119 @@
+package app
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/llm"
+)
+
+func TestPlanAndApply(t *testing.T) {
+	t.Setenv("XDG_DATA_HOME", t.TempDir())
+
+	// Implements "return one" and "return two", and fails on anything else
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		var req llm.OpenAIRequest
+		if err := json.NewDecoder(r.Body).Decode(&req); err != nil {
+			t.Errorf("failed to decode request: %v", err)
+		}
+		prompt := req.Messages[len(req.Messages)-1].Content
+
+		// Only the implementation phase can check code
+		implementation := false
+		for _, tool := range req.Tools {
+			implementation = implementation || tool.Function.Name == "check_code"
+		}
+
+		result := map[string]any{"success": true, "constants": []any{}, "code": "return 0"}
+		switch {
+		case !implementation:
+		case strings.Contains(prompt, "return one"):
+			result["code"] = "return 1"
+		case strings.Contains(prompt, "return two"):
+			result["code"] = "return 2"
+		default:
+			result = map[string]any{"success": false, "error": map[string]any{"message": "cannot implement"}}
+		}
+		args, _ := json.Marshal(result)
+		fmt.Fprintf(w, `{"choices": [{"message": {"role": "assistant", "tool_calls": [{"id": "call_1", "type": "function", "function": {"name": "result", "arguments": %s}}]}}]}`, args)
+	}))
+	defer server.Close()
+
+	pkgDir := t.TempDir()
+	destDir := filepath.Join(pkgDir, "generated")
+	if err := os.WriteFile(filepath.Join(pkgDir, "go.mod"), []byte("module numbers\n\ngo 1.21\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	sourceFile := filepath.Join(pkgDir, "numbers.go")
+	source := `package numbers
+
+// mantra: return one
+func One() int {
+	panic("not implemented")
+}
+
+// mantra: return two
+func Two() int {
+	panic("not implemented")
+}
+
+// mantra: return three
+func Three() int {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	cfg := &config.Config{Model: "test-model", URL: server.URL, Dest: destDir, Plain: true}
+	a := NewGenerateApp()
+
+	var buf bytes.Buffer
+	if err := a.Plan(context.Background(), pkgDir, cfg, &buf); err != nil {
+		t.Fatalf("Plan failed: %v", err)
+	}
+	var plan Plan
+	if err := json.Unmarshal(buf.Bytes(), &plan); err != nil {
+		t.Fatalf("Expected a JSON plan, got %q: %v", buf.String(), err)
+	}
+
+	// The failed target is recorded instead of dropped
+	if len(plan.Entries) != 2 {
+		t.Errorf("Expected 2 planned entries, got %+v", plan.Entries)
+	}
+	if len(plan.Failed) != 1 || plan.Failed[0].Target != "Three" || plan.Failed[0].Message != "cannot implement" {
+		t.Errorf("Expected Three to be recorded as failed, got %+v", plan.Failed)
+	}
+
+	// One changes after planning
+	edited := strings.Replace(source, "// mantra: return one", "// mantra: return one plus zero", 1)
+	if err := os.WriteFile(sourceFile, []byte(edited), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	if err := a.Apply(&plan, cfg); err != nil {
+		t.Fatalf("Apply failed: %v", err)
+	}
+
+	generated, err := os.ReadFile(filepath.Join(destDir, "numbers.go"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	content := string(generated)
+	if !strings.Contains(content, "return 2") {
+		t.Errorf("Expected the unchanged target to be applied, got:\n%s", content)
+	}
+	if strings.Contains(content, "return 1") {
+		t.Errorf("Expected the changed target to be skipped, got:\n%s", content)
+	}
+}
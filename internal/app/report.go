@@ -0,0 +1,168 @@
+package app
+
+import (
+	"encoding/json"
+	"fmt"
+	"log/slog"
+	"os"
+	"path/filepath"
+	"strings"
+	"time"
+
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// Report statuses of a target
+const (
+	ReportGenerated = "generated" // Generated in this run
+	ReportCached    = "cached"    // Already up-to-date
+	ReportSkipped   = "skipped"   // Pending, but not processed in this run
+	ReportFailed    = "failed"    // Generation failed
+)
+
+// Report is a machine-readable summary of a generate run
+type Report struct {
+	Package string       `json:"package"`
+	Files   []ReportFile `json:"files"`
+	Stats   ReportStats  `json:"stats"`
+}
+
+// ReportStats holds request statistics of a run
+type ReportStats struct {
+	Requests          int     `json:"requests"`
+	RateLimited       int     `json:"rate_limited"`
+	AvgLatencySeconds float64 `json:"avg_latency_seconds"`
+	CacheHitRate      float64 `json:"cache_hit_rate"`
+}
+
+// ReportFile summarizes the targets of one source file
+type ReportFile struct {
+	File        string         `json:"file"`
+	Targets     []ReportTarget `json:"targets"`
+	Diagnostics []string       `json:"diagnostics,omitempty"`
+}
+
+// ReportTarget summarizes the outcome of one target
+type ReportTarget struct {
+	Name             string  `json:"name"`
+	Checksum         string  `json:"checksum"`
+	Status           string  `json:"status"`
+	Model            string  `json:"model,omitempty"`
+	PromptTokens     int     `json:"prompt_tokens"`
+	CompletionTokens int     `json:"completion_tokens"`
+	ElapsedSeconds   float64 `json:"elapsed_seconds"`
+	Error            string  `json:"error,omitempty"`
+}
+
+// buildReport combines detection and generation results into a report
+func buildReport(pkgDir string, results []*detector.FileDetectionResult, generated []*parser.GenerationResult) *Report {
+	byTarget := make(map[string]*parser.GenerationResult)
+	for _, result := range generated {
+		byTarget[planKey(result.Target.FilePath, result.Target.GetDisplayName())] = result
+	}
+
+	summary := summarizeMetrics(results, generated)
+	report := &Report{
+		Package: pkgDir,
+		Files:   []ReportFile{},
+		Stats: ReportStats{
+			Requests:          summary.Requests,
+			RateLimited:       summary.RateLimited,
+			AvgLatencySeconds: summary.AverageRequestTime().Round(time.Millisecond).Seconds(),
+			CacheHitRate:      summary.CacheHitRate(),
+		},
+	}
+	for _, result := range results {
+		if len(result.Statuses) == 0 {
+			continue
+		}
+
+		file := ReportFile{File: filepath.Base(result.FileInfo.FilePath)}
+		for _, status := range result.Statuses {
+			target := ReportTarget{
+				Name:     status.Target.GetDisplayName(),
+				Checksum: status.CurrentChecksum,
+				Status:   ReportSkipped,
+			}
+
+			if status.Status == detector.StatusCurrent {
+				target.Status = ReportCached
+			} else if genResult, ok := byTarget[planKey(status.Target.FilePath, status.Target.GetDisplayName())]; ok {
+				target.Status = ReportGenerated
+				target.Model = genResult.Model
+				target.PromptTokens = genResult.PromptTokens
+				target.CompletionTokens = genResult.CompletionTokens
+				target.ElapsedSeconds = genResult.Duration.Round(time.Millisecond).Seconds()
+				if !genResult.Success {
+					target.Status = ReportFailed
+					if genResult.FailureReason != nil {
+						target.Error = genResult.FailureReason.Message
+					}
+				}
+			}
+
+			file.Targets = append(file.Targets, target)
+		}
+		report.Files = append(report.Files, file)
+	}
+
+	return report
+}
+
+// addDiagnostics attaches diagnostics of the generated package to their files
+func (r *Report) addDiagnostics(diagnostics []string) {
+	for _, d := range diagnostics {
+		for i := range r.Files {
+			if strings.Contains(d, r.Files[i].File+":") {
+				r.Files[i].Diagnostics = append(r.Files[i].Diagnostics, d)
+				break
+			}
+		}
+	}
+}
+
+// newReport builds the report of a run, including type errors of the generated package
+func (a *GenerateApp) newReport(pkgDir, destDir string, results []*detector.FileDetectionResult, generated []*parser.GenerationResult) *Report {
+	report := buildReport(pkgDir, results, generated)
+
+	diagnostics, err := checkGeneratedPackage(destDir)
+	if err != nil {
+		a.logger.Warn("failed to check generated package for report", slog.String("error", err.Error()))
+	}
+	var messages []string
+	for _, d := range diagnostics {
+		messages = append(messages, d.Error())
+	}
+	report.addDiagnostics(messages)
+
+	return report
+}
+
+// writeReport writes the report of a run to path, replacing it atomically
+func (a *GenerateApp) writeReport(path, pkgDir, destDir string, results []*detector.FileDetectionResult, generated []*parser.GenerationResult) error {
+	data, err := json.MarshalIndent(a.newReport(pkgDir, destDir, results, generated), "", "  ")
+	if err != nil {
+		return fmt.Errorf("failed to encode report: %w", err)
+	}
+
+	tmp, err := os.CreateTemp(filepath.Dir(path), ".mantra-report-*")
+	if err != nil {
+		return fmt.Errorf("failed to create report: %w", err)
+	}
+	defer os.Remove(tmp.Name())
+
+	if _, err := tmp.Write(append(data, '\n')); err != nil {
+		tmp.Close()
+		return fmt.Errorf("failed to write report: %w", err)
+	}
+	if err := tmp.Close(); err != nil {
+		return fmt.Errorf("failed to write report: %w", err)
+	}
+	if err := os.Rename(tmp.Name(), path); err != nil {
+		return fmt.Errorf("failed to write report: %w", err)
+	}
+
+	a.logger.Info("report written", slog.String("path", path))
+	return nil
+}
@@ -0,0 +1,69 @@
+package app
+
+import (
+	"testing"
+	"time"
+
+	"github.com/rail44/mantra/internal/detector"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestBuildReport(t *testing.T) {
+	newTarget := func(name string) *parser.Target {
+		return &parser.Target{Name: name, FilePath: "/pkg/math.go"}
+	}
+	add, sub, mul, div := newTarget("Add"), newTarget("Sub"), newTarget("Mul"), newTarget("Div")
+
+	results := []*detector.FileDetectionResult{
+		{
+			FileInfo: &parser.FileInfo{FilePath: "/pkg/math.go"},
+			Statuses: []*detector.TargetStatus{
+				{Target: add, Status: detector.StatusCurrent, CurrentChecksum: "aaaa"},
+				{Target: sub, Status: detector.StatusUngenerated, CurrentChecksum: "bbbb"},
+				{Target: mul, Status: detector.StatusOutdated, CurrentChecksum: "cccc"},
+				{Target: div, Status: detector.StatusUngenerated, CurrentChecksum: "dddd"},
+			},
+		},
+		{
+			FileInfo: &parser.FileInfo{FilePath: "/pkg/doc.go"},
+		},
+	}
+	generated := []*parser.GenerationResult{
+		{Target: sub, Success: true, Model: "m", PromptTokens: 10, CompletionTokens: 5, Duration: 1500 * time.Millisecond, Requests: 2, RequestTime: time.Second},
+		{Target: mul, Success: false, FailureReason: &parser.FailureReason{Message: "timeout"}},
+	}
+
+	report := buildReport("/pkg", results, generated)
+	report.addDiagnostics([]string{"/dest/math.go:3:2: undefined: x"})
+
+	if len(report.Files) != 1 {
+		t.Fatalf("Expected only files with targets, got %d files", len(report.Files))
+	}
+	file := report.Files[0]
+	if file.File != "math.go" || len(file.Diagnostics) != 1 {
+		t.Errorf("Unexpected file entry: %+v", file)
+	}
+
+	expected := map[string]string{
+		"Add": ReportCached,
+		"Sub": ReportGenerated,
+		"Mul": ReportFailed,
+		"Div": ReportSkipped,
+	}
+	for _, target := range file.Targets {
+		if target.Status != expected[target.Name] {
+			t.Errorf("Expected %s to be %s, got %s", target.Name, expected[target.Name], target.Status)
+		}
+	}
+
+	sub0 := file.Targets[1]
+	if sub0.Checksum != "bbbb" || sub0.PromptTokens != 10 || sub0.CompletionTokens != 5 || sub0.ElapsedSeconds != 1.5 {
+		t.Errorf("Unexpected generated target entry: %+v", sub0)
+	}
+	if file.Targets[2].Error != "timeout" {
+		t.Errorf("Expected failure message, got %+v", file.Targets[2])
+	}
+	if stats := report.Stats; stats.Requests != 2 || stats.AvgLatencySeconds != 0.5 || stats.CacheHitRate != 0.25 {
+		t.Errorf("Unexpected stats: %+v", stats)
+	}
+}
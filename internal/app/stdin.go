@@ -0,0 +1,114 @@
+package app
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	goparser "go/parser"
+	"go/token"
+	"io"
+	"log/slog"
+	"os"
+	"path/filepath"
+
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// stdinFileName is the name used for source read from stdin
+const stdinFileName = "stdin.go"
+
+// RunStdin reads Go source from r, generates implementations for its targets and
+// writes the resulting file to w.
+//
+// The source is written to a temporary package directory under tempRoot so that
+// go/packages can resolve types from the surrounding module. When tempRoot is
+// empty, the current directory is used. All temporary files are removed before
+// returning.
+func (a *GenerateApp) RunStdin(ctx context.Context, r io.Reader, w io.Writer, cfg *config.Config, tempRoot string) error {
+	source, err := io.ReadAll(r)
+	if err != nil {
+		return fmt.Errorf("failed to read source from stdin: %w", err)
+	}
+
+	// The package name is needed up front so the generated file keeps it.
+	// Fragments get the package of the current directory, and the clause is
+	// added so that the temporary package can be loaded, then removed from
+	// the output.
+	packageName, inferred, err := parser.PackageClause(stdinFileName, source)
+	if err != nil {
+		return fmt.Errorf("failed to parse package clause from stdin: %w", err)
+	}
+	if inferred {
+		a.logger.Warn("no package clause found, inferring package from directory",
+			slog.String("package", packageName))
+		source = append([]byte(fmt.Sprintf("package %s\n\n", packageName)), source...)
+	}
+
+	if tempRoot == "" {
+		tempRoot = "."
+	}
+
+	// Directory names starting with "." or "_" are ignored by the go tool,
+	// so the prefix must be a plain identifier
+	workDir, err := os.MkdirTemp(tempRoot, "mantra-stdin-")
+	if err != nil {
+		return fmt.Errorf("failed to create temporary directory: %w", err)
+	}
+	defer os.RemoveAll(workDir)
+
+	workDir, err = filepath.Abs(workDir)
+	if err != nil {
+		return fmt.Errorf("failed to get absolute path: %w", err)
+	}
+
+	srcDir := filepath.Join(workDir, "src")
+	if err := os.MkdirAll(srcDir, 0755); err != nil {
+		return fmt.Errorf("failed to create temporary source directory: %w", err)
+	}
+	if err := os.WriteFile(filepath.Join(srcDir, stdinFileName), source, 0644); err != nil {
+		return fmt.Errorf("failed to write temporary source file: %w", err)
+	}
+
+	// Generate into a directory named after the source package so the
+	// package clause of the output matches the input
+	stdinCfg := *cfg
+	stdinCfg.Dest = filepath.Join(workDir, packageName)
+	stdinCfg.Plain = true
+
+	if err := a.Run(ctx, srcDir, &stdinCfg); err != nil {
+		return err
+	}
+
+	generated, err := os.ReadFile(filepath.Join(stdinCfg.Dest, stdinFileName))
+	if err != nil {
+		return fmt.Errorf("failed to read generated file: %w", err)
+	}
+
+	if inferred {
+		if generated, err = stripPackageClause(generated); err != nil {
+			return fmt.Errorf("failed to parse generated file: %w", err)
+		}
+	}
+
+	if _, err := w.Write(generated); err != nil {
+		return fmt.Errorf("failed to write generated source: %w", err)
+	}
+
+	return nil
+}
+
+// stripPackageClause removes the package clause of source together with the
+// blank lines following it
+func stripPackageClause(source []byte) ([]byte, error) {
+	fset := token.NewFileSet()
+	file, err := goparser.ParseFile(fset, stdinFileName, source, goparser.PackageClauseOnly)
+	if err != nil {
+		return nil, err
+	}
+
+	start := fset.Position(file.Package).Offset
+	end := fset.Position(file.Name.End()).Offset
+	rest := bytes.TrimLeft(source[end:], "\n")
+	return append(source[:start:start], rest...), nil
+}
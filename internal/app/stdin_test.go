@@ -0,0 +1,34 @@
+package app
+
+import "testing"
+
+func TestStripPackageClause(t *testing.T) {
+	tests := []struct {
+		name   string
+		source string
+		want   string
+	}{
+		{
+			name:   "fragment",
+			source: "package user\n\n// Code generated by mantra; DO NOT EDIT.\n\nfunc Get() {}\n",
+			want:   "// Code generated by mantra; DO NOT EDIT.\n\nfunc Get() {}\n",
+		},
+		{
+			name:   "package clause only",
+			source: "package user\n",
+			want:   "",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := stripPackageClause([]byte(tt.source))
+			if err != nil {
+				t.Fatalf("stripPackageClause failed: %v", err)
+			}
+			if string(got) != tt.want {
+				t.Errorf("stripPackageClause() = %q, want %q", got, tt.want)
+			}
+		})
+	}
+}
@@ -0,0 +1,164 @@
+package app
+
+import (
+	"context"
+	"log/slog"
+	"maps"
+	"os"
+	"path/filepath"
+	"time"
+
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+const (
+	// watchPollInterval is how often the watched files are checked for changes
+	watchPollInterval = 100 * time.Millisecond
+
+	// watchDebounce is how long changes must settle before regenerating,
+	// so that rapid saves trigger a single run
+	watchDebounce = 200 * time.Millisecond
+)
+
+// Watch generates the package, then regenerates it whenever its Go files change,
+// until ctx is cancelled. Only targets whose checksum changed are regenerated.
+// Failed runs are logged and watching continues.
+//
+// If reload is not nil, the config file is watched as well, and reload is
+// called to load it again when it changes.
+func (a *GenerateApp) Watch(ctx context.Context, pkgDir string, cfg *config.Config, reload func() (*config.Config, error)) error {
+	// The interactive UI cannot be restarted for every run
+	cfg.Plain = true
+
+	a.runWatched(ctx, pkgDir, cfg)
+
+	var configFiles []string
+	if reload != nil && cfg.Path != "" {
+		configFiles = append(configFiles, cfg.Path)
+	}
+	configStamp := stampFile(cfg.Path)
+
+	a.logger.Info("watching for changes", slog.String("package", filepath.Base(pkgDir)))
+	for range watchPackage(ctx, pkgDir, configFiles, watchPollInterval, watchDebounce) {
+		if len(configFiles) > 0 {
+			if stamp := stampFile(cfg.Path); stamp != configStamp {
+				configStamp = stamp
+				cfg = a.reloadConfig(cfg, reload)
+			}
+		}
+
+		a.logger.Info("change detected, regenerating")
+		a.runWatched(ctx, pkgDir, cfg)
+	}
+
+	return nil
+}
+
+// reloadConfig loads the configuration again. An invalid configuration is
+// logged and the current one is kept.
+func (a *GenerateApp) reloadConfig(current *config.Config, reload func() (*config.Config, error)) *config.Config {
+	cfg, err := reload()
+	if err != nil {
+		a.logger.Error("failed to reload config, keeping the previous one", slog.String("error", err.Error()))
+		return current
+	}
+	cfg.Plain = true
+
+	a.logger.Info("config reloaded", slog.String("model", cfg.Model))
+	return cfg
+}
+
+// runWatched runs one generation, logging instead of returning failures
+func (a *GenerateApp) runWatched(ctx context.Context, pkgDir string, cfg *config.Config) {
+	if err := a.Run(ctx, pkgDir, cfg); err != nil && ctx.Err() == nil {
+		a.logger.Error("generation failed", slog.String("error", err.Error()))
+	}
+}
+
+// fileStamp identifies a version of a file
+type fileStamp struct {
+	modTime int64
+	size    int64
+}
+
+// stampFile returns the stamp of a file, or the zero stamp if it does not exist
+func stampFile(path string) fileStamp {
+	info, err := os.Stat(path)
+	if err != nil {
+		return fileStamp{}
+	}
+	return fileStamp{modTime: info.ModTime().UnixNano(), size: info.Size()}
+}
+
+// snapshotGoFiles returns the stamps of the Go files and instruction sidecars
+// in dir, plus those of extra files
+func snapshotGoFiles(dir string, extra []string) (map[string]fileStamp, error) {
+	files, err := filepath.Glob(filepath.Join(dir, "*.go"))
+	if err != nil {
+		return nil, err
+	}
+	// Instruction sidecars change targets too
+	sidecars, err := filepath.Glob(filepath.Join(dir, "*.go"+parser.SidecarSuffix))
+	if err != nil {
+		return nil, err
+	}
+	files = append(files, sidecars...)
+	files = append(files, extra...)
+
+	snapshot := make(map[string]fileStamp, len(files))
+	for _, file := range files {
+		stamp := stampFile(file)
+		if stamp == (fileStamp{}) {
+			// Removed since globbing
+			continue
+		}
+		snapshot[file] = stamp
+	}
+	return snapshot, nil
+}
+
+// watchPackage polls the Go files of dir and the extra files, and sends on the
+// returned channel once changes have settled for the debounce duration. The
+// channel is closed when ctx is done.
+func watchPackage(ctx context.Context, dir string, extra []string, interval, debounce time.Duration) <-chan struct{} {
+	changes := make(chan struct{})
+
+	go func() {
+		defer close(changes)
+
+		ticker := time.NewTicker(interval)
+		defer ticker.Stop()
+
+		last, _ := snapshotGoFiles(dir, extra)
+		var changedAt time.Time // Zero while no change is pending
+		for {
+			select {
+			case <-ctx.Done():
+				return
+			case now := <-ticker.C:
+				current, err := snapshotGoFiles(dir, extra)
+				if err != nil {
+					continue
+				}
+				if !maps.Equal(current, last) {
+					last = current
+					changedAt = now
+					continue
+				}
+				if changedAt.IsZero() || now.Sub(changedAt) < debounce {
+					continue
+				}
+
+				changedAt = time.Time{}
+				select {
+				case changes <- struct{}{}:
+				case <-ctx.Done():
+					return
+				}
+			}
+		}
+	}()
+
+	return changes
+}
@@ -0,0 +1,98 @@
+package app
+
+import (
+	"context"
+	"fmt"
+	"os"
+	"path/filepath"
+	"testing"
+	"time"
+
+	"github.com/rail44/mantra/internal/config"
+)
+
+func TestWatchPackage(t *testing.T) {
+	dir := t.TempDir()
+	file := filepath.Join(dir, "math.go")
+	if err := os.WriteFile(file, []byte("package math\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	ctx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+	changes := watchPackage(ctx, dir, nil, 10*time.Millisecond, 100*time.Millisecond)
+
+	// Rapid saves are reported once
+	for i := range 5 {
+		content := fmt.Sprintf("package math\n\n// revision %d\n", i)
+		if err := os.WriteFile(file, []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+		time.Sleep(20 * time.Millisecond)
+	}
+
+	select {
+	case <-changes:
+	case <-time.After(2 * time.Second):
+		t.Fatal("Expected a change to be reported")
+	}
+
+	select {
+	case <-changes:
+		t.Fatal("Expected rapid saves to be reported once")
+	case <-time.After(300 * time.Millisecond):
+	}
+
+	// Later changes are reported again, including new files
+	if err := os.WriteFile(filepath.Join(dir, "extra.go"), []byte("package math\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	select {
+	case <-changes:
+	case <-time.After(2 * time.Second):
+		t.Fatal("Expected a new file to be reported")
+	}
+
+	cancel()
+	if _, ok := <-changes; ok {
+		t.Error("Expected the channel to be closed after cancellation")
+	}
+}
+
+func TestReloadConfig(t *testing.T) {
+	dir := t.TempDir()
+	configPath := filepath.Join(dir, "mantra.toml")
+	writeConfig := func(content string) {
+		t.Helper()
+		if err := os.WriteFile(configPath, []byte(content), 0644); err != nil {
+			t.Fatal(err)
+		}
+	}
+	reload := func() (*config.Config, error) {
+		return config.Load(dir)
+	}
+
+	writeConfig("model = \"first\"\nurl = \"http://localhost:11434\"\ndest = \"generated\"\n")
+	cfg, err := reload()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if cfg.Path != configPath {
+		t.Errorf("Expected config path %s, got %s", configPath, cfg.Path)
+	}
+
+	a := NewGenerateApp()
+
+	// The new model is used for subsequent runs
+	writeConfig("model = \"second\"\nurl = \"http://localhost:11434\"\ndest = \"generated\"\n")
+	cfg = a.reloadConfig(cfg, reload)
+	if cfg.Model != "second" || !cfg.Plain {
+		t.Errorf("Expected reloaded plain config with model second, got model %q plain %v", cfg.Model, cfg.Plain)
+	}
+
+	// An invalid config keeps the previous one
+	writeConfig("url = \"http://localhost:11434\"\n")
+	if reloaded := a.reloadConfig(cfg, reload); reloaded != cfg {
+		t.Errorf("Expected the previous config to be kept, got %+v", reloaded)
+	}
+}
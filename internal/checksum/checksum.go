@@ -8,7 +8,9 @@ import (
 	"github.com/rail44/mantra/internal/parser"
 )
 
-// Calculate computes a checksum for a target function based on its signature and instruction
+// Calculate computes a checksum for a target function based on its signature and instruction.
+// The signature is the rendered one, so rendering changes such as showing the
+// element type of variadic parameters regenerate the affected targets once.
 func Calculate(target *parser.Target) string {
 	// Normalize the signature (remove extra spaces, newlines)
 	signature := normalizeSignature(target.GetFunctionSignature())
@@ -6,6 +6,7 @@ import (
 	"go/format"
 	goparser "go/parser"
 	"go/token"
+	"log/slog"
 	"os"
 	"path/filepath"
 	"sort"
@@ -19,25 +20,48 @@ import (
 
 // Config holds configuration for code generation
 type Config struct {
-	Dest          string // Directory where generated files will be saved
-	PackageName   string // Package name for generated files
-	SourcePackage string // Original package name for import reference
+	Dest            string // Directory where generated files will be saved
+	PackageName     string // Package name for generated files
+	SourcePackage   string // Original package name for import reference
+	PlaceholderBody string // Body for targets without an implementation (empty keeps the source body)
 }
 
 type Generator struct {
 	config *Config
+
+	// missingImports holds the packages each written file may be missing,
+	// keyed by source file and reported by WarnMissingImports
+	missingImports map[string][]string
 }
 
 func New(config *Config) *Generator {
-	return &Generator{config: config}
+	return &Generator{config: config, missingImports: make(map[string][]string)}
+}
+
+// WarnMissingImports logs the packages that could not be imported automatically
+// into the files written by GenerateFile, as of their last write. Names declared
+// in other files of the package are reported too, hence only a warning.
+func (g *Generator) WarnMissingImports() {
+	files := make([]string, 0, len(g.missingImports))
+	for file := range g.missingImports {
+		files = append(files, file)
+	}
+	sort.Strings(files)
+
+	for _, file := range files {
+		slog.Warn("generated file may be missing imports",
+			slog.String("file", filepath.Base(file)),
+			slog.String("packages", strings.Join(g.missingImports[file], ", ")))
+	}
+	clear(g.missingImports)
 }
 
 // PrepareTargetStubs prepares the generated file with stub implementations for targets
 // that are about to be generated. This creates a valid Go file that can be analyzed
 // by go/packages while generation is in progress.
 //
-// For targets to be generated: uses panic("not implemented")
-// For other targets: preserves existing implementation if file exists, otherwise uses panic
+// For targets to be generated: uses the placeholder body (panic("not implemented") by default)
+// For other targets: preserves existing implementation if file exists, otherwise uses the placeholder
 func (g *Generator) PrepareTargetStubs(fileInfo *parser.FileInfo, targetsToGenerate map[string]bool) error {
 	// Create output directory if it doesn't exist
 	if err := os.MkdirAll(g.config.Dest, 0755); err != nil {
@@ -51,7 +75,7 @@ func (g *Generator) PrepareTargetStubs(fileInfo *parser.FileInfo, targetsToGener
 	var existingContent string
 	hasExistingFile := false
 	if existingData, err := os.ReadFile(outputFile); err == nil {
-		existingContent = string(existingData)
+		existingContent = parser.NormalizeLineEndings(string(existingData))
 		hasExistingFile = true
 	}
 
@@ -102,7 +126,8 @@ func (g *Generator) PrepareTargetStubs(fileInfo *parser.FileInfo, targetsToGener
 		return fmt.Errorf("failed to generate file content: %w", err)
 	}
 
-	// Write to file
+	// Write to file, keeping the line endings of the source
+	content = parser.RestoreLineEndings(content, fileInfo.CRLF)
 	if err := os.WriteFile(outputFile, []byte(content), 0644); err != nil {
 		return fmt.Errorf("failed to write file: %w", err)
 	}
@@ -125,6 +150,7 @@ func (g *Generator) mergeWithExisting(fileInfo *parser.FileInfo, results []*pars
 
 	// Build a map of which targets to regenerate (use stubs)
 	useStub := make(map[string]bool)
+	usedPlaceholder := false
 	for _, result := range results {
 		if !result.Success {
 			useStub[result.Target.GetDisplayName()] = true
@@ -139,7 +165,12 @@ func (g *Generator) mergeWithExisting(fileInfo *parser.FileInfo, results []*pars
 				// Method - check if it needs stub
 				for _, result := range results {
 					if result.Target.Name == funcName && useStub[result.Target.GetDisplayName()] {
-						// Replace with panic stub
+						// Replace with the configured placeholder, or a panic stub
+						if body, err := g.placeholderFor(result.Target, fset); err == nil && body != nil {
+							fn.Body = body
+							usedPlaceholder = true
+							break
+						}
 						fn.Body = &ast.BlockStmt{
 							List: []ast.Stmt{
 								&ast.ExprStmt{
@@ -170,6 +201,11 @@ func (g *Generator) mergeWithExisting(fileInfo *parser.FileInfo, results []*pars
 		return g.generateFileContent(fileInfo, results, "")
 	}
 
+	// The existing file may lack the imports of the placeholder
+	if usedPlaceholder {
+		return g.addImports(buf.String(), imports.AnalyzeRequiredImports(g.config.PlaceholderBody)), nil
+	}
+
 	return buf.String(), nil
 }
 
@@ -186,7 +222,7 @@ func (g *Generator) GenerateFile(fileInfo *parser.FileInfo, results []*parser.Ge
 
 	var existingContent string
 	if existingData, err := os.ReadFile(outputFile); err == nil {
-		existingContent = string(existingData)
+		existingContent = parser.NormalizeLineEndings(string(existingData))
 	}
 
 	// Generate the file content
@@ -203,9 +239,15 @@ func (g *Generator) GenerateFile(fileInfo *parser.FileInfo, results []*parser.Ge
 		formatted = []byte(content)
 	}
 
-	// File paths already determined above
+	// Keep the packages that could not be imported for WarnMissingImports
+	if missing, err := imports.DetectMissingImports(string(formatted)); err == nil && len(missing) > 0 {
+		g.missingImports[fileInfo.FilePath] = missing
+	} else {
+		delete(g.missingImports, fileInfo.FilePath)
+	}
 
-	// Write the generated file
+	// Write the generated file, keeping the line endings of the source
+	formatted = []byte(parser.RestoreLineEndings(string(formatted), fileInfo.CRLF))
 	if err := os.WriteFile(outputFile, formatted, 0644); err != nil {
 		return fmt.Errorf("failed to write file: %w", err)
 	}
@@ -215,8 +257,8 @@ func (g *Generator) GenerateFile(fileInfo *parser.FileInfo, results []*parser.Ge
 
 // generateFileContent creates the content for the generated file by replacing mantra functions
 func (g *Generator) generateFileContent(fileInfo *parser.FileInfo, results []*parser.GenerationResult, existingContent string) (string, error) {
-	// Start with the original source content
-	content := fileInfo.SourceContent
+	// Start with the original source content, including the package clause of fragments
+	content := fileInfo.ParsableContent()
 
 	// Add generated code header if not already present
 	if !strings.Contains(content, "Code generated by mantra") {
@@ -289,12 +331,25 @@ func (g *Generator) generateFileContent(fileInfo *parser.FileInfo, results []*pa
 		if result.Success {
 			implImports := imports.AnalyzeRequiredImports(result.Implementation)
 			requiredImports = imports.MergeImports(requiredImports, implImports)
+			// Imports declared explicitly by structured output, unless unused
+			requiredImports = imports.MergeImports(requiredImports, imports.FilterUsedImports(result.Implementation, result.Imports))
+		}
+	}
+
+	// Placeholders written for targets without an implementation may need imports too
+	for _, target := range targetsToProcess {
+		if !target.GenerationFailed && cleanCode(target.Implementation) != "" {
+			continue
+		}
+		if body, err := g.placeholderFor(target, token.NewFileSet()); err == nil && body != nil {
+			requiredImports = imports.MergeImports(requiredImports, imports.AnalyzeRequiredImports(g.config.PlaceholderBody))
+			break
 		}
 	}
 
 	// Extract blank imports from the original file (imports marked with _)
 	// These indicate packages that should be used in generated code
-	blankImports := imports.ExtractBlankImports(fileInfo.SourceContent)
+	blankImports := imports.ExtractBlankImports(fileInfo.ParsableContent())
 	if len(blankImports) > 0 {
 		requiredImports = imports.MergeImports(requiredImports, blankImports)
 	}
@@ -335,8 +390,12 @@ func (g *Generator) replaceAllFunctionsWithChecksum(content string, targets []*p
 		var checksumComment string
 
 		if target.GenerationFailed {
-			// For failed targets, keep original body and set detailed failure comment
-			implBody = target.FuncDecl.Body // Keep original implementation (panic)
+			// For failed targets, use the placeholder body and set detailed failure comment
+			placeholder, err := g.placeholderBody(target, fset)
+			if err != nil {
+				return "", err
+			}
+			implBody = placeholder
 			if target.FailureReason != nil {
 				checksumComment = fmt.Sprintf("// mantra:failed:%s: %s",
 					target.FailureReason.Phase, target.FailureReason.Message)
@@ -347,7 +406,11 @@ func (g *Generator) replaceAllFunctionsWithChecksum(content string, targets []*p
 			// Parse the implementation as a function body
 			cleanedImpl := cleanCode(target.Implementation)
 			var err error
-			implBody, err = g.parseImplementationAsBlockWithFileSet(cleanedImpl, fset)
+			if cleanedImpl == "" {
+				implBody, err = g.placeholderBody(target, fset)
+			} else {
+				implBody, err = g.parseImplementationAsBlockWithFileSet(cleanedImpl, fset)
+			}
 			if err != nil {
 				return "", fmt.Errorf("failed to parse implementation for %s: %w", target.Name, err)
 			}
@@ -366,61 +429,82 @@ func (g *Generator) replaceAllFunctionsWithChecksum(content string, targets []*p
 		}
 	}
 
-	// Find and update all target functions in the AST in a single pass
+	// Find and update all target functions in the AST in a single pass.
+	// Declarations are looked up by key, so the cost is linear in the file size
+	// regardless of the number of targets.
 	processedCount := 0
-	ast.Inspect(node, func(n ast.Node) bool {
-		if funcDecl, ok := n.(*ast.FuncDecl); ok {
-			// Try to match this function with any of our targets
-			for key, data := range sourceTargetData {
-				if g.isTargetFunction(funcDecl, data.sourceTarget) {
-					processedCount++
-
-					// Replace function body with the new implementation
-					funcDecl.Body = data.implBody
-
-					// Remove old doc from file's Comments list if exists
-					if funcDecl.Doc != nil {
-						for i, cg := range node.Comments {
-							if cg == funcDecl.Doc {
-								node.Comments = append(node.Comments[:i], node.Comments[i+1:]...)
-								break
-							}
-						}
-					}
+	replacedDocs := make(map[*ast.CommentGroup]bool)
+	for _, decl := range node.Decls {
+		// Locate the doc comment and body of functions and closures
+		var doc **ast.CommentGroup
+		var body **ast.BlockStmt
+		var key string
+		switch x := decl.(type) {
+		case *ast.FuncDecl:
+			doc, body = &x.Doc, &x.Body
+			key = g.getDeclKey(x)
+		case *ast.GenDecl:
+			name, lit := parser.ClosureLiteral(x)
+			if lit == nil {
+				continue
+			}
+			doc, body = &x.Doc, &lit.Body
+			key = closureKeyPrefix + name.Name
+		default:
+			continue
+		}
 
-					// Build new comments: original + checksum
-					var comments []*ast.Comment
-					pos := funcDecl.Pos() - 1
-
-					// Copy original comments from source
-					if data.sourceTarget.FuncDecl.Doc != nil {
-						for i, c := range data.sourceTarget.FuncDecl.Doc.List {
-							comments = append(comments, &ast.Comment{
-								Slash: pos - token.Pos(len(data.sourceTarget.FuncDecl.Doc.List)-i),
-								Text:  c.Text,
-							})
-						}
-					}
+		data, ok := sourceTargetData[key]
+		if !ok {
+			continue
+		}
+		processedCount++
 
-					// Add checksum
-					comments = append(comments, &ast.Comment{
-						Slash: pos,
-						Text:  data.checksum,
-					})
+		// Replace function body with the new implementation
+		*body = data.implBody
 
-					// Create and set new doc
-					newDoc := &ast.CommentGroup{List: comments}
-					funcDecl.Doc = newDoc
-					node.Comments = append(node.Comments, newDoc)
+		// Old doc is removed from the file's Comments list after the pass
+		if *doc != nil {
+			replacedDocs[*doc] = true
+		}
 
-					// Remove from map to avoid processing again
-					delete(sourceTargetData, key)
-					break
-				}
+		// Build new comments: original + checksum
+		var comments []*ast.Comment
+		pos := decl.Pos() - 1
+
+		// Copy original comments from source
+		if data.sourceTarget.FuncDecl.Doc != nil {
+			for i, c := range data.sourceTarget.FuncDecl.Doc.List {
+				comments = append(comments, &ast.Comment{
+					Slash: pos - token.Pos(len(data.sourceTarget.FuncDecl.Doc.List)-i),
+					Text:  c.Text,
+				})
 			}
 		}
-		return true
-	})
+
+		// Add checksum
+		comments = append(comments, &ast.Comment{
+			Slash: pos,
+			Text:  data.checksum,
+		})
+
+		// Create and set new doc
+		newDoc := &ast.CommentGroup{List: comments}
+		*doc = newDoc
+		node.Comments = append(node.Comments, newDoc)
+
+		// Remove from map to avoid processing again
+		delete(sourceTargetData, key)
+	}
+
+	// Remove replaced docs from the file's Comments list in one pass
+	comments := node.Comments[:0]
+	for _, cg := range node.Comments {
+		if !replacedDocs[cg] {
+			comments = append(comments, cg)
+		}
+	}
+	node.Comments = comments
 
 	if processedCount != len(targets) {
 		// List unprocessed functions for better debugging
@@ -443,33 +527,76 @@ func (g *Generator) replaceAllFunctionsWithChecksum(content string, targets []*p
 	return buf.String(), nil
 }
 
-// getTargetKey creates a unique key for a target function
-func (g *Generator) getTargetKey(target *parser.Target) string {
-	if target.Receiver != nil {
-		return fmt.Sprintf("%s.%s", target.Receiver.Type, target.Name)
+// placeholderBody returns the body used for targets without an implementation:
+// the configured placeholder, or the body of the source declaration
+func (g *Generator) placeholderBody(target *parser.Target, fset *token.FileSet) (*ast.BlockStmt, error) {
+	body, err := g.placeholderFor(target, fset)
+	if err != nil {
+		return nil, err
 	}
-	return target.Name
+	if body == nil {
+		return target.FuncDecl.Body, nil
+	}
+	return body, nil
 }
 
-// isTargetFunction checks if the given function declaration matches the target
-func (g *Generator) isTargetFunction(funcDecl *ast.FuncDecl, target *parser.Target) bool {
-	// Check function name
-	if funcDecl.Name.Name != target.Name {
-		return false
+// placeholderFor parses the configured placeholder for the target. It returns
+// nil if no placeholder is configured, or if its return statements return a
+// different number of values than the target, where it would not compile.
+func (g *Generator) placeholderFor(target *parser.Target, fset *token.FileSet) (*ast.BlockStmt, error) {
+	if g.config.PlaceholderBody == "" {
+		return nil, nil
 	}
 
-	// Check receiver for methods
-	if target.Receiver != nil {
-		if funcDecl.Recv == nil || len(funcDecl.Recv.List) == 0 {
+	body, err := g.parseImplementationAsBlockWithFileSet(g.config.PlaceholderBody, fset)
+	if err != nil {
+		return nil, fmt.Errorf("failed to parse placeholder body: %w", err)
+	}
+	if !returnsFit(body, len(target.Returns)) {
+		return nil, nil
+	}
+	return body, nil
+}
+
+// returnsFit reports whether every return statement of body, outside of
+// function literals, returns the given number of values
+func returnsFit(body *ast.BlockStmt, results int) bool {
+	fits := true
+	ast.Inspect(body, func(n ast.Node) bool {
+		switch x := n.(type) {
+		case *ast.FuncLit:
 			return false
+		case *ast.ReturnStmt:
+			if len(x.Results) != results {
+				fits = false
+			}
 		}
+		return fits
+	})
+	return fits
+}
 
-		receiverType := analysis.ExtractTypeString(funcDecl.Recv.List[0].Type)
-		return receiverType == target.Receiver.Type
+// closureKeyPrefix distinguishes closures from functions of the same name
+const closureKeyPrefix = "var "
+
+// getTargetKey creates a unique key for a target function
+func (g *Generator) getTargetKey(target *parser.Target) string {
+	if target.Closure {
+		return closureKeyPrefix + target.Name
 	}
+	if target.Receiver != nil {
+		return fmt.Sprintf("%s.%s", target.Receiver.Type, target.Name)
+	}
+	return target.Name
+}
 
-	// For functions (no receiver), ensure funcDecl also has no receiver
-	return funcDecl.Recv == nil
+// getDeclKey creates the key of a function declaration, matching getTargetKey
+func (g *Generator) getDeclKey(funcDecl *ast.FuncDecl) string {
+	if funcDecl.Recv != nil && len(funcDecl.Recv.List) > 0 {
+		receiverType := analysis.ExtractTypeString(funcDecl.Recv.List[0].Type)
+		return fmt.Sprintf("%s.%s", receiverType, funcDecl.Name.Name)
+	}
+	return funcDecl.Name.Name
 }
 
 // parseImplementationAsBlockWithFileSet parses implementation code as a block statement.
@@ -496,6 +623,28 @@ func (g *Generator) parseImplementationAsBlockWithFileSet(implementation string,
 	return funcDecl.Body, nil
 }
 
+// WrapBody places a generated body between a prologue and an epilogue.
+// The combined body must parse as Go statements.
+func WrapBody(body, prologue, epilogue string) (string, error) {
+	if prologue == "" && epilogue == "" {
+		return body, nil
+	}
+
+	var parts []string
+	for _, part := range []string{prologue, cleanCode(body), epilogue} {
+		if part = strings.Trim(part, "\n"); part != "" {
+			parts = append(parts, part)
+		}
+	}
+	wrapped := strings.Join(parts, "\n")
+
+	testFunc := fmt.Sprintf("package main\nfunc test() {\n%s\n}", wrapped)
+	if _, err := goparser.ParseFile(token.NewFileSet(), "", testFunc, 0); err != nil {
+		return "", fmt.Errorf("wrapped body is not valid Go: %w", err)
+	}
+	return wrapped, nil
+}
+
 // cleanCode removes markdown formatting and extracts function body from AI responses.
 // It handles cases where the AI includes function signatures or markdown code blocks.
 func cleanCode(response string) string {
@@ -539,11 +688,13 @@ func cleanCode(response string) string {
 		}
 	}
 
-	// Check if response contains function signature and extract body
-	if strings.Contains(response, "func ") && strings.Contains(response, "{") {
-		// Find the first opening brace
-		braceIdx := strings.Index(response, "{")
+	// Check if response contains function signature and extract body. Only a
+	// line starting with "func " counts, so comments mentioning a func are kept.
+	if funcIdx := funcDeclIndex(response); funcIdx != -1 {
+		// Find the first opening brace after the signature
+		braceIdx := strings.Index(response[funcIdx:], "{")
 		if braceIdx != -1 {
+			braceIdx += funcIdx
 			// Find the matching closing brace
 			braceCount := 1
 			i := braceIdx + 1
@@ -578,3 +729,16 @@ func cleanCode(response string) string {
 
 	return response
 }
+
+// funcDeclIndex returns the offset of the first line starting with a function
+// declaration, or -1 if there is none
+func funcDeclIndex(response string) int {
+	offset := 0
+	for _, line := range strings.SplitAfter(response, "\n") {
+		if strings.HasPrefix(strings.TrimSpace(line), "func ") {
+			return offset
+		}
+		offset += len(line)
+	}
+	return -1
+}
@@ -0,0 +1,540 @@
+package codegen
+
+import (
+	"go/ast"
+	"go/importer"
+	goparser "go/parser"
+	"go/token"
+	"go/types"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestGenerateFile_PreservesCRLF(t *testing.T) {
+	tempDir := t.TempDir()
+	sourceFile := filepath.Join(tempDir, "math.go")
+	destDir := filepath.Join(tempDir, "generated")
+
+	source := strings.Join([]string{
+		"package math",
+		"",
+		"// mantra: add two numbers",
+		"func Add(a, b int) int {",
+		"\tpanic(\"not implemented\")",
+		"}",
+		"",
+	}, "\r\n")
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := parser.ParseFileInfo(sourceFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !fileInfo.CRLF {
+		t.Fatal("Expected CRLF line endings to be detected")
+	}
+	if strings.Contains(fileInfo.SourceContent, "\r") {
+		t.Error("Expected source content to be normalized to LF")
+	}
+	if len(fileInfo.Targets) != 1 || fileInfo.Targets[0].Instruction != "add two numbers" {
+		t.Fatalf("Unexpected targets: %+v", fileInfo.Targets)
+	}
+
+	gen := New(&Config{
+		Dest:          destDir,
+		PackageName:   "generated",
+		SourcePackage: "math",
+	})
+	results := []*parser.GenerationResult{
+		{
+			Target:         fileInfo.Targets[0],
+			Success:        true,
+			Implementation: "return a + b",
+		},
+	}
+	if err := gen.GenerateFile(fileInfo, results); err != nil {
+		t.Fatal(err)
+	}
+
+	output, err := os.ReadFile(filepath.Join(destDir, "math.go"))
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	content := string(output)
+	if !strings.Contains(content, "return a + b\r\n") {
+		t.Errorf("Expected generated body with CRLF, got:\n%q", content)
+	}
+	if strings.Count(content, "\n") != strings.Count(content, "\r\n") {
+		t.Errorf("Expected only CRLF line endings, got:\n%q", content)
+	}
+	if strings.Contains(content, "\r\r") {
+		t.Errorf("Expected no doubled carriage returns, got:\n%q", content)
+	}
+}
+
+func TestGenerateFile_Closure(t *testing.T) {
+	tempDir := t.TempDir()
+	sourceFile := filepath.Join(tempDir, "handlers.go")
+	destDir := filepath.Join(tempDir, "generated")
+
+	source := `package handlers
+
+import "net/http"
+
+// mantra: respond with 204 No Content
+var handler = func(w http.ResponseWriter, r *http.Request) {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := parser.ParseFileInfo(sourceFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(fileInfo.Targets) != 1 || !fileInfo.Targets[0].Closure {
+		t.Fatalf("Expected one closure target, got: %+v", fileInfo.Targets)
+	}
+
+	target := fileInfo.Targets[0]
+	expectedSig := "var handler = func(w http.ResponseWriter, r *http.Request)"
+	if sig := target.GetFunctionSignature(); sig != expectedSig {
+		t.Errorf("Expected signature %q, got %q", expectedSig, sig)
+	}
+
+	gen := New(&Config{
+		Dest:          destDir,
+		PackageName:   "generated",
+		SourcePackage: "handlers",
+	})
+	results := []*parser.GenerationResult{
+		{
+			Target:         target,
+			Success:        true,
+			Implementation: "w.WriteHeader(http.StatusNoContent)",
+		},
+	}
+	if err := gen.GenerateFile(fileInfo, results); err != nil {
+		t.Fatal(err)
+	}
+
+	output, err := os.ReadFile(filepath.Join(destDir, "handlers.go"))
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	content := string(output)
+	if !strings.Contains(content, "w.WriteHeader(http.StatusNoContent)") {
+		t.Errorf("Expected closure body to be replaced, got:\n%s", content)
+	}
+	if !strings.Contains(content, "// mantra:checksum:") {
+		t.Errorf("Expected checksum comment, got:\n%s", content)
+	}
+	if strings.Contains(content, "not implemented") {
+		t.Errorf("Expected panic stub to be removed, got:\n%s", content)
+	}
+}
+
+func TestGenerateFile_PartialResults(t *testing.T) {
+	tempDir := t.TempDir()
+	sourceFile := filepath.Join(tempDir, "math.go")
+	destDir := filepath.Join(tempDir, "generated")
+
+	source := `package math
+
+// mantra: add two numbers
+func Add(a, b int) int {
+	panic("not implemented")
+}
+
+// mantra: subtract b from a
+func Sub(a, b int) int {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := parser.ParseFileInfo(sourceFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(fileInfo.Targets) != 2 {
+		t.Fatalf("Expected 2 targets, got %d", len(fileInfo.Targets))
+	}
+
+	gen := New(&Config{
+		Dest:          destDir,
+		PackageName:   "generated",
+		SourcePackage: "math",
+	})
+	results := []*parser.GenerationResult{
+		{
+			Target:         fileInfo.Targets[0],
+			Success:        true,
+			Implementation: "return a + b",
+		},
+		{
+			Target:  fileInfo.Targets[1],
+			Success: false,
+			FailureReason: &parser.FailureReason{
+				Phase:   "implementation",
+				Message: "timeout",
+			},
+		},
+	}
+	if err := gen.GenerateFile(fileInfo, results); err != nil {
+		t.Fatal(err)
+	}
+
+	output, err := os.ReadFile(filepath.Join(destDir, "math.go"))
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	content := string(output)
+	if !strings.Contains(content, "return a + b") || strings.Count(content, "// mantra:checksum:") != 1 {
+		t.Errorf("Expected the successful target to be applied with a checksum, got:\n%s", content)
+	}
+	if !strings.Contains(content, "// mantra:failed:implementation: timeout") {
+		t.Errorf("Expected a failure comment for the failed target, got:\n%s", content)
+	}
+	if strings.Count(content, `panic("not implemented")`) != 1 {
+		t.Errorf("Expected the failed target to keep its stub, got:\n%s", content)
+	}
+}
+
+func TestGenerateFile_ChecksumAfterMultiLineInstruction(t *testing.T) {
+	tempDir := t.TempDir()
+	sourceFile := filepath.Join(tempDir, "bank.go")
+	destDir := filepath.Join(tempDir, "generated")
+
+	source := `package bank
+
+// Transfer moves money between accounts.
+// mantra: move amount from one balance to the other
+// fail if the source balance is insufficient
+// return the new balances
+func Transfer(from, to, amount int) (int, int, error) {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := parser.ParseFileInfo(sourceFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	target := fileInfo.Targets[0]
+	if strings.Count(target.Instruction, "\n") != 2 {
+		t.Fatalf("Expected a 3-line instruction, got %q", target.Instruction)
+	}
+
+	gen := New(&Config{
+		Dest:          destDir,
+		PackageName:   "generated",
+		SourcePackage: "bank",
+	})
+	results := []*parser.GenerationResult{{
+		Target:         target,
+		Success:        true,
+		Implementation: "if from < amount {\n\treturn from, to, errors.New(\"insufficient balance\")\n}\nreturn from - amount, to + amount, nil",
+		Imports:        []string{"errors"},
+	}}
+	if err := gen.GenerateFile(fileInfo, results); err != nil {
+		t.Fatal(err)
+	}
+
+	output, err := os.ReadFile(filepath.Join(destDir, "bank.go"))
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// The marker goes below the whole instruction, directly above the signature
+	expected := "// Transfer moves money between accounts.\n" +
+		"// mantra: move amount from one balance to the other\n" +
+		"// fail if the source balance is insufficient\n" +
+		"// return the new balances\n" +
+		checksum.FormatComment(checksum.Calculate(target)) + "\n" +
+		"func Transfer(from, to, amount int) (int, int, error) {\n"
+	if !strings.Contains(string(output), expected) {
+		t.Errorf("Expected the checksum between instruction and signature, got:\n%s", output)
+	}
+}
+
+func TestGenerateFile_PlaceholderBody(t *testing.T) {
+	tempDir := t.TempDir()
+	sourceFile := filepath.Join(tempDir, "store.go")
+	destDir := filepath.Join(tempDir, "generated")
+
+	source := `package store
+
+// mantra: save the value
+func Save(value string) error {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := parser.ParseFileInfo(sourceFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	gen := New(&Config{
+		Dest:            destDir,
+		PackageName:     "generated",
+		SourcePackage:   "store",
+		PlaceholderBody: `return errors.New("not implemented")`,
+	})
+	results := []*parser.GenerationResult{
+		{
+			Target:        fileInfo.Targets[0],
+			Success:       false,
+			FailureReason: &parser.FailureReason{Phase: "implementation", Message: "timeout"},
+		},
+	}
+	if err := gen.GenerateFile(fileInfo, results); err != nil {
+		t.Fatal(err)
+	}
+
+	output, err := os.ReadFile(filepath.Join(destDir, "store.go"))
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	content := string(output)
+	if !strings.Contains(content, `return errors.New("not implemented")`) {
+		t.Errorf("Expected placeholder body, got:\n%s", content)
+	}
+	if strings.Contains(content, "panic(") {
+		t.Errorf("Expected panic stub to be replaced, got:\n%s", content)
+	}
+}
+
+func TestCleanCode_KeepsComments(t *testing.T) {
+	tests := []struct {
+		name     string
+		response string
+		expected string
+	}{
+		{
+			name:     "plain body",
+			response: "// Reject empty input early\nif s == \"\" {\n\treturn 0\n}\nreturn len(s) // bytes, not runes",
+			expected: "// Reject empty input early\nif s == \"\" {\n\treturn 0\n}\nreturn len(s) // bytes, not runes",
+		},
+		{
+			name:     "comment mentioning a func",
+			response: "// Use the func from the pool {if any}\nreturn pool.Get()",
+			expected: "// Use the func from the pool {if any}\nreturn pool.Get()",
+		},
+		{
+			name:     "whole function in markdown",
+			response: "```go\n// Len returns the length\nfunc Len(s string) int {\n\t// bytes, not runes\n\treturn len(s)\n}\n```",
+			expected: "\t// bytes, not runes\n\treturn len(s)",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := cleanCode(tt.response); got != tt.expected {
+				t.Errorf("Expected:\n%s\ngot:\n%s", tt.expected, got)
+			}
+		})
+	}
+}
+
+func TestGenerateFile_PlaceholderCompiles(t *testing.T) {
+	tempDir := t.TempDir()
+	sourceFile := filepath.Join(tempDir, "store.go")
+	destDir := filepath.Join(tempDir, "generated")
+	outputFile := filepath.Join(destDir, "store.go")
+
+	source := `package store
+
+type Store struct{}
+
+// mantra: validate the store
+func (s *Store) Validate() error {
+	panic("not implemented")
+}
+
+// mantra: count the entries
+func (s *Store) Count() (int, error) {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := parser.ParseFileInfo(sourceFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	validate, count := fileInfo.Targets[0], fileInfo.Targets[1]
+
+	gen := New(&Config{
+		Dest:            destDir,
+		PackageName:     "generated",
+		SourcePackage:   "store",
+		PlaceholderBody: `return errors.New("not implemented")`,
+	})
+
+	// Failed targets get the placeholder where its return statement fits
+	failed := &parser.FailureReason{Phase: "implementation", Message: "timeout"}
+	if err := gen.GenerateFile(fileInfo, []*parser.GenerationResult{
+		{Target: validate, FailureReason: failed},
+		{Target: count, FailureReason: failed},
+	}); err != nil {
+		t.Fatal(err)
+	}
+	content := typeCheck(t, outputFile)
+	if !strings.Contains(content, `return errors.New("not implemented")`) || !strings.Contains(content, `panic("not implemented")`) {
+		t.Errorf("Expected placeholder for Validate and the source body for Count, got:\n%s", content)
+	}
+
+	// Stubs of an existing file without the import get it as well
+	if err := gen.GenerateFile(fileInfo, []*parser.GenerationResult{
+		{Target: validate, Success: true, Implementation: "return nil"},
+		{Target: count, Success: true, Implementation: "return 0, nil"},
+	}); err != nil {
+		t.Fatal(err)
+	}
+	if content := typeCheck(t, outputFile); strings.Contains(content, `"errors"`) {
+		t.Fatalf("Expected no errors import without placeholders, got:\n%s", content)
+	}
+	if err := gen.PrepareTargetStubs(fileInfo, map[string]bool{"(*Store).Validate": true}); err != nil {
+		t.Fatal(err)
+	}
+	if content := typeCheck(t, outputFile); !strings.Contains(content, `return errors.New("not implemented")`) {
+		t.Errorf("Expected placeholder stub for Validate, got:\n%s", content)
+	}
+}
+
+// typeCheck fails the test if the Go file does not compile on its own, and
+// returns its content
+func TestGenerateFile_UnusedDeclaredImport(t *testing.T) {
+	tempDir := t.TempDir()
+	sourceFile := filepath.Join(tempDir, "math.go")
+	destDir := filepath.Join(tempDir, "generated")
+
+	source := `package math
+
+// mantra: add a and b
+func Add(a, b int) int {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(sourceFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := parser.ParseFileInfo(sourceFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	gen := New(&Config{
+		Dest:          destDir,
+		PackageName:   "generated",
+		SourcePackage: "math",
+	})
+	// The model declared an import that the body does not use
+	results := []*parser.GenerationResult{{
+		Target:         fileInfo.Targets[0],
+		Success:        true,
+		Implementation: "return a + b",
+		Imports:        []string{"fmt"},
+	}}
+	if err := gen.GenerateFile(fileInfo, results); err != nil {
+		t.Fatal(err)
+	}
+
+	if content := typeCheck(t, filepath.Join(destDir, "math.go")); strings.Contains(content, `"fmt"`) {
+		t.Errorf("Expected unused import to be dropped, got:\n%s", content)
+	}
+}
+
+func typeCheck(t *testing.T, path string) string {
+	t.Helper()
+
+	content, err := os.ReadFile(path)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	fset := token.NewFileSet()
+	file, err := goparser.ParseFile(fset, path, content, 0)
+	if err != nil {
+		t.Fatalf("Generated file does not parse: %v\n%s", err, content)
+	}
+	conf := types.Config{Importer: importer.ForCompiler(fset, "source", nil)}
+	if _, err := conf.Check("generated", fset, []*ast.File{file}, nil); err != nil {
+		t.Errorf("Generated file does not compile: %v\n%s", err, content)
+	}
+	return string(content)
+}
+
+func TestWrapBody(t *testing.T) {
+	tests := []struct {
+		name     string
+		body     string
+		prologue string
+		epilogue string
+		expected string
+		wantErr  bool
+	}{
+		{
+			name:     "no wrapping",
+			body:     "return a + b",
+			expected: "return a + b",
+		},
+		{
+			name:     "prologue only",
+			body:     "```go\nreturn a + b\n```",
+			prologue: "defer func() { recover() }()\n",
+			expected: "defer func() { recover() }()\nreturn a + b",
+		},
+		{
+			name:     "prologue and epilogue",
+			body:     "x := 1\n_ = x",
+			prologue: "start()",
+			epilogue: "finish()",
+			expected: "start()\nx := 1\n_ = x\nfinish()",
+		},
+		{
+			name:     "invalid combination",
+			body:     "return a + b",
+			prologue: "if true {",
+			wantErr:  true,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := WrapBody(tt.body, tt.prologue, tt.epilogue)
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("WrapBody() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if !tt.wantErr && got != tt.expected {
+				t.Errorf("Expected:\n%s\ngot:\n%s", tt.expected, got)
+			}
+		})
+	}
+}
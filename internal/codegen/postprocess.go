@@ -0,0 +1,43 @@
+package codegen
+
+import (
+	"bytes"
+	"context"
+	"fmt"
+	"os/exec"
+	"runtime"
+	"strings"
+)
+
+// PostProcess pipes a generated body through a shell command and returns its
+// output as the new body. The command fails the target if it exits non-zero.
+func PostProcess(ctx context.Context, command, dir, body string) (string, error) {
+	if command == "" {
+		return body, nil
+	}
+
+	shell, flag := "sh", "-c"
+	if runtime.GOOS == "windows" {
+		shell, flag = "cmd", "/C"
+	}
+
+	var stdout, stderr bytes.Buffer
+	cmd := exec.CommandContext(ctx, shell, flag, command)
+	cmd.Dir = dir
+	cmd.Stdin = strings.NewReader(cleanCode(body) + "\n")
+	cmd.Stdout = &stdout
+	cmd.Stderr = &stderr
+
+	if err := cmd.Run(); err != nil {
+		if msg := strings.TrimSpace(stderr.String()); msg != "" {
+			return "", fmt.Errorf("post-process command failed: %w: %s", err, msg)
+		}
+		return "", fmt.Errorf("post-process command failed: %w", err)
+	}
+
+	processed := strings.TrimRight(stdout.String(), "\n")
+	if strings.TrimSpace(processed) == "" {
+		return "", fmt.Errorf("post-process command produced no output")
+	}
+	return processed, nil
+}
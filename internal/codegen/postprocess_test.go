@@ -0,0 +1,70 @@
+package codegen
+
+import (
+	"context"
+	"runtime"
+	"strings"
+	"testing"
+)
+
+func TestPostProcess(t *testing.T) {
+	if runtime.GOOS == "windows" {
+		t.Skip("commands use POSIX shell syntax")
+	}
+
+	tests := []struct {
+		name     string
+		command  string
+		body     string
+		expected string
+		errMsg   string
+	}{
+		{
+			name:     "no command",
+			body:     "return a + b",
+			expected: "return a + b",
+		},
+		{
+			name:     "receives cleaned body",
+			command:  "cat",
+			body:     "```go\nreturn a + b\n```",
+			expected: "return a + b",
+		},
+		{
+			name:     "rewrites body",
+			command:  "sed 's/a + b/b + a/'",
+			body:     "return a + b",
+			expected: "return b + a",
+		},
+		{
+			name:    "non-zero exit",
+			command: "echo 'lint failed' >&2; exit 1",
+			body:    "return a + b",
+			errMsg:  "lint failed",
+		},
+		{
+			name:    "empty output",
+			command: "true",
+			body:    "return a + b",
+			errMsg:  "no output",
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := PostProcess(context.Background(), tt.command, t.TempDir(), tt.body)
+			if tt.errMsg != "" {
+				if err == nil || !strings.Contains(err.Error(), tt.errMsg) {
+					t.Errorf("Expected error containing %q, got %v", tt.errMsg, err)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("Unexpected error: %v", err)
+			}
+			if got != tt.expected {
+				t.Errorf("Expected %q, got %q", tt.expected, got)
+			}
+		})
+	}
+}
@@ -13,11 +13,13 @@ import (
 
 	"golang.org/x/sync/errgroup"
 
+	"github.com/rail44/mantra/internal/codegen"
 	"github.com/rail44/mantra/internal/config"
 	"github.com/rail44/mantra/internal/llm"
 	"github.com/rail44/mantra/internal/log"
 	"github.com/rail44/mantra/internal/parser"
 	"github.com/rail44/mantra/internal/phase"
+	"github.com/rail44/mantra/internal/prompt"
 	"github.com/rail44/mantra/internal/ui"
 )
 
@@ -27,6 +29,13 @@ type ParallelCoder struct {
 	config       *config.Config
 	logger       *slog.Logger
 	httpClient   *http.Client // Shared HTTP client for connection pooling
+
+	promptOptions prompt.Options // Prompt settings shared by all targets
+	spent         float64        // Cost already spent against the budget in dollars
+
+	// onResult is called with each result as soon as its target completes.
+	// Calls are serialized.
+	onResult func(*parser.GenerationResult)
 }
 
 // NewParallelCoder creates a new parallel coder
@@ -41,12 +50,37 @@ func NewParallelCoder(clientConfig *llm.ClientConfig, cfg *config.Config) *Paral
 	}
 }
 
+// SetResultHandler registers a function called with each result as soon as its
+// target completes, e.g. to write files before all targets are done
+func (c *ParallelCoder) SetResultHandler(handler func(*parser.GenerationResult)) {
+	c.onResult = handler
+}
+
+// SetSpent records the cost in dollars already spent against the budget,
+// e.g. by earlier attempts of the same run
+func (c *ParallelCoder) SetSpent(spent float64) {
+	c.spent = spent
+}
+
+// PhaseBudget is the failure phase of targets skipped because the cost budget is spent
+const PhaseBudget = "budget"
+
 // TargetContext contains a target and its associated file context
 type TargetContext struct {
 	Target      *parser.Target
 	Index       int
 	FileContent string
 	FileInfo    *parser.FileInfo
+
+	// TestFailure holds the code and test output of a previous attempt
+	// that failed the feedback test command (nil on the first attempt)
+	TestFailure *TestFailure
+}
+
+// TestFailure describes a generated implementation whose tests failed
+type TestFailure struct {
+	Code   string
+	Output string
 }
 
 // ExecuteTargets generates implementations for all targets in parallel
@@ -55,6 +89,36 @@ func (c *ParallelCoder) ExecuteTargets(ctx context.Context, targets []TargetCont
 		return []*parser.GenerationResult{}, nil
 	}
 
+	// Load prompt customization once for all targets
+	if c.config.PromptTemplate != "" {
+		tmpl, err := prompt.LoadTemplate(c.config.PromptTemplate)
+		if err != nil {
+			return nil, err
+		}
+		c.promptOptions.Template = tmpl
+	}
+	c.promptOptions.PackageContext = c.config.IncludePackageContext
+	c.promptOptions.MaxContextTokens = c.config.MaxContextTokens
+	c.promptOptions.MaxExamples = c.config.MaxExamples
+	c.promptOptions.PatternExamples = c.config.PatternExamples
+	for _, example := range c.config.Examples {
+		c.promptOptions.Examples = append(c.promptOptions.Examples, prompt.Example{
+			Signature:   example.Signature,
+			Instruction: example.Instruction,
+			Body:        example.Body,
+		})
+	}
+
+	// Extract type information of all targets before the first LLM call,
+	// instead of once per phase while generations wait on it
+	builder := prompt.NewBuilder(c.logger)
+	builder.SetOptions(c.promptOptions)
+	prefetched := make([]*parser.Target, len(targets))
+	for i, tc := range targets {
+		prefetched[i] = tc.Target
+	}
+	c.promptOptions.Contexts = builder.PrefetchContexts(prefetched, c.config.GetContextConcurrency())
+
 	// Get project root from the first target's file path
 	projectRoot := findProjectRoot(filepath.Dir(targets[0].Target.FilePath))
 
@@ -66,6 +130,16 @@ func (c *ParallelCoder) ExecuteTargets(ctx context.Context, targets []TargetCont
 	var mu sync.Mutex
 	var allResults []*parser.GenerationResult
 
+	// Budget accounting. Running targets reserve the average cost of the
+	// generated ones, so that targets in flight cannot overshoot the budget.
+	// Until the first target is generated, its cost is unknown and only one
+	// target runs.
+	budgeted := c.config.MaxCost > 0
+	budgetChanged := sync.NewCond(&mu)
+	spent := c.spent     // Estimated cost of completed targets in dollars
+	var reserved float64 // Estimated cost of running targets in dollars
+	var generated, running int
+
 	// Start TUI in background
 	tuiDone := make(chan *ui.Model, 1)
 	go func() {
@@ -73,8 +147,17 @@ func (c *ParallelCoder) ExecuteTargets(ctx context.Context, targets []TargetCont
 		tuiDone <- model
 	}()
 
+	// Track remaining targets per file to report file progress
+	remaining := make(map[string]int)
+	for _, tc := range targets {
+		remaining[tc.Target.FilePath]++
+	}
+	filesTotal := len(remaining)
+	filesDone := 0
+	uiProgram.UpdateFileProgress(filesDone, filesTotal)
+
 	g, ctx := errgroup.WithContext(ctx)
-	g.SetLimit(16)
+	g.SetLimit(c.config.GetMaxConcurrency())
 
 	// Process each target in parallel
 	for _, tc := range targets {
@@ -82,19 +165,63 @@ func (c *ParallelCoder) ExecuteTargets(ctx context.Context, targets []TargetCont
 			// Register target with UI
 			uiProgram.AddTarget(tc.Target.GetDisplayName(), tc.Index, len(targets))
 
+			// Every record of this generation carries the same correlation ID
 			handler := log.NewCallbackHandler(
 				uiProgram.SendLog,
 			).WithAttrs([]slog.Attr{
 				slog.Int("targetIndex", tc.Index),
 				slog.Int("totalTargets", len(targets)),
 				slog.String("targetName", tc.Target.GetDisplayName()),
+				slog.String("generationID", log.NewCorrelationID()),
 			})
 
+			// Targets that would exceed the budget are left ungenerated
+			var overBudget bool
+			var estimate float64
+			if budgeted {
+				mu.Lock()
+				for generated == 0 && running > 0 {
+					budgetChanged.Wait()
+				}
+				if generated > 0 {
+					estimate = spent / float64(generated)
+				}
+				overBudget = spent >= c.config.MaxCost || spent+reserved+estimate > c.config.MaxCost
+				if !overBudget {
+					reserved += estimate
+					running++
+				}
+				mu.Unlock()
+			}
+
 			coder := NewTargetCoder(ctx, c, tc, projectRoot, slog.New(handler), uiProgram)
-			result := coder.Generate()
+			var result *parser.GenerationResult
+			if overBudget {
+				result = coder.budgetResult()
+			} else {
+				result = coder.Generate()
+			}
 
 			mu.Lock()
 			allResults = append(allResults, result)
+			if budgeted && !overBudget {
+				spent += c.config.EstimateCost(result.PromptTokens, result.CompletionTokens)
+				reserved -= estimate
+				running--
+				generated++
+				budgetChanged.Broadcast()
+			}
+			if c.onResult != nil {
+				c.onResult(result)
+			}
+			remaining[tc.Target.FilePath]--
+			if remaining[tc.Target.FilePath] == 0 {
+				filesDone++
+				uiProgram.UpdateFileProgress(filesDone, filesTotal)
+				coder.logger.Info("File complete",
+					slog.String("file", filepath.Base(tc.Target.FilePath)),
+					slog.String("progress", fmt.Sprintf("%d/%d", filesDone, filesTotal)))
+			}
 			mu.Unlock()
 			return nil
 		})
@@ -102,8 +229,7 @@ func (c *ParallelCoder) ExecuteTargets(ctx context.Context, targets []TargetCont
 
 	g.Wait()
 
-	// Stop the UI
-	time.Sleep(100 * time.Millisecond) // Allow final render
+	// Stop the UI. Updates sent so far are applied and rendered before it exits.
 	uiProgram.Quit()
 
 	// Wait for UI to finish and get final model
@@ -112,7 +238,7 @@ func (c *ParallelCoder) ExecuteTargets(ctx context.Context, targets []TargetCont
 	// Display logs for failed targets
 	// Only needed in TUI mode where logs are captured
 	// In plain mode, logs are already displayed in real-time
-	if finalModel.IsTUIEnabled() {
+	if finalModel != nil && finalModel.IsTUIEnabled() {
 		c.displayFailedTargetLogs(ctx, finalModel)
 	}
 
@@ -142,7 +268,7 @@ func NewTargetCoder(ctx context.Context, coder *ParallelCoder, target TargetCont
 }
 
 // Generate executes the code generation process for the target
-func (t *TargetCoder) Generate() *parser.GenerationResult {
+func (t *TargetCoder) Generate() (result *parser.GenerationResult) {
 	startTime := time.Now()
 
 	// Log generation start
@@ -156,9 +282,26 @@ func (t *TargetCoder) Generate() *parser.GenerationResult {
 	if err != nil {
 		return t.failureResult(startTime, "initialization", fmt.Sprintf("Failed to create AI client: %v", err), "Check your API configuration and network connection")
 	}
+	defer func() {
+		attachUsage(result, client)
+	}()
 
 	// Execute phases
 	runner := phase.NewRunner(client, t.logger)
+	runner.SetPromptOptions(t.coder.promptOptions)
+	if t.coder.config.SystemPrompt != "" {
+		runner.SetSystemPromptOverride(t.coder.config.SystemPrompt)
+	}
+	runner.SetStructuredOutput(t.coder.config.StructuredOutput)
+	runner.SetAllowComments(t.coder.config.AllowComments)
+	if t.coder.config.Seed != nil {
+		// Deterministic mode
+		runner.SetTemperatureOverride(0)
+	}
+	if opts := t.target.Target.Options; opts != nil && opts.Temperature != nil {
+		t.logger.Debug("Using temperature override", "temperature", *opts.Temperature)
+		runner.SetTemperatureOverride(*opts.Temperature)
+	}
 
 	// Phase 1: Context Gathering
 	contextResult, failureReason := t.executeContextGathering(runner)
@@ -166,19 +309,54 @@ func (t *TargetCoder) Generate() *parser.GenerationResult {
 		return t.phaseFailureResult(startTime, failureReason)
 	}
 
+	// Show the failing tests of the previous attempt
+	if failure := t.target.TestFailure; failure != nil {
+		if contextResult == nil {
+			contextResult = make(map[string]any)
+		}
+		contextResult["test_failure"] = map[string]any{
+			"code":   failure.Code,
+			"output": failure.Output,
+		}
+	}
+
 	// Phase 2: Implementation
 	implementation, failureReason := t.executeImplementation(runner, contextResult)
 	if failureReason != nil {
 		return t.phaseFailureResult(startTime, failureReason)
 	}
 
+	// Apply the configured prologue and epilogue
+	code, err := codegen.WrapBody(implementation.Code, t.coder.config.BodyPrologue, t.coder.config.BodyEpilogue)
+	if err != nil {
+		return t.failureResult(startTime, "implementation", err.Error(), "Check body_prologue and body_epilogue in mantra.toml")
+	}
+	implementation.Code = code
+
+	// Run the project's post-processor over the body
+	code, err = codegen.PostProcess(t.ctx, t.coder.config.PostProcessCommand, filepath.Dir(t.target.Target.FilePath), implementation.Code)
+	if err != nil {
+		return t.failureResult(startTime, "post_process", err.Error(), "Check post_process_command in mantra.toml")
+	}
+	implementation.Code = code
+
 	// Success
-	return t.successResult(startTime, implementation)
+	return t.successResult(startTime, implementation, client)
 }
 
 // createClient creates a new LLM client for this target
 func (t *TargetCoder) createClient() (*llm.Client, error) {
-	return llm.NewClient(t.coder.clientConfig, t.coder.httpClient, t.logger)
+	clientConfig := t.coder.clientConfig
+
+	// Apply inline model override from // mantra[model=...]:
+	if opts := t.target.Target.Options; opts != nil && opts.Model != "" {
+		t.logger.Debug("Using model override", "model", opts.Model)
+		overridden := *clientConfig
+		overridden.Model = opts.Model
+		clientConfig = &overridden
+	}
+
+	return llm.NewClient(clientConfig, t.coder.httpClient, t.logger)
 }
 
 // executeContextGathering executes the context gathering phase
@@ -187,12 +365,12 @@ func (t *TargetCoder) executeContextGathering(runner *phase.Runner) (map[string]
 }
 
 // executeImplementation executes the implementation phase
-func (t *TargetCoder) executeImplementation(runner *phase.Runner, contextResult map[string]any) (string, *parser.FailureReason) {
+func (t *TargetCoder) executeImplementation(runner *phase.Runner, contextResult map[string]any) (*phase.ImplementationOutput, *parser.FailureReason) {
 	return runner.ExecuteImplementation(t.ctx, t.target.Target, t.target.FileContent, t.target.FileInfo, t.projectRoot, contextResult)
 }
 
 // successResult creates a successful generation result
-func (t *TargetCoder) successResult(startTime time.Time, implementation string) *parser.GenerationResult {
+func (t *TargetCoder) successResult(startTime time.Time, implementation *phase.ImplementationOutput, client *llm.Client) *parser.GenerationResult {
 	duration := time.Since(startTime).Round(time.Millisecond)
 	t.logger.Info("Successfully generated implementation", "duration", duration)
 	t.markComplete()
@@ -200,11 +378,24 @@ func (t *TargetCoder) successResult(startTime time.Time, implementation string)
 	return &parser.GenerationResult{
 		Target:         t.target.Target,
 		Success:        true,
-		Implementation: implementation,
+		Implementation: implementation.Code,
+		Imports:        implementation.Imports,
 		Duration:       duration,
+		Model:          client.GetConfig().Model,
 	}
 }
 
+// attachUsage records the tokens and requests the client used for the result.
+// Failed generations consume them as well.
+func attachUsage(result *parser.GenerationResult, client *llm.Client) {
+	usage := client.Usage()
+	result.PromptTokens = usage.PromptTokens
+	result.CompletionTokens = usage.CompletionTokens
+	result.Requests = usage.Requests
+	result.RateLimited = usage.RateLimited
+	result.RequestTime = usage.RequestTime
+}
+
 // failureResult creates a failure result
 func (t *TargetCoder) failureResult(startTime time.Time, phase, message, context string) *parser.GenerationResult {
 	t.markFailed()
@@ -220,6 +411,14 @@ func (t *TargetCoder) failureResult(startTime time.Time, phase, message, context
 	}
 }
 
+// budgetResult creates the result of a target skipped because the cost budget is spent
+func (t *TargetCoder) budgetResult() *parser.GenerationResult {
+	t.logger.Warn("Skipped, cost budget reached")
+	return t.failureResult(time.Now(), PhaseBudget,
+		fmt.Sprintf("Skipped because the estimated cost would exceed the budget of $%.2f", t.coder.config.MaxCost),
+		"Raise --max-cost to generate the remaining targets")
+}
+
 // phaseFailureResult creates a failure result from a phase error
 func (t *TargetCoder) phaseFailureResult(startTime time.Time, failureReason *parser.FailureReason) *parser.GenerationResult {
 	t.markFailed()
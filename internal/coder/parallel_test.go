@@ -0,0 +1,126 @@
+package coder
+
+import (
+	"context"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+
+	"github.com/rail44/mantra/internal/config"
+	"github.com/rail44/mantra/internal/llm"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// resultResponse answers every request with a call of the result tool that
+// completes both phases, using a million prompt tokens
+const resultResponse = `{
+	"choices": [{"message": {"role": "assistant", "tool_calls": [{
+		"id": "call_1",
+		"type": "function",
+		"function": {"name": "result", "arguments": {"success": true, "constants": [], "code": "return 1"}}
+	}]}}],
+	"usage": {"prompt_tokens": 1000000, "completion_tokens": 0}
+}`
+
+func TestExecuteTargets_MaxCost(t *testing.T) {
+	// Each target makes two requests of $1, the budget is $5
+	tests := []struct {
+		name          string
+		spent         float64 // Cost spent before the run
+		wantSucceeded int
+		wantSkipped   int
+	}{
+		{name: "fresh budget", spent: 0, wantSucceeded: 2, wantSkipped: 4},
+		{name: "partly spent budget", spent: 3, wantSucceeded: 1, wantSkipped: 5},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			testExecuteTargetsMaxCost(t, tt.spent, tt.wantSucceeded, tt.wantSkipped)
+		})
+	}
+}
+
+func testExecuteTargetsMaxCost(t *testing.T, spent float64, wantSucceeded, wantSkipped int) {
+	server := httptest.NewServer(http.HandlerFunc(func(w http.ResponseWriter, r *http.Request) {
+		// Keep generations in flight long enough to overlap
+		time.Sleep(20 * time.Millisecond)
+		fmt.Fprint(w, resultResponse)
+	}))
+	defer server.Close()
+
+	pkgDir := t.TempDir()
+	if err := os.WriteFile(filepath.Join(pkgDir, "go.mod"), []byte("module numbers\n\ngo 1.21\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	var source strings.Builder
+	source.WriteString("package numbers\n")
+	for i := range 6 {
+		fmt.Fprintf(&source, "\n// mantra: return one\nfunc One%d() int {\n\tpanic(\"not implemented\")\n}\n", i)
+	}
+	sourceFile := filepath.Join(pkgDir, "numbers.go")
+	if err := os.WriteFile(sourceFile, []byte(source.String()), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := parser.ParseFileInfo(sourceFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	var targets []TargetContext
+	for i, target := range fileInfo.Targets {
+		targets = append(targets, TargetContext{
+			Target:      target,
+			Index:       i + 1,
+			FileContent: source.String(),
+			FileInfo:    fileInfo,
+		})
+	}
+
+	cfg := &config.Config{
+		Model:          "test-model",
+		URL:            server.URL,
+		Dest:           filepath.Join(pkgDir, "generated"),
+		Plain:          true,
+		MaxConcurrency: 4,
+		MaxCost:        5,
+		Pricing:        &config.PricingConfig{PromptPerMillion: 1},
+	}
+	clientConfig := &llm.ClientConfig{URL: server.URL, Model: cfg.Model}
+
+	parallelCoder := NewParallelCoder(clientConfig, cfg)
+	parallelCoder.SetSpent(spent)
+	results, err := parallelCoder.ExecuteTargets(context.Background(), targets)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(results) != len(targets) {
+		t.Fatalf("Expected %d results, got %d", len(targets), len(results))
+	}
+
+	cost := spent
+	var succeeded, skipped int
+	for _, result := range results {
+		cost += cfg.EstimateCost(result.PromptTokens, result.CompletionTokens)
+		switch {
+		case result.Success:
+			succeeded++
+		case result.FailureReason != nil && result.FailureReason.Phase == PhaseBudget:
+			skipped++
+		default:
+			t.Errorf("Unexpected failure of %s: %+v", result.Target.Name, result.FailureReason)
+		}
+	}
+
+	if cost > cfg.MaxCost {
+		t.Errorf("Expected cost within the budget of $%.2f, got $%.2f", cfg.MaxCost, cost)
+	}
+	if succeeded != wantSucceeded || skipped != wantSkipped {
+		t.Errorf("Expected %d generated and %d skipped targets, got %d and %d", wantSucceeded, wantSkipped, succeeded, skipped)
+	}
+}
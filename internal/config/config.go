@@ -2,6 +2,9 @@ package config
 
 import (
 	"fmt"
+	goparser "go/parser"
+	"go/token"
+	"net/url"
 	"os"
 	"path/filepath"
 	"regexp"
@@ -22,21 +25,188 @@ type Config struct {
 	LogLevel string `toml:"log_level"`
 	Plain    bool   `toml:"-"` // CLI flag, not from config file
 
+	// Stats prints request statistics at the end of a run (CLI flag)
+	Stats bool `toml:"-"`
+
+	// FailOnDiagnostics makes generation fail when the generated package has errors (CLI flag)
+	FailOnDiagnostics bool `toml:"-"`
+
+	// Path is the mantra.toml the configuration was loaded from
+	// (empty when read from the environment)
+	Path string `toml:"-"`
+
+	// Force regenerates targets even when their checksum is up-to-date (CLI flag)
+	Force bool `toml:"-"`
+
+	// AllowLargeBatch lifts max_targets_per_file and max_file_size_bytes (CLI flag)
+	AllowLargeBatch bool `toml:"-"`
+
+	// ReportPath is where a JSON summary of the run is written (CLI flag)
+	ReportPath string `toml:"-"`
+
+	// IncrementalWrite writes each generated file as soon as one of its targets
+	// completes, so an interrupted run can resume (CLI flag)
+	IncrementalWrite bool `toml:"-"`
+
+	// MaxCost stops starting new generations once the estimated cost of the run
+	// reaches this many dollars (CLI flag, requires [pricing])
+	MaxCost float64 `toml:"-"`
+
+	// OutputMode selects what is printed to stdout after generation (CLI flag)
+	OutputMode string `toml:"-"`
+
+	// Explain prints an explanation of each generated implementation (CLI flag)
+	Explain bool `toml:"-"`
+
+	// InstructionPrefix marks instruction comments instead of "// mantra:", e.g. "// ai:"
+	InstructionPrefix string `toml:"instruction_prefix"`
+
+	// Prompt customization
+	PromptTemplate string `toml:"prompt_template"` // Path to a custom prompt template
+	SystemPrompt   string `toml:"system_prompt"`   // Replaces the built-in implementation system prompt
+
+	// PlaceholderBody replaces panic("not implemented") in generated files when
+	// generation fails (e.g., `return errors.New("not implemented")`)
+	PlaceholderBody string `toml:"placeholder_body"`
+
+	// BodyPrologue and BodyEpilogue are placed before and after every generated
+	// body, e.g. a deferred recover
+	BodyPrologue string `toml:"body_prologue"`
+	BodyEpilogue string `toml:"body_epilogue"`
+
+	// PostProcessCommand is a shell command that receives each generated body on
+	// stdin and prints the body to use; a non-zero exit fails the target
+	PostProcessCommand string `toml:"post_process_command"`
+
+	// Seed is passed to the API for reproducible output, and makes all phases
+	// use temperature 0. Whether output is deterministic depends on the backend.
+	Seed *int `toml:"seed"`
+
+	// Stop lists sequences at which the model stops generating (e.g., ["```"])
+	Stop []string `toml:"stop"`
+
+	// MaxConcurrency limits how many targets are generated at the same time (default 16)
+	MaxConcurrency int `toml:"max_concurrency"`
+
+	// ContextConcurrency limits how many targets have their type information
+	// extracted at the same time before generation starts (default 8)
+	ContextConcurrency int `toml:"context_concurrency"`
+
+	// MaxFileSizeBytes limits the size of source files with targets without --allow-large-batch (default 512 KB)
+	MaxFileSizeBytes int64 `toml:"max_file_size_bytes"`
+
+	// MaxTargetsPerFile limits how many targets of one file are generated in a run without --allow-large-batch (default 50)
+	MaxTargetsPerFile int `toml:"max_targets_per_file"`
+
+	// StructuredOutput lets the AI declare required imports along with the code
+	StructuredOutput bool `toml:"structured_output"`
+
+	// AllowComments asks the AI to explain non-obvious steps with inline comments
+	AllowComments bool `toml:"allow_comments"`
+
+	// Package context
+	IncludePackageContext bool `toml:"include_package_context"` // Include the exported API of the whole package
+	MaxContextTokens      int  `toml:"max_context_tokens"`      // Approximate limit for the package API section
+
+	// Few-shot examples of the project's conventions
+	Examples        []Example `toml:"examples"`
+	MaxExamples     int       `toml:"max_examples"`     // Maximum number of examples sent per target (0 means all)
+	PatternExamples bool      `toml:"pattern_examples"` // Include built-in examples of common patterns such as HTTP handlers
+
+	// Feedback regenerates targets with the output of failing tests
+	Feedback *FeedbackConfig `toml:"feedback"`
+
+	// Pricing of the model, used to estimate the cost of a run
+	Pricing *PricingConfig `toml:"pricing"`
+
 	// OpenRouter configuration
 	OpenRouter *OpenRouterConfig `toml:"openrouter"`
 }
 
+// PricingConfig is the price of the model in dollars per million tokens
+type PricingConfig struct {
+	PromptPerMillion     float64 `toml:"prompt_per_million"`
+	CompletionPerMillion float64 `toml:"completion_per_million"`
+}
+
+// EstimateCost returns the estimated cost in dollars of the given token usage,
+// or 0 when no pricing is configured
+func (c *Config) EstimateCost(promptTokens, completionTokens int) float64 {
+	if c.Pricing == nil {
+		return 0
+	}
+	return (float64(promptTokens)*c.Pricing.PromptPerMillion + float64(completionTokens)*c.Pricing.CompletionPerMillion) / 1e6
+}
+
+// FeedbackConfig configures the test feedback loop
+type FeedbackConfig struct {
+	Enabled       bool     `toml:"enabled"`
+	TestCommand   []string `toml:"test_command"`   // Run in the destination directory (default ["go", "test", "."])
+	MaxIterations int      `toml:"max_iterations"` // Regeneration attempts after failing tests (default 2)
+}
+
+// DefaultFeedbackIterations is the number of regeneration attempts when max_iterations is unset
+const DefaultFeedbackIterations = 2
+
+// Command returns the configured test command, or the default
+func (f *FeedbackConfig) Command() []string {
+	if len(f.TestCommand) == 0 {
+		return []string{"go", "test", "."}
+	}
+	return f.TestCommand
+}
+
+// Iterations returns the configured number of regeneration attempts, or the default
+func (f *FeedbackConfig) Iterations() int {
+	if f.MaxIterations <= 0 {
+		return DefaultFeedbackIterations
+	}
+	return f.MaxIterations
+}
+
+// Example is an example implementation shown to the AI before the actual request
+type Example struct {
+	Signature   string `toml:"signature"`
+	Instruction string `toml:"instruction"`
+	Body        string `toml:"body"`
+}
+
 // OpenRouterConfig represents OpenRouter-specific configuration
 type OpenRouterConfig struct {
-	Providers []string `toml:"providers"`
+	Providers      []string `toml:"providers"`       // Only use these providers
+	Order          []string `toml:"order"`           // Providers to try first, in order
+	AllowFallbacks *bool    `toml:"allow_fallbacks"` // Whether other providers may be used as fallbacks
+	Transforms     []string `toml:"transforms"`      // Message transforms (e.g., ["middle-out"])
 }
 
-// Load loads configuration from mantra.toml
+// Environment variables for running without a config file
+const (
+	EnvConfigFile          = "MANTRA_CONFIG_FILE"    // Path of the config file, skipping the search
+	EnvNoConfigFile        = "MANTRA_NO_CONFIG_FILE" // When "1", configuration is read from the environment only
+	EnvModel               = "MANTRA_MODEL"
+	EnvURL                 = "MANTRA_URL"
+	EnvDest                = "MANTRA_DEST"
+	EnvAPIKey              = "MANTRA_API_KEY"
+	EnvLogLevel            = "MANTRA_LOG_LEVEL"
+	EnvOpenRouterProviders = "MANTRA_OPENROUTER_PROVIDERS" // Comma-separated
+)
+
+// Load loads configuration from mantra.toml.
+// MANTRA_CONFIG_FILE overrides the search for mantra.toml, and
+// MANTRA_NO_CONFIG_FILE=1 reads the configuration from the environment only.
 func Load(targetPath string) (*Config, error) {
+	if os.Getenv(EnvNoConfigFile) == "1" {
+		return FromEnv()
+	}
+
 	// Find config file starting from target directory
-	configPath, err := findConfigFile(targetPath)
-	if err != nil {
-		return nil, err
+	configPath := os.Getenv(EnvConfigFile)
+	if configPath == "" {
+		var err error
+		configPath, err = findConfigFile(targetPath)
+		if err != nil {
+			return nil, err
+		}
 	}
 
 	// Read and parse config file
@@ -56,17 +226,103 @@ func Load(targetPath string) (*Config, error) {
 		fmt.Fprintf(os.Stderr, "Warning: API key appears to be hardcoded in mantra.toml. Consider using environment variables: api_key = \"${OPENROUTER_API_KEY}\"\n")
 	}
 
-	// Validate required fields
-	if err := cfg.validate(); err != nil {
+	if err := cfg.finalize(filepath.Dir(configPath)); err != nil {
 		return nil, err
 	}
+	cfg.Path = configPath
 
-	// Normalize paths
-	cfg.Dest = normalizePath(cfg.Dest, filepath.Dir(configPath))
+	return &cfg, nil
+}
+
+// FromEnv reads the configuration from MANTRA_* environment variables,
+// for deployments without a mantra.toml. Relative paths are resolved
+// against the current directory.
+func FromEnv() (*Config, error) {
+	cfg := Config{
+		Model:    os.Getenv(EnvModel),
+		URL:      os.Getenv(EnvURL),
+		Dest:     os.Getenv(EnvDest),
+		APIKey:   os.Getenv(EnvAPIKey),
+		LogLevel: os.Getenv(EnvLogLevel),
+	}
+
+	if providers := os.Getenv(EnvOpenRouterProviders); providers != "" {
+		cfg.OpenRouter = &OpenRouterConfig{}
+		for _, provider := range strings.Split(providers, ",") {
+			if provider = strings.TrimSpace(provider); provider != "" {
+				cfg.OpenRouter.Providers = append(cfg.OpenRouter.Providers, provider)
+			}
+		}
+	}
+
+	workDir, err := os.Getwd()
+	if err != nil {
+		return nil, fmt.Errorf("failed to get working directory: %w", err)
+	}
+
+	if err := cfg.finalize(workDir); err != nil {
+		return nil, err
+	}
 
 	return &cfg, nil
 }
 
+// Overrides are settings given on the command line, which take precedence over
+// the configuration file or environment
+type Overrides struct {
+	Model  string
+	URL    string
+	APIKey string
+}
+
+// ApplyOverrides replaces the settings set in o. Empty fields keep the
+// configured value.
+func (c *Config) ApplyOverrides(o Overrides) error {
+	if o.Model != "" {
+		c.Model = o.Model
+	}
+	if o.URL != "" {
+		normalizedURL, err := normalizeURL(o.URL)
+		if err != nil {
+			return err
+		}
+		c.URL = normalizedURL
+	}
+	if o.APIKey != "" {
+		c.APIKey = o.APIKey
+	}
+	return nil
+}
+
+// finalize validates the configuration and normalizes URL and paths,
+// resolving relative paths against baseDir
+func (c *Config) finalize(baseDir string) error {
+	// Validate required fields
+	if err := c.validate(); err != nil {
+		return err
+	}
+
+	// Report likely misconfigurations without failing
+	for _, warning := range c.Warnings() {
+		fmt.Fprintf(os.Stderr, "Warning: %s\n", warning)
+	}
+
+	// Normalize URL so that endpoints can be joined to it
+	normalizedURL, err := normalizeURL(c.URL)
+	if err != nil {
+		return err
+	}
+	c.URL = normalizedURL
+
+	// Normalize paths
+	c.Dest = normalizePath(c.Dest, baseDir)
+	if c.PromptTemplate != "" {
+		c.PromptTemplate = normalizePath(c.PromptTemplate, baseDir)
+	}
+
+	return nil
+}
+
 // findConfigFile searches for mantra.toml starting from the given path
 func findConfigFile(startPath string) (string, error) {
 	// Convert to absolute path
@@ -134,6 +390,25 @@ func (c *Config) validate() error {
 	if c.Dest == "" {
 		errors = append(errors, "dest is required")
 	}
+	if c.InstructionPrefix != "" {
+		marker := strings.TrimSuffix(c.InstructionPrefix, ":")
+		if !strings.HasPrefix(c.InstructionPrefix, "//") || !strings.HasSuffix(c.InstructionPrefix, ":") || strings.TrimSpace(strings.TrimPrefix(marker, "//")) == "" {
+			errors = append(errors, fmt.Sprintf("instruction_prefix %q must have the form \"// name:\"", c.InstructionPrefix))
+		}
+	}
+	if c.Pricing != nil && (c.Pricing.PromptPerMillion < 0 || c.Pricing.CompletionPerMillion < 0) {
+		errors = append(errors, "pricing must not be negative")
+	}
+	if c.PlaceholderBody != "" {
+		if err := parseStatements(c.PlaceholderBody); err != nil {
+			errors = append(errors, fmt.Sprintf("placeholder_body is not valid Go: %v", err))
+		}
+	}
+	if c.BodyPrologue != "" || c.BodyEpilogue != "" {
+		if err := parseStatements(c.BodyPrologue + "\n" + c.BodyEpilogue); err != nil {
+			errors = append(errors, fmt.Sprintf("body_prologue and body_epilogue are not valid Go: %v", err))
+		}
+	}
 
 	// Check for unexpanded environment variables
 	if strings.Contains(c.APIKey, "${") {
@@ -158,6 +433,13 @@ func (c *Config) validate() error {
 	return nil
 }
 
+// parseStatements checks that code parses as the statements of a function body
+func parseStatements(code string) error {
+	wrapped := fmt.Sprintf("package p\nfunc _() {\n%s\n}", code)
+	_, err := goparser.ParseFile(token.NewFileSet(), "", wrapped, 0)
+	return err
+}
+
 // normalizePath converts relative paths to absolute paths based on config file location
 func normalizePath(path, configDir string) string {
 	if filepath.IsAbs(path) {
@@ -166,11 +448,108 @@ func normalizePath(path, configDir string) string {
 	return filepath.Join(configDir, path)
 }
 
+// Warnings returns likely misconfigurations that do not prevent running,
+// such as an API key that does not match the provider of the URL
+func (c *Config) Warnings() []string {
+	var warnings []string
+
+	apiKey := c.GetAPIKey()
+	isOpenRouterKey := strings.HasPrefix(apiKey, "sk-or-")
+	isOpenAIKey := strings.HasPrefix(apiKey, "sk-") && !isOpenRouterKey
+
+	if strings.Contains(c.URL, "openai.com") && isOpenRouterKey {
+		warnings = append(warnings, "api_key looks like an OpenRouter key (sk-or-...) but url points to OpenAI")
+	}
+	if strings.Contains(c.URL, "openrouter.ai") && isOpenAIKey {
+		warnings = append(warnings, "api_key looks like an OpenAI key (sk-...) but url points to OpenRouter")
+	}
+
+	return warnings
+}
+
+// ValidateStrict fails if there are any configuration warnings, for use in CI
+func (c *Config) ValidateStrict() error {
+	warnings := c.Warnings()
+	if len(warnings) > 0 {
+		return fmt.Errorf("configuration warnings: %s", strings.Join(warnings, "; "))
+	}
+	return nil
+}
+
+// normalizeURL validates the API URL and strips trailing slashes and a
+// mistakenly included /chat/completions endpoint
+func normalizeURL(rawURL string) (string, error) {
+	parsed, err := url.Parse(strings.TrimSpace(rawURL))
+	if err != nil {
+		return "", fmt.Errorf("invalid url %q: %w", rawURL, err)
+	}
+	if parsed.Scheme != "http" && parsed.Scheme != "https" {
+		return "", fmt.Errorf("invalid url %q: scheme must be http or https", rawURL)
+	}
+	if parsed.Host == "" {
+		return "", fmt.Errorf("invalid url %q: missing host", rawURL)
+	}
+
+	path := strings.TrimRight(parsed.Path, "/")
+	if trimmed, found := strings.CutSuffix(path, "/chat/completions"); found {
+		fmt.Fprintf(os.Stderr, "Warning: url should not include /chat/completions, using %q\n", trimmed)
+		path = trimmed
+	}
+	parsed.Path = path
+	parsed.RawPath = ""
+
+	return parsed.String(), nil
+}
+
 // GetPackageName returns the package name based on the destination directory
 func (c *Config) GetPackageName() string {
 	return filepath.Base(c.Dest)
 }
 
+// DefaultMaxConcurrency is the number of targets generated in parallel when max_concurrency is unset
+const DefaultMaxConcurrency = 16
+
+// GetMaxConcurrency returns the configured concurrency limit, or the default
+func (c *Config) GetMaxConcurrency() int {
+	if c.MaxConcurrency <= 0 {
+		return DefaultMaxConcurrency
+	}
+	return c.MaxConcurrency
+}
+
+// DefaultContextConcurrency is the number of targets whose context is extracted in parallel when context_concurrency is unset
+const DefaultContextConcurrency = 8
+
+// GetContextConcurrency returns the configured context extraction concurrency, or the default
+func (c *Config) GetContextConcurrency() int {
+	if c.ContextConcurrency <= 0 {
+		return DefaultContextConcurrency
+	}
+	return c.ContextConcurrency
+}
+
+// DefaultMaxFileSizeBytes is the source file size limit when max_file_size_bytes is unset
+const DefaultMaxFileSizeBytes = 512 * 1024
+
+// GetMaxFileSizeBytes returns the configured source file size limit, or the default
+func (c *Config) GetMaxFileSizeBytes() int64 {
+	if c.MaxFileSizeBytes <= 0 {
+		return DefaultMaxFileSizeBytes
+	}
+	return c.MaxFileSizeBytes
+}
+
+// DefaultMaxTargetsPerFile is the pending target limit per file when max_targets_per_file is unset
+const DefaultMaxTargetsPerFile = 50
+
+// GetMaxTargetsPerFile returns the configured pending target limit per file, or the default
+func (c *Config) GetMaxTargetsPerFile() int {
+	if c.MaxTargetsPerFile <= 0 {
+		return DefaultMaxTargetsPerFile
+	}
+	return c.MaxTargetsPerFile
+}
+
 // GetAPIKey returns the API key with environment variables expanded
 func (c *Config) GetAPIKey() string {
 	if c.APIKey == "" {
@@ -0,0 +1,266 @@
+package config
+
+import (
+	"os"
+	"path/filepath"
+	"testing"
+)
+
+func TestNormalizeURL(t *testing.T) {
+	tests := []struct {
+		name    string
+		input   string
+		want    string
+		wantErr bool
+	}{
+		{name: "plain", input: "http://localhost:11434/v1", want: "http://localhost:11434/v1"},
+		{name: "trailing slash", input: "https://openrouter.ai/api/v1/", want: "https://openrouter.ai/api/v1"},
+		{name: "full endpoint path", input: "https://api.openai.com/v1/chat/completions", want: "https://api.openai.com/v1"},
+		{name: "full endpoint path with slash", input: "https://api.openai.com/v1/chat/completions/", want: "https://api.openai.com/v1"},
+		{name: "host only", input: "http://localhost:8080/", want: "http://localhost:8080"},
+		{name: "missing scheme", input: "localhost:11434/v1", wantErr: true},
+		{name: "unsupported scheme", input: "ftp://example.com", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			got, err := normalizeURL(tt.input)
+			if tt.wantErr {
+				if err == nil {
+					t.Errorf("Expected error for %q, got %q", tt.input, got)
+				}
+				return
+			}
+			if err != nil {
+				t.Fatalf("Unexpected error: %v", err)
+			}
+			if got != tt.want {
+				t.Errorf("normalizeURL(%q) = %q, want %q", tt.input, got, tt.want)
+			}
+		})
+	}
+}
+
+func TestLoad_NormalizesURL(t *testing.T) {
+	dir := t.TempDir()
+	content := `model = "test-model"
+url = "http://localhost:11434/v1/chat/completions/"
+dest = "./generated"
+`
+	if err := os.WriteFile(filepath.Join(dir, "mantra.toml"), []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	cfg, err := Load(dir)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if cfg.URL != "http://localhost:11434/v1" {
+		t.Errorf("Expected normalized URL, got %q", cfg.URL)
+	}
+	if cfg.Dest != filepath.Join(dir, "generated") {
+		t.Errorf("Expected dest relative to config, got %q", cfg.Dest)
+	}
+}
+
+func TestWarnings(t *testing.T) {
+	tests := []struct {
+		name     string
+		url      string
+		apiKey   string
+		warnings int
+	}{
+		{name: "openai key with openai", url: "https://api.openai.com/v1", apiKey: "sk-abc", warnings: 0},
+		{name: "openrouter key with openrouter", url: "https://openrouter.ai/api/v1", apiKey: "sk-or-v1-abc", warnings: 0},
+		{name: "openrouter key with openai", url: "https://api.openai.com/v1", apiKey: "sk-or-v1-abc", warnings: 1},
+		{name: "openai key with openrouter", url: "https://openrouter.ai/api/v1", apiKey: "sk-abc", warnings: 1},
+		{name: "local without key", url: "http://localhost:11434/v1", apiKey: "", warnings: 0},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			cfg := &Config{URL: tt.url, APIKey: tt.apiKey}
+
+			warnings := cfg.Warnings()
+			if len(warnings) != tt.warnings {
+				t.Errorf("Expected %d warnings, got %v", tt.warnings, warnings)
+			}
+
+			err := cfg.ValidateStrict()
+			if (err != nil) != (tt.warnings > 0) {
+				t.Errorf("Unexpected ValidateStrict result: %v", err)
+			}
+		})
+	}
+}
+
+func TestValidate_PlaceholderBody(t *testing.T) {
+	tests := []struct {
+		name    string
+		body    string
+		wantErr bool
+	}{
+		{name: "unset", body: "", wantErr: false},
+		{name: "return statement", body: `return errors.New("not implemented")`, wantErr: false},
+		{name: "multiple statements", body: "// TODO\nreturn nil", wantErr: false},
+		{name: "invalid", body: "return (", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			cfg := &Config{Model: "m", URL: "http://localhost", Dest: "out", PlaceholderBody: tt.body}
+			if err := cfg.validate(); (err != nil) != tt.wantErr {
+				t.Errorf("validate() error = %v, wantErr %v", err, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestValidate_InstructionPrefix(t *testing.T) {
+	tests := []struct {
+		name    string
+		prefix  string
+		wantErr bool
+	}{
+		{name: "unset", prefix: "", wantErr: false},
+		{name: "custom", prefix: "// ai:", wantErr: false},
+		{name: "no space", prefix: "//gen:", wantErr: false},
+		{name: "missing colon", prefix: "// ai", wantErr: true},
+		{name: "not a comment", prefix: "ai:", wantErr: true},
+		{name: "empty marker", prefix: "// :", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			cfg := &Config{Model: "m", URL: "http://localhost", Dest: "out", InstructionPrefix: tt.prefix}
+			if err := cfg.validate(); (err != nil) != tt.wantErr {
+				t.Errorf("validate() error = %v, wantErr %v", err, tt.wantErr)
+			}
+		})
+	}
+}
+
+func TestApplyOverrides(t *testing.T) {
+	tempDir := t.TempDir()
+	content := "model = \"file-model\"\nurl = \"http://localhost:11434/v1\"\ndest = \"generated\"\napi_key = \"file-key\"\n"
+	if err := os.WriteFile(filepath.Join(tempDir, "mantra.toml"), []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+	cfg, err := Load(tempDir)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	// Empty overrides keep the file values
+	if err := cfg.ApplyOverrides(Overrides{}); err != nil {
+		t.Fatal(err)
+	}
+	if cfg.Model != "file-model" || cfg.GetAPIKey() != "file-key" {
+		t.Errorf("Expected file values to be kept, got model %q, api key %q", cfg.Model, cfg.GetAPIKey())
+	}
+
+	err = cfg.ApplyOverrides(Overrides{Model: "cli-model", URL: "https://openrouter.ai/api/v1/", APIKey: "cli-key"})
+	if err != nil {
+		t.Fatal(err)
+	}
+	if cfg.Model != "cli-model" || cfg.URL != "https://openrouter.ai/api/v1" || cfg.GetAPIKey() != "cli-key" {
+		t.Errorf("Expected command line values to take precedence, got model %q, url %q, api key %q", cfg.Model, cfg.URL, cfg.GetAPIKey())
+	}
+
+	if err := cfg.ApplyOverrides(Overrides{URL: "localhost:11434"}); err == nil {
+		t.Error("Expected an invalid URL override to be rejected")
+	}
+}
+
+func TestEstimateCost(t *testing.T) {
+	cfg := &Config{}
+	if cost := cfg.EstimateCost(1000, 1000); cost != 0 {
+		t.Errorf("Expected no cost without pricing, got %v", cost)
+	}
+
+	cfg.Pricing = &PricingConfig{PromptPerMillion: 3, CompletionPerMillion: 15}
+	if cost := cfg.EstimateCost(2_000_000, 100_000); cost != 7.5 {
+		t.Errorf("EstimateCost() = %v, want 7.5", cost)
+	}
+}
+
+func TestFromEnv(t *testing.T) {
+	t.Setenv(EnvModel, "gpt-4")
+	t.Setenv(EnvURL, "https://openrouter.ai/api/v1/")
+	t.Setenv(EnvDest, "/tmp/generated")
+	t.Setenv(EnvAPIKey, "sk-or-v1-abc")
+	t.Setenv(EnvLogLevel, "debug")
+	t.Setenv(EnvOpenRouterProviders, "Cerebras, Groq")
+
+	cfg, err := FromEnv()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	if cfg.Model != "gpt-4" {
+		t.Errorf("Expected model from env, got %q", cfg.Model)
+	}
+	if cfg.URL != "https://openrouter.ai/api/v1" {
+		t.Errorf("Expected normalized URL from env, got %q", cfg.URL)
+	}
+	if cfg.Dest != "/tmp/generated" {
+		t.Errorf("Expected dest from env, got %q", cfg.Dest)
+	}
+	if cfg.APIKey != "sk-or-v1-abc" {
+		t.Errorf("Expected API key from env, got %q", cfg.APIKey)
+	}
+	if cfg.LogLevel != "debug" {
+		t.Errorf("Expected log level from env, got %q", cfg.LogLevel)
+	}
+	if cfg.OpenRouter == nil || len(cfg.OpenRouter.Providers) != 2 || cfg.OpenRouter.Providers[1] != "Groq" {
+		t.Errorf("Expected providers from env, got %+v", cfg.OpenRouter)
+	}
+}
+
+func TestFromEnv_MissingRequired(t *testing.T) {
+	t.Setenv(EnvModel, "")
+	t.Setenv(EnvURL, "http://localhost:11434/v1")
+	t.Setenv(EnvDest, "./generated")
+
+	if _, err := FromEnv(); err == nil {
+		t.Error("Expected error when MANTRA_MODEL is not set")
+	}
+}
+
+func TestLoad_EnvOverrides(t *testing.T) {
+	dir := t.TempDir()
+	configPath := filepath.Join(dir, "custom.toml")
+	content := `model = "file-model"
+url = "http://localhost:11434/v1"
+dest = "./generated"
+`
+	if err := os.WriteFile(configPath, []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	t.Run("config file", func(t *testing.T) {
+		t.Setenv(EnvConfigFile, configPath)
+		cfg, err := Load(t.TempDir())
+		if err != nil {
+			t.Fatal(err)
+		}
+		if cfg.Model != "file-model" {
+			t.Errorf("Expected model from MANTRA_CONFIG_FILE, got %q", cfg.Model)
+		}
+	})
+
+	t.Run("no config file", func(t *testing.T) {
+		t.Setenv(EnvConfigFile, configPath)
+		t.Setenv(EnvNoConfigFile, "1")
+		t.Setenv(EnvModel, "env-model")
+		t.Setenv(EnvURL, "http://localhost:11434/v1")
+		t.Setenv(EnvDest, "./generated")
+		cfg, err := Load(dir)
+		if err != nil {
+			t.Fatal(err)
+		}
+		if cfg.Model != "env-model" {
+			t.Errorf("Expected model from environment, got %q", cfg.Model)
+		}
+	})
+}
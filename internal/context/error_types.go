@@ -0,0 +1,38 @@
+package context
+
+import (
+	"go/types"
+)
+
+// GetErrorTypes returns definitions of all types in the package that implement
+// the error interface, keyed by type name
+func (l *PackageLoader) GetErrorTypes() (map[string]string, error) {
+	allTypes, err := l.GetAllTypes()
+	if err != nil {
+		return nil, err
+	}
+
+	errorInterface := types.Universe.Lookup("error").Type().Underlying().(*types.Interface)
+
+	errorTypes := make(map[string]string)
+	scope := l.pkg.Types.Scope()
+	for name, typeInfo := range allTypes {
+		obj, ok := scope.Lookup(name).(*types.TypeName)
+		if !ok {
+			continue
+		}
+
+		// Interfaces embedding error are not concrete error types
+		if types.IsInterface(obj.Type()) {
+			continue
+		}
+
+		// Error() may be declared on either the value or the pointer receiver
+		if types.Implements(obj.Type(), errorInterface) ||
+			types.Implements(types.NewPointer(obj.Type()), errorInterface) {
+			errorTypes[name] = l.buildCompleteTypeDefinition(typeInfo)
+		}
+	}
+
+	return errorTypes, nil
+}
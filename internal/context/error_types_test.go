@@ -0,0 +1,97 @@
+package context
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestExtractFunctionContext_ErrorTypes(t *testing.T) {
+	tmpDir := t.TempDir()
+	testFile := filepath.Join(tmpDir, "store.go")
+
+	testContent := `package store
+
+type NotFoundError struct {
+	Key string
+}
+
+func (e *NotFoundError) Error() string {
+	return "not found: " + e.Key
+}
+
+type ValidationError string
+
+func (e ValidationError) Error() string {
+	return string(e)
+}
+
+type Item struct {
+	Key string
+}
+
+// mantra: Look up an item by key
+func Find(key string) (*Item, error) {
+	panic("not implemented")
+}
+
+// mantra: Count items
+func Count() int {
+	panic("not implemented")
+}
+`
+
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatalf("Failed to write test file: %v", err)
+	}
+
+	goModContent := `module store
+
+go 1.21
+`
+	if err := os.WriteFile(filepath.Join(tmpDir, "go.mod"), []byte(goModContent), 0644); err != nil {
+		t.Fatalf("Failed to write go.mod file: %v", err)
+	}
+
+	fileInfo, err := parser.ParseFileInfo(testFile)
+	if err != nil {
+		t.Fatalf("Failed to parse file: %v", err)
+	}
+
+	targets := make(map[string]*parser.Target)
+	for _, target := range fileInfo.Targets {
+		targets[target.Name] = target
+	}
+
+	ctx, err := ExtractFunctionContext(testFile, targets["Find"])
+	if err != nil {
+		t.Fatalf("Failed to extract context: %v", err)
+	}
+
+	for _, name := range []string{"NotFoundError", "ValidationError"} {
+		def, ok := ctx.ErrorTypes[name]
+		if !ok {
+			t.Errorf("Expected error type %s to be included, got %v", name, ctx.ErrorTypes)
+			continue
+		}
+		if !strings.Contains(def, "type "+name) {
+			t.Errorf("Unexpected definition for %s: %s", name, def)
+		}
+	}
+
+	if _, ok := ctx.ErrorTypes["Item"]; ok {
+		t.Error("Item does not implement error and should not be included")
+	}
+
+	// Targets that do not return error get no error types
+	ctx, err = ExtractFunctionContext(testFile, targets["Count"])
+	if err != nil {
+		t.Fatalf("Failed to extract context: %v", err)
+	}
+	if len(ctx.ErrorTypes) != 0 {
+		t.Errorf("Expected no error types for Count, got %v", ctx.ErrorTypes)
+	}
+}
@@ -2,7 +2,10 @@ package context
 
 import (
 	"fmt"
+	"go/ast"
 	"path/filepath"
+	"strings"
+	"unicode"
 
 	"github.com/rail44/mantra/internal/analysis"
 	"github.com/rail44/mantra/internal/parser"
@@ -10,10 +13,16 @@ import (
 
 // RelevantContext holds context information relevant to a function
 type RelevantContext struct {
-	Imports     []*ImportInfo                    // All imports with structured information
-	Types       map[string]string                // Type definitions (name -> definition)
-	Methods     map[string][]analysis.MethodInfo // Type methods (typeName -> methods)
-	PackageName string                           // Package name
+	Imports         []*ImportInfo                    // All imports with structured information
+	Types           map[string]string                // Type definitions (name -> definition)
+	TypeRoles       map[string]string                // How the signature uses a type (name -> e.g. "return type")
+	Methods         map[string][]analysis.MethodInfo // Type methods (typeName -> methods)
+	ErrorTypes      map[string]string                // Error types defined in the package (name -> definition)
+	Tests           []parser.TestFunctionInfo        // Existing tests calling the target
+	PackageAPI      []string                         // Exported declarations of other files (include_package_context only)
+	Constants       []ConstOrVar                     // Package-level variables used by the target (all constants and variables with include_package_context)
+	TestedFunctions []string                         // Signatures of functions named by a mantra:test instruction
+	PackageName     string                           // Package name
 }
 
 // ExtractFunctionContext extracts context using go/packages for accurate type resolution
@@ -35,10 +44,57 @@ func ExtractFunctionContext(filePath string, target *parser.Target) (*RelevantCo
 	if err != nil {
 		return nil, fmt.Errorf("failed to extract context: %w", err)
 	}
+	ctx.TypeRoles = typeRoles(target)
+
+	// Interfaces of imported packages tell the AI which methods it can call
+	for name, definition := range loader.GetImportedInterfaces(qualifiedTypes(target)) {
+		ctx.Types[name] = definition
+	}
+
+	// Custom error types help the AI return typed errors instead of ad-hoc ones
+	if returnsError(target) {
+		errorTypes, err := loader.GetErrorTypes()
+		if err != nil {
+			return nil, fmt.Errorf("failed to extract error types: %w", err)
+		}
+		for name := range ctx.Types {
+			delete(errorTypes, name)
+		}
+		ctx.ErrorTypes = errorTypes
+	}
+
+	// Existing tests describe the expected behaviour; a broken test file is not fatal
+	if tests, err := parser.FindTestsForTarget(target); err == nil {
+		ctx.Tests = tests
+	}
+
+	// Package-level variables are otherwise unknown to the AI; a broken file is not fatal
+	if vars, err := ExtractConstantsAndVariables(packagePath); err == nil {
+		ctx.Constants = usedPackageVariables(target, vars)
+	}
+
+	// Test targets need the signatures of the functions they exercise
+	if target.Test {
+		signatures, err := ExtractFunctionSignatures(packagePath, identifiers(target.Instruction))
+		if err != nil {
+			return nil, fmt.Errorf("failed to extract functions under test: %w", err)
+		}
+		ctx.TestedFunctions = signatures
+	}
 
 	return ctx, nil
 }
 
+// returnsError reports whether the target has an error return value
+func returnsError(target *parser.Target) bool {
+	for _, ret := range target.Returns {
+		if ret.Type == "error" {
+			return true
+		}
+	}
+	return false
+}
+
 // extractDirectlyUsedTypes extracts types directly used in function signature
 func extractDirectlyUsedTypes(target *parser.Target) map[string]bool {
 	types := make(map[string]bool)
@@ -69,3 +125,119 @@ func extractDirectlyUsedTypes(target *parser.Target) map[string]bool {
 
 	return types
 }
+
+// qualifiedTypes returns the package-qualified types of the target signature,
+// without pointers, slices and type arguments, e.g. "io.Reader" for "[]*io.Reader"
+func qualifiedTypes(target *parser.Target) []string {
+	var typeStrs []string
+	if target.Receiver != nil {
+		typeStrs = append(typeStrs, target.Receiver.Type)
+	}
+	for _, param := range target.Params {
+		typeStrs = append(typeStrs, param.Type)
+	}
+	for _, ret := range target.Returns {
+		typeStrs = append(typeStrs, ret.Type)
+	}
+
+	var names []string
+	seen := make(map[string]bool)
+	for _, typeStr := range typeStrs {
+		for {
+			trimmed := strings.TrimPrefix(strings.TrimLeft(typeStr, "*[]."), "chan ")
+			if strings.HasPrefix(trimmed, "map[") {
+				if idx := strings.Index(trimmed, "]"); idx > 0 {
+					trimmed = trimmed[idx+1:]
+				}
+			}
+			if trimmed == typeStr {
+				break
+			}
+			typeStr = trimmed
+		}
+		if idx := strings.Index(typeStr, "["); idx > 0 {
+			typeStr = typeStr[:idx]
+		}
+		if strings.Count(typeStr, ".") == 1 && !seen[typeStr] {
+			seen[typeStr] = true
+			names = append(names, typeStr)
+		}
+	}
+	return names
+}
+
+// typeRoles labels the types of the signature as receiver, parameter or return types.
+// A type used in several roles gets all of them, e.g. "parameter type, return type".
+func typeRoles(target *parser.Target) map[string]string {
+	roles := make(map[string][]string)
+	add := func(typeStr, role string) {
+		typeName := analysis.CleanTypeName(typeStr)
+		if typeName == "" || analysis.IsBuiltinType(typeName) {
+			return
+		}
+		for _, existing := range roles[typeName] {
+			if existing == role {
+				return
+			}
+		}
+		roles[typeName] = append(roles[typeName], role)
+	}
+
+	if target.Receiver != nil {
+		add(target.Receiver.Type, "receiver type")
+	}
+	for _, param := range target.Params {
+		add(param.Type, "parameter type")
+	}
+	for _, ret := range target.Returns {
+		add(ret.Type, "return type")
+	}
+
+	labels := make(map[string]string, len(roles))
+	for typeName, typeRoles := range roles {
+		labels[typeName] = strings.Join(typeRoles, ", ")
+	}
+	return labels
+}
+
+// usedPackageVariables returns the package-level variables referenced in the
+// body of the target or named in its instruction
+func usedPackageVariables(target *parser.Target, vars []ConstOrVar) []ConstOrVar {
+	used := make(map[string]bool)
+	for _, word := range identifiers(target.Instruction) {
+		used[word] = true
+	}
+
+	if target.FuncDecl != nil && target.FuncDecl.Body != nil {
+		var visit func(ast.Node) bool
+		visit = func(n ast.Node) bool {
+			switch n := n.(type) {
+			case *ast.SelectorExpr:
+				// Field and method names never refer to package-level variables
+				ast.Inspect(n.X, visit)
+				return false
+			case *ast.Ident:
+				used[n.Name] = true
+			}
+			return true
+		}
+		ast.Inspect(target.FuncDecl.Body, visit)
+	}
+
+	var result []ConstOrVar
+	for _, v := range vars {
+		// A closure target is itself a package-level variable
+		if v.Kind != KindVar || !used[v.Name] || (target.Closure && v.Name == target.Name) {
+			continue
+		}
+		result = append(result, v)
+	}
+	return result
+}
+
+// identifiers returns all Go identifier-like words in text
+func identifiers(text string) []string {
+	return strings.FieldsFunc(text, func(r rune) bool {
+		return !unicode.IsLetter(r) && !unicode.IsDigit(r) && r != '_'
+	})
+}
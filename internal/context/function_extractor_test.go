@@ -0,0 +1,148 @@
+package context
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestTypeRoles(t *testing.T) {
+	target := &parser.Target{
+		Name:     "NewWidget",
+		Receiver: &parser.Receiver{Name: "f", Type: "*Factory"},
+		Params: []parser.Param{
+			{Name: "spec", Type: "Spec"},
+			{Name: "parent", Type: "*Widget"},
+			{Name: "name", Type: "string"},
+		},
+		Returns: []parser.Return{{Type: "*Widget"}, {Type: "error"}},
+	}
+
+	expected := map[string]string{
+		"Factory": "receiver type",
+		"Spec":    "parameter type",
+		"Widget":  "parameter type, return type",
+	}
+	if roles := typeRoles(target); !reflect.DeepEqual(roles, expected) {
+		t.Errorf("Expected %v, got %v", expected, roles)
+	}
+}
+
+func TestExtractDirectlyUsedTypes_GenericReceiver(t *testing.T) {
+	target := &parser.Target{
+		Name:     "Push",
+		Receiver: &parser.Receiver{Name: "s", Type: "*Stack[T]"},
+		Params:   []parser.Param{{Name: "items", Type: "[]Pair[K, V]"}},
+	}
+
+	expected := map[string]bool{"Stack": true, "Pair": true}
+	if types := extractDirectlyUsedTypes(target); !reflect.DeepEqual(types, expected) {
+		t.Errorf("Expected %v, got %v", expected, types)
+	}
+	if roles := typeRoles(target); roles["Stack"] != "receiver type" {
+		t.Errorf("Expected Stack to be labeled as receiver type, got %v", roles)
+	}
+}
+
+func TestUsedPackageVariables(t *testing.T) {
+	tmpDir := t.TempDir()
+	testFile := filepath.Join(tmpDir, "store.go")
+	testContent := `package store
+
+type Database struct{}
+
+func (d *Database) Find(id int) string { return "" }
+
+var db *Database
+
+var cache map[int]string
+
+var unused = 1
+
+const limit = 10
+
+type Store struct {
+	unused int
+}
+
+// mantra: look up id, caching results in cache
+func (s *Store) Find(id int) string {
+	if id > limit {
+		return ""
+	}
+	s.unused++
+	return db.Find(id)
+}
+`
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	targets, err := parser.ParseFile(testFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	vars, err := ExtractConstantsAndVariables(tmpDir)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	used := usedPackageVariables(targets[0], vars)
+
+	expected := []ConstOrVar{
+		{Kind: KindVar, Name: "db", Type: "*Database"},
+		{Kind: KindVar, Name: "cache", Type: "map[int]string"},
+	}
+	if !reflect.DeepEqual(used, expected) {
+		t.Errorf("Unexpected result:\n got: %+v\nwant: %+v", used, expected)
+	}
+}
+
+func TestExtractFunctionContext_ImportedInterfaces(t *testing.T) {
+	tmpDir := t.TempDir()
+	testFile := filepath.Join(tmpDir, "copy.go")
+	testContent := `package copy
+
+import (
+	"bytes"
+	"io"
+	stdio "io"
+)
+
+// mantra: copy src to dst through buf
+func Copy(dst io.Writer, src stdio.Reader, buf *bytes.Buffer) (int64, error) {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(tmpDir, "go.mod"), []byte("module copy\n\ngo 1.21\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	targets, err := parser.ParseFile(testFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	ctx, err := ExtractFunctionContext(testFile, targets[0])
+	if err != nil {
+		t.Fatalf("Failed to extract context: %v", err)
+	}
+
+	expected := map[string]string{
+		"io.Writer":    "type io.Writer interface {\n    Write(p []byte) (n int, err error)\n}",
+		"stdio.Reader": "type stdio.Reader interface {\n    Read(p []byte) (n int, err error)\n}",
+	}
+	for name, definition := range expected {
+		if ctx.Types[name] != definition {
+			t.Errorf("Expected %s to be\n%s\ngot\n%s", name, definition, ctx.Types[name])
+		}
+	}
+	if _, ok := ctx.Types["bytes.Buffer"]; ok {
+		t.Error("bytes.Buffer is not an interface and should not be included")
+	}
+}
@@ -0,0 +1,75 @@
+package context
+
+import (
+	"fmt"
+	"go/types"
+	"strings"
+)
+
+// GetImportedInterfaces returns definitions of the interfaces among the given
+// package-qualified type names (e.g. "io.Reader"), keyed by qualified name.
+// Packages are resolved through the imports of the target file, so aliased
+// imports are found under their alias. Types that are not interfaces are left out.
+func (l *PackageLoader) GetImportedInterfaces(qualifiedNames []string) map[string]string {
+	interfaces := make(map[string]string)
+	if l.pkg == nil || len(qualifiedNames) == 0 {
+		return interfaces
+	}
+
+	// Package name or alias used in the target file -> imported package
+	imported := make(map[string]*types.Package)
+	for _, imp := range l.targetImports {
+		pkg, ok := l.pkg.Imports[imp.Path]
+		if !ok || pkg.Types == nil {
+			continue
+		}
+		name := imp.Alias
+		if name == "" {
+			name = pkg.Types.Name()
+		}
+		imported[name] = pkg.Types
+	}
+
+	for _, qualifiedName := range qualifiedNames {
+		pkgName, typeName, ok := strings.Cut(qualifiedName, ".")
+		if !ok {
+			continue
+		}
+		pkg, ok := imported[pkgName]
+		if !ok {
+			continue
+		}
+		obj, ok := pkg.Scope().Lookup(typeName).(*types.TypeName)
+		if !ok || !obj.Exported() {
+			continue
+		}
+		iface, ok := obj.Type().Underlying().(*types.Interface)
+		if !ok {
+			continue
+		}
+		interfaces[qualifiedName] = formatInterface(qualifiedName, pkg, pkgName, iface)
+	}
+
+	return interfaces
+}
+
+// formatInterface renders the method set of an interface of pkg, qualifying
+// types of pkg with the name it is imported as in the target file
+func formatInterface(qualifiedName string, pkg *types.Package, pkgName string, iface *types.Interface) string {
+	qualifier := func(other *types.Package) string {
+		if other == pkg {
+			return pkgName
+		}
+		return other.Name()
+	}
+
+	var sb strings.Builder
+	sb.WriteString(fmt.Sprintf("type %s interface {\n", qualifiedName))
+	for i := 0; i < iface.NumMethods(); i++ {
+		method := iface.Method(i)
+		signature := types.TypeString(method.Type(), qualifier)
+		sb.WriteString(fmt.Sprintf("    %s%s\n", method.Name(), strings.TrimPrefix(signature, "func")))
+	}
+	sb.WriteString("}")
+	return sb.String()
+}
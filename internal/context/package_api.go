@@ -0,0 +1,234 @@
+package context
+
+import (
+	"bytes"
+	"fmt"
+	"go/ast"
+	"go/format"
+	"go/parser"
+	"go/token"
+	"path/filepath"
+	"sort"
+	"strings"
+)
+
+// ExtractPackageAPI returns the exported declarations of all other files in
+// the package directory. Functions are reduced to their signatures.
+func ExtractPackageAPI(dir string, excludeFile string) ([]string, error) {
+	files, err := filepath.Glob(filepath.Join(dir, "*.go"))
+	if err != nil {
+		return nil, fmt.Errorf("failed to glob files: %w", err)
+	}
+	sort.Strings(files)
+
+	excludeFile = filepath.Clean(excludeFile)
+
+	var declarations []string
+	for _, file := range files {
+		if strings.HasSuffix(file, "_test.go") || filepath.Clean(file) == excludeFile {
+			continue
+		}
+
+		fset := token.NewFileSet()
+		node, err := parser.ParseFile(fset, file, nil, 0)
+		if err != nil {
+			return nil, fmt.Errorf("failed to parse %s: %w", file, err)
+		}
+
+		for _, decl := range node.Decls {
+			switch d := decl.(type) {
+			case *ast.FuncDecl:
+				if !d.Name.IsExported() {
+					continue
+				}
+				// Only the signature is of interest
+				signature := *d
+				signature.Body = nil
+				signature.Doc = nil
+				if text, err := formatNode(fset, &signature); err == nil {
+					declarations = append(declarations, text)
+				}
+			case *ast.GenDecl:
+				if d.Tok != token.TYPE {
+					continue
+				}
+				for _, spec := range d.Specs {
+					typeSpec := spec.(*ast.TypeSpec)
+					if !typeSpec.Name.IsExported() {
+						continue
+					}
+					typeDecl := &ast.GenDecl{Tok: token.TYPE, Specs: []ast.Spec{typeSpec}}
+					if text, err := formatNode(fset, typeDecl); err == nil {
+						declarations = append(declarations, text)
+					}
+				}
+			}
+		}
+	}
+
+	return declarations, nil
+}
+
+// ConstOrVarKind distinguishes constants from variables
+type ConstOrVarKind string
+
+const (
+	KindConst ConstOrVarKind = "const"
+	KindVar   ConstOrVarKind = "var"
+)
+
+// maxValueSnippet is the maximum length of the value shown for a constant or variable
+const maxValueSnippet = 80
+
+// ConstOrVar is a package-level constant or variable
+type ConstOrVar struct {
+	Kind  ConstOrVarKind
+	Name  string
+	Type  string // Declared type (empty if inferred)
+	Value string // Beginning of the value expression (empty if none)
+}
+
+// String renders the declaration as a single line of Go
+func (c ConstOrVar) String() string {
+	s := string(c.Kind) + " " + c.Name
+	if c.Type != "" {
+		s += " " + c.Type
+	}
+	if c.Value != "" {
+		s += " = " + c.Value
+	}
+	return s
+}
+
+// ExtractConstantsAndVariables returns the package-level constants and
+// variables of all files in the package directory. Test files are ignored.
+func ExtractConstantsAndVariables(dir string) ([]ConstOrVar, error) {
+	files, err := filepath.Glob(filepath.Join(dir, "*.go"))
+	if err != nil {
+		return nil, fmt.Errorf("failed to glob files: %w", err)
+	}
+	sort.Strings(files)
+
+	var result []ConstOrVar
+	for _, file := range files {
+		if strings.HasSuffix(file, "_test.go") {
+			continue
+		}
+
+		fset := token.NewFileSet()
+		node, err := parser.ParseFile(fset, file, nil, 0)
+		if err != nil {
+			return nil, fmt.Errorf("failed to parse %s: %w", file, err)
+		}
+
+		result = append(result, constantsAndVariables(fset, node)...)
+	}
+
+	return result, nil
+}
+
+// constantsAndVariables returns the package-level constants and variables of a file
+func constantsAndVariables(fset *token.FileSet, node *ast.File) []ConstOrVar {
+	var result []ConstOrVar
+	for _, decl := range node.Decls {
+		genDecl, ok := decl.(*ast.GenDecl)
+		if !ok || (genDecl.Tok != token.CONST && genDecl.Tok != token.VAR) {
+			continue
+		}
+
+		kind := KindVar
+		if genDecl.Tok == token.CONST {
+			kind = KindConst
+		}
+
+		for _, spec := range genDecl.Specs {
+			valueSpec := spec.(*ast.ValueSpec)
+
+			var typeName string
+			if valueSpec.Type != nil {
+				typeName, _ = formatNode(fset, valueSpec.Type)
+			}
+
+			for i, name := range valueSpec.Names {
+				if name.Name == "_" {
+					continue
+				}
+				item := ConstOrVar{Kind: kind, Name: name.Name, Type: typeName}
+				if i < len(valueSpec.Values) {
+					item.Value = valueSnippet(fset, valueSpec.Values[i])
+				}
+				result = append(result, item)
+			}
+		}
+	}
+	return result
+}
+
+// valueSnippet renders the first line of an expression, truncated to maxValueSnippet
+func valueSnippet(fset *token.FileSet, expr ast.Expr) string {
+	text, err := formatNode(fset, expr)
+	if err != nil {
+		return ""
+	}
+	text, _, multiline := strings.Cut(text, "\n")
+	if len(text) > maxValueSnippet {
+		text = text[:maxValueSnippet]
+		multiline = true
+	}
+	if multiline {
+		text += " ..."
+	}
+	return text
+}
+
+// formatNode renders an AST node as Go source
+func formatNode(fset *token.FileSet, node ast.Node) (string, error) {
+	var buf bytes.Buffer
+	if err := format.Node(&buf, fset, node); err != nil {
+		return "", err
+	}
+	return buf.String(), nil
+}
+
+// ExtractFunctionSignatures returns the signatures of functions and methods in
+// the package directory whose name is one of names. Test files are ignored.
+func ExtractFunctionSignatures(dir string, names []string) ([]string, error) {
+	wanted := make(map[string]bool)
+	for _, name := range names {
+		wanted[name] = true
+	}
+
+	files, err := filepath.Glob(filepath.Join(dir, "*.go"))
+	if err != nil {
+		return nil, fmt.Errorf("failed to glob files: %w", err)
+	}
+	sort.Strings(files)
+
+	var signatures []string
+	for _, file := range files {
+		if strings.HasSuffix(file, "_test.go") {
+			continue
+		}
+
+		fset := token.NewFileSet()
+		node, err := parser.ParseFile(fset, file, nil, 0)
+		if err != nil {
+			return nil, fmt.Errorf("failed to parse %s: %w", file, err)
+		}
+
+		for _, decl := range node.Decls {
+			funcDecl, ok := decl.(*ast.FuncDecl)
+			if !ok || !wanted[funcDecl.Name.Name] {
+				continue
+			}
+			signature := *funcDecl
+			signature.Body = nil
+			signature.Doc = nil
+			if text, err := formatNode(fset, &signature); err == nil {
+				signatures = append(signatures, text)
+			}
+		}
+	}
+
+	return signatures, nil
+}
@@ -0,0 +1,66 @@
+package context
+
+import (
+	"os"
+	"path/filepath"
+	"reflect"
+	"testing"
+)
+
+func TestExtractConstantsAndVariables(t *testing.T) {
+	tmpDir := t.TempDir()
+	content := `package store
+
+import "time"
+
+const (
+	DefaultTTL time.Duration = 5 * time.Minute
+	maxItems                 = 100
+)
+
+const (
+	StateIdle = iota
+	StateBusy
+)
+
+var (
+	ErrClosed = errors.New("store closed")
+	cache     map[string]string
+	_         = maxItems
+)
+
+var names = []string{
+	"a",
+	"b",
+}
+`
+	if err := os.WriteFile(filepath.Join(tmpDir, "store.go"), []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+	// Test files are ignored
+	if err := os.WriteFile(filepath.Join(tmpDir, "store_test.go"), []byte("package store\n\nconst testOnly = 1\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	constants, err := ExtractConstantsAndVariables(tmpDir)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	expected := []ConstOrVar{
+		{Kind: KindConst, Name: "DefaultTTL", Type: "time.Duration", Value: "5 * time.Minute"},
+		{Kind: KindConst, Name: "maxItems", Value: "100"},
+		{Kind: KindConst, Name: "StateIdle", Value: "iota"},
+		{Kind: KindConst, Name: "StateBusy"},
+		{Kind: KindVar, Name: "ErrClosed", Value: `errors.New("store closed")`},
+		{Kind: KindVar, Name: "cache", Type: "map[string]string"},
+		{Kind: KindVar, Name: "names", Value: "[]string{ ..."},
+	}
+	if !reflect.DeepEqual(constants, expected) {
+		t.Errorf("Unexpected result:\n got: %+v\nwant: %+v", constants, expected)
+	}
+
+	if s := constants[0].String(); s != "const DefaultTTL time.Duration = 5 * time.Minute" {
+		t.Errorf("Unexpected rendering: %q", s)
+	}
+}
@@ -1,15 +1,18 @@
 package detector
 
 import (
+	"errors"
 	"fmt"
 	"go/ast"
 	goparser "go/parser"
 	"go/token"
+	"log/slog"
 	"os"
 	"path/filepath"
 	"strings"
 
 	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/imports"
 	"github.com/rail44/mantra/internal/parser"
 )
 
@@ -35,13 +38,42 @@ type FileDetectionResult struct {
 type TargetStatus struct {
 	Target           *parser.Target
 	Status           Status
-	CurrentChecksum  string // Checksum of current declaration
-	ExistingChecksum string // Checksum found in generated file (if any)
-	ExistingImpl     string // Existing implementation (if checksum matches)
+	CurrentChecksum  string   // Checksum of current declaration
+	ExistingChecksum string   // Checksum found in generated file (if any)
+	ExistingImpl     string   // Existing implementation (if checksum matches)
+	ExistingImports  []string // Imports of the generated file used by ExistingImpl
 }
 
-// DetectPackageTargets analyzes all Go files in a package directory and returns detection results for all files
-func DetectPackageTargets(packageDir string, generatedDir string) ([]*FileDetectionResult, error) {
+// FileTooLargeError is returned for source files larger than the configured limit,
+// which would exceed the context of the model
+type FileTooLargeError struct {
+	FilePath string
+	Size     int64
+	Limit    int64
+}
+
+func (e *FileTooLargeError) Error() string {
+	return fmt.Sprintf("%s is too large (%d bytes, limit %d bytes); pass --allow-large-batch or set max_file_size_bytes in mantra.toml to a higher value to process it",
+		e.FilePath, e.Size, e.Limit)
+}
+
+// checkFileSize returns a FileTooLargeError if the file exceeds maxFileSize bytes
+func checkFileSize(filePath string, maxFileSize int64) error {
+	info, err := os.Stat(filePath)
+	if err != nil {
+		return fmt.Errorf("failed to stat %s: %w", filePath, err)
+	}
+	if info.Size() > maxFileSize {
+		return &FileTooLargeError{FilePath: filePath, Size: info.Size(), Limit: maxFileSize}
+	}
+	return nil
+}
+
+// DetectPackageTargets analyzes all Go files in a package directory and returns detection results for all files.
+// Files with targets larger than maxFileSize bytes fail with a FileTooLargeError,
+// unless maxFileSize is 0. Instruction
+// comments start with instructionPrefix (empty for the default "// mantra:").
+func DetectPackageTargets(packageDir string, generatedDir string, maxFileSize int64, instructionPrefix string) ([]*FileDetectionResult, error) {
 	// Find all Go files in the package
 	files, err := filepath.Glob(filepath.Join(packageDir, "*.go"))
 	if err != nil {
@@ -52,17 +84,39 @@ func DetectPackageTargets(packageDir string, generatedDir string) ([]*FileDetect
 
 	// Process each source file
 	for _, sourceFile := range files {
-		// Skip test files
-		if strings.HasSuffix(sourceFile, "_test.go") {
-			continue
-		}
+		isTestFile := strings.HasSuffix(sourceFile, "_test.go")
 
 		// Parse source file for targets
-		fileInfo, err := parser.ParseFileInfo(sourceFile)
+		fileInfo, err := parser.ParseFileInfoWithPrefix(sourceFile, instructionPrefix)
 		if err != nil {
+			var parseErr *parser.ParseError
+			if errors.As(err, &parseErr) {
+				for _, instruction := range parseErr.SkippedInstructions {
+					slog.Warn("mantra target skipped due to syntax error",
+						slog.String("file", filepath.Base(sourceFile)),
+						slog.Int("line", parseErr.Line),
+						slog.String("instruction", firstLine(instruction)))
+				}
+			}
+			// A broken test file does not prevent generating the package
+			if isTestFile {
+				continue
+			}
 			return nil, fmt.Errorf("failed to parse %s: %w", sourceFile, err)
 		}
 
+		// Test files are only generated when they contain mantra:test targets
+		if isTestFile && len(fileInfo.Targets) == 0 {
+			continue
+		}
+
+		// Only files with targets are sent to the model
+		if len(fileInfo.Targets) > 0 && maxFileSize > 0 {
+			if err := checkFileSize(sourceFile, maxFileSize); err != nil {
+				return nil, err
+			}
+		}
+
 		// Get generated file path
 		generatedFile := filepath.Join(generatedDir, filepath.Base(sourceFile))
 
@@ -85,17 +139,19 @@ func DetectPackageTargets(packageDir string, generatedDir string) ([]*FileDetect
 		for _, target := range fileInfo.Targets {
 			// Process all targets with mantra comments (remove HasPanic check)
 			currentChecksum := checksum.Calculate(target)
-			existingImpl, exists := existingImplementations[target.Name]
+			existingImpl, exists := existingImplementations[target.GetDisplayName()]
 
 			var status Status
 			var existingChecksum string
 			var existingBody string
+			var existingImports []string
 
 			if exists {
 				existingChecksum = existingImpl.Checksum
 				if existingChecksum == currentChecksum {
 					status = StatusCurrent
 					existingBody = existingImpl.Body
+					existingImports = existingImpl.Imports
 				} else {
 					status = StatusOutdated
 				}
@@ -109,6 +165,7 @@ func DetectPackageTargets(packageDir string, generatedDir string) ([]*FileDetect
 				CurrentChecksum:  currentChecksum,
 				ExistingChecksum: existingChecksum,
 				ExistingImpl:     existingBody,
+				ExistingImports:  existingImports,
 			})
 		}
 
@@ -123,15 +180,40 @@ func DetectPackageTargets(packageDir string, generatedDir string) ([]*FileDetect
 type ImplementationInfo struct {
 	Checksum string
 	Body     string
+	Imports  []string // Import paths referenced from the body
+}
+
+// ExistingImplementations returns the functions of a generated file that have a
+// checksum comment, keyed by their display name, e.g. "(*Repo).Get" for methods
+func ExistingImplementations(generatedFile string) (map[string]*ImplementationInfo, error) {
+	return extractImplementationsFromFile(generatedFile)
+}
+
+// ExistingChecksums returns the checksums recorded in a generated file, keyed by
+// display name like ExistingImplementations
+func ExistingChecksums(generatedFile string) (map[string]string, error) {
+	impls, err := extractImplementationsFromFile(generatedFile)
+	if err != nil {
+		return nil, err
+	}
+
+	checksums := make(map[string]string, len(impls))
+	for name, impl := range impls {
+		checksums[name] = impl.Checksum
+	}
+	return checksums, nil
 }
 
 // extractImplementationsFromFile parses a generated file and extracts function checksums and implementations
 func extractImplementationsFromFile(filePath string) (map[string]*ImplementationInfo, error) {
-	content, err := os.ReadFile(filePath)
+	data, err := os.ReadFile(filePath)
 	if err != nil {
 		return nil, err
 	}
 
+	// Generated files keep the line endings of their source
+	content := parser.NormalizeLineEndings(string(data))
+
 	fset := token.NewFileSet()
 	node, err := goparser.ParseFile(fset, filePath, content, goparser.ParseComments)
 	if err != nil {
@@ -140,15 +222,25 @@ func extractImplementationsFromFile(filePath string) (map[string]*Implementation
 
 	implementations := make(map[string]*ImplementationInfo)
 
-	// Walk through all functions
-	ast.Inspect(node, func(n ast.Node) bool {
-		funcDecl, ok := n.(*ast.FuncDecl)
-		if !ok {
-			return true
+	// Walk through all functions and closures assigned to vars
+	for _, decl := range node.Decls {
+		var name string
+		var body *ast.BlockStmt
+		switch x := decl.(type) {
+		case *ast.FuncDecl:
+			name, body = parser.DeclDisplayName(x), x.Body
+		case *ast.GenDecl:
+			ident, lit := parser.ClosureLiteral(x)
+			if lit == nil {
+				continue
+			}
+			name, body = ident.Name, lit.Body
+		default:
+			continue
 		}
 
 		// Look for checksum comment immediately before function
-		funcPos := fset.Position(funcDecl.Pos())
+		funcPos := fset.Position(decl.Pos())
 		var foundChecksum string
 		for _, commentGroup := range node.Comments {
 			commentPos := fset.Position(commentGroup.End())
@@ -166,28 +258,63 @@ func extractImplementationsFromFile(filePath string) (map[string]*Implementation
 		// If we found a checksum, extract the function body
 		if foundChecksum != "" {
 			// Get the function body without panic check
-			bodyContent := extractFunctionBody(string(content), funcDecl, fset)
-			implementations[funcDecl.Name.Name] = &ImplementationInfo{
+			bodyContent := extractFunctionBody(content, body, fset)
+			implementations[name] = &ImplementationInfo{
 				Checksum: foundChecksum,
 				Body:     bodyContent,
+				Imports:  referencedImports(node, body),
 			}
 		}
+	}
+
+	return implementations, nil
+}
 
+// referencedImports returns the import paths of the file that are used within body
+func referencedImports(file *ast.File, body *ast.BlockStmt) []string {
+	if body == nil {
+		return nil
+	}
+
+	// Map package identifiers to import paths
+	importsByName := make(map[string]string)
+	for _, imp := range file.Imports {
+		path := strings.Trim(imp.Path.Value, `"`)
+		name := imports.PackageName(path)
+		if imp.Name != nil {
+			name = imp.Name.Name
+		}
+		importsByName[name] = path
+	}
+
+	seen := make(map[string]bool)
+	var paths []string
+	ast.Inspect(body, func(n ast.Node) bool {
+		sel, ok := n.(*ast.SelectorExpr)
+		if !ok {
+			return true
+		}
+		if ident, ok := sel.X.(*ast.Ident); ok {
+			if path, exists := importsByName[ident.Name]; exists && !seen[path] {
+				seen[path] = true
+				paths = append(paths, path)
+			}
+		}
 		return true
 	})
 
-	return implementations, nil
+	return paths
 }
 
 // extractFunctionBody extracts the body content of a function from source
-func extractFunctionBody(source string, funcDecl *ast.FuncDecl, fset *token.FileSet) string {
-	if funcDecl.Body == nil {
+func extractFunctionBody(source string, body *ast.BlockStmt, fset *token.FileSet) string {
+	if body == nil {
 		return ""
 	}
 
 	// Get positions
-	start := fset.Position(funcDecl.Body.Lbrace)
-	end := fset.Position(funcDecl.Body.Rbrace)
+	start := fset.Position(body.Lbrace)
+	end := fset.Position(body.Rbrace)
 
 	lines := strings.Split(source, "\n")
 	if start.Line <= 0 || end.Line > len(lines) {
@@ -214,3 +341,9 @@ func extractFunctionBody(source string, funcDecl *ast.FuncDecl, fset *token.File
 
 	return strings.Join(bodyLines, "\n")
 }
+
+// firstLine returns the first line of a possibly multi-line instruction
+func firstLine(s string) string {
+	line, _, _ := strings.Cut(s, "\n")
+	return line
+}
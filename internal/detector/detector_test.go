@@ -0,0 +1,96 @@
+package detector
+
+import (
+	"errors"
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/config"
+)
+
+func TestExistingChecksums(t *testing.T) {
+	dir := t.TempDir()
+	generatedFile := filepath.Join(dir, "user.go")
+	content := `package generated
+
+// mantra:checksum:1a2b3c4d
+func GetUser(id string) string {
+	return id
+}
+
+// mantra:checksum:5e6f7a8b
+func (r *Repo) GetUser(id string) string {
+	return id
+}
+
+func helper() {}
+`
+	if err := os.WriteFile(generatedFile, []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	checksums, err := ExistingChecksums(generatedFile)
+	if err != nil {
+		t.Fatalf("ExistingChecksums failed: %v", err)
+	}
+
+	if got := checksums["GetUser"]; got != "1a2b3c4d" {
+		t.Errorf("Expected checksum 1a2b3c4d for GetUser, got %q", got)
+	}
+	if got := checksums["(*Repo).GetUser"]; got != "5e6f7a8b" {
+		t.Errorf("Expected checksum 5e6f7a8b for (*Repo).GetUser, got %q", got)
+	}
+	if _, ok := checksums["helper"]; ok {
+		t.Error("Expected no checksum for function without checksum comment")
+	}
+}
+
+func TestExistingChecksums_MissingFile(t *testing.T) {
+	if _, err := ExistingChecksums(filepath.Join(t.TempDir(), "missing.go")); !os.IsNotExist(err) {
+		t.Errorf("Expected not-exist error, got %v", err)
+	}
+}
+
+func TestDetectPackageTargets_FileTooLarge(t *testing.T) {
+	dir := t.TempDir()
+	padding := "// " + strings.Repeat("x", config.DefaultMaxFileSizeBytes) + "\n"
+	content := "package large\n\n" + padding + "\n// mantra: Return one\nfunc One() int {\n\tpanic(\"not implemented\")\n}\n"
+	if err := os.WriteFile(filepath.Join(dir, "large.go"), []byte(content), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	// Large files without targets are not checked
+	if err := os.WriteFile(filepath.Join(dir, "data.go"), []byte("package large\n\n"+padding), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(dir, "large_test.go"), []byte("package large\n\n"+padding), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	_, err := DetectPackageTargets(dir, filepath.Join(dir, "generated"), config.DefaultMaxFileSizeBytes, "")
+	var tooLarge *FileTooLargeError
+	if !errors.As(err, &tooLarge) {
+		t.Fatalf("Expected FileTooLargeError, got %v", err)
+	}
+	if tooLarge.FilePath != filepath.Join(dir, "large.go") {
+		t.Errorf("Expected large.go to be reported, got %s", tooLarge.FilePath)
+	}
+	if tooLarge.Size != int64(len(content)) || tooLarge.Limit != config.DefaultMaxFileSizeBytes {
+		t.Errorf("Expected size %d and limit %d, got %d and %d", len(content), config.DefaultMaxFileSizeBytes, tooLarge.Size, tooLarge.Limit)
+	}
+	if !strings.Contains(err.Error(), "max_file_size_bytes") {
+		t.Errorf("Expected error to suggest max_file_size_bytes, got %q", err.Error())
+	}
+
+	// A higher limit lets the file through
+	if _, err := DetectPackageTargets(dir, filepath.Join(dir, "generated"), int64(len(content)), ""); err != nil {
+		t.Errorf("Expected no error with a higher limit, got %v", err)
+	}
+
+	// No limit at all
+	if _, err := DetectPackageTargets(dir, filepath.Join(dir, "generated"), 0, ""); err != nil {
+		t.Errorf("Expected no error without a limit, got %v", err)
+	}
+}
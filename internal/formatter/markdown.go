@@ -77,5 +77,18 @@ func FormatContextAsMarkdown(contextResult map[string]any) string {
 		formatted.WriteString("\n")
 	}
 
+	// Format the failing tests of a previous attempt
+	if failure, ok := contextResult["test_failure"].(map[string]any); ok {
+		formatted.WriteString("### Previous Attempt Failed Tests\n\n")
+		formatted.WriteString("Fix the implementation so that the tests pass.\n")
+		if code, ok := failure["code"].(string); ok {
+			formatted.WriteString(fmt.Sprintf("```go\n%s\n```\n", code))
+		}
+		if output, ok := failure["output"].(string); ok {
+			formatted.WriteString(fmt.Sprintf("**Test output:**\n```\n%s\n```\n", output))
+		}
+		formatted.WriteString("\n")
+	}
+
 	return formatted.String()
 }
@@ -0,0 +1,124 @@
+package history
+
+import (
+	"bufio"
+	"crypto/sha256"
+	"encoding/hex"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"os"
+	"path/filepath"
+	"time"
+)
+
+// Entry is a single recorded generation of a target
+type Entry struct {
+	Timestamp        time.Time `json:"timestamp"`
+	Model            string    `json:"model"`
+	Instruction      string    `json:"instruction"`
+	GeneratedBody    string    `json:"generated_body"`
+	PromptTokens     int       `json:"prompt_tokens"`
+	CompletionTokens int       `json:"completion_tokens"`
+}
+
+// Store keeps generation history as JSON lines, one file per target checksum
+// under a directory derived from the source file path
+type Store struct {
+	root string
+}
+
+// NewStore creates a store rooted at the given directory
+func NewStore(root string) *Store {
+	return &Store{root: root}
+}
+
+// DefaultStore returns the store in the user's data directory
+// ($XDG_DATA_HOME/mantra/history, or ~/.local/share/mantra/history)
+func DefaultStore() (*Store, error) {
+	dataDir := os.Getenv("XDG_DATA_HOME")
+	if dataDir == "" {
+		home, err := os.UserHomeDir()
+		if err != nil {
+			return nil, fmt.Errorf("failed to find home directory: %w", err)
+		}
+		dataDir = filepath.Join(home, ".local", "share")
+	}
+	return NewStore(filepath.Join(dataDir, "mantra", "history")), nil
+}
+
+// Append records a generation for the target with the given checksum
+func (s *Store) Append(filePath, checksum string, entry Entry) error {
+	path, err := s.path(filePath, checksum)
+	if err != nil {
+		return err
+	}
+
+	if err := os.MkdirAll(filepath.Dir(path), 0755); err != nil {
+		return fmt.Errorf("failed to create history directory: %w", err)
+	}
+
+	data, err := json.Marshal(entry)
+	if err != nil {
+		return fmt.Errorf("failed to encode history entry: %w", err)
+	}
+
+	f, err := os.OpenFile(path, os.O_APPEND|os.O_CREATE|os.O_WRONLY, 0644)
+	if err != nil {
+		return fmt.Errorf("failed to open history file: %w", err)
+	}
+	defer f.Close()
+
+	if _, err := f.Write(append(data, '\n')); err != nil {
+		return fmt.Errorf("failed to write history entry: %w", err)
+	}
+	return nil
+}
+
+// Load returns all recorded generations for the target with the given
+// checksum, oldest first. It returns no entries if nothing was recorded.
+func (s *Store) Load(filePath, checksum string) ([]Entry, error) {
+	path, err := s.path(filePath, checksum)
+	if err != nil {
+		return nil, err
+	}
+
+	f, err := os.Open(path)
+	if errors.Is(err, os.ErrNotExist) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to open history file: %w", err)
+	}
+	defer f.Close()
+
+	var entries []Entry
+	scanner := bufio.NewScanner(f)
+	scanner.Buffer(make([]byte, 0, 64*1024), 16*1024*1024)
+	for scanner.Scan() {
+		if len(scanner.Bytes()) == 0 {
+			continue
+		}
+		var entry Entry
+		if err := json.Unmarshal(scanner.Bytes(), &entry); err != nil {
+			return nil, fmt.Errorf("failed to decode history entry: %w", err)
+		}
+		entries = append(entries, entry)
+	}
+	if err := scanner.Err(); err != nil {
+		return nil, fmt.Errorf("failed to read history file: %w", err)
+	}
+
+	return entries, nil
+}
+
+// path returns the history file of a target
+func (s *Store) path(filePath, checksum string) (string, error) {
+	absPath, err := filepath.Abs(filePath)
+	if err != nil {
+		return "", fmt.Errorf("failed to get absolute path: %w", err)
+	}
+
+	hash := sha256.Sum256([]byte(absPath))
+	return filepath.Join(s.root, hex.EncodeToString(hash[:8]), checksum+".jsonl"), nil
+}
@@ -0,0 +1,50 @@
+package history
+
+import (
+	"testing"
+	"time"
+)
+
+func TestStoreAppendAndLoad(t *testing.T) {
+	store := NewStore(t.TempDir())
+
+	first := Entry{
+		Timestamp:        time.Date(2025, 1, 1, 12, 0, 0, 0, time.UTC),
+		Model:            "model-a",
+		Instruction:      "add two numbers",
+		GeneratedBody:    "return a + b",
+		PromptTokens:     100,
+		CompletionTokens: 10,
+	}
+	second := first
+	second.Model = "model-b"
+	second.GeneratedBody = "sum := a + b\nreturn sum"
+
+	for _, entry := range []Entry{first, second} {
+		if err := store.Append("math.go", "abc123", entry); err != nil {
+			t.Fatal(err)
+		}
+	}
+
+	entries, err := store.Load("math.go", "abc123")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(entries) != 2 {
+		t.Fatalf("Expected 2 entries, got %d", len(entries))
+	}
+	if entries[0] != first {
+		t.Errorf("Expected %+v, got %+v", first, entries[0])
+	}
+	if entries[1] != second {
+		t.Errorf("Expected %+v, got %+v", second, entries[1])
+	}
+
+	// Other checksums and files have their own history
+	if entries, err := store.Load("math.go", "def456"); err != nil || len(entries) != 0 {
+		t.Errorf("Expected no entries for another checksum, got %v (err: %v)", entries, err)
+	}
+	if entries, err := store.Load("other.go", "abc123"); err != nil || len(entries) != 0 {
+		t.Errorf("Expected no entries for another file, got %v (err: %v)", entries, err)
+	}
+}
@@ -140,6 +140,109 @@ func checkFunctionImport(funcName string, requiredImports map[string]bool) {
 	}
 }
 
+// FilterUsedImports returns the import paths whose package name is referenced
+// in a selector expression (e.g., strings.ToUpper) of the function body code,
+// so that unused imports do not break compilation
+func FilterUsedImports(code string, paths []string) []string {
+	fileContent := "package temp\nfunc temp() {\n" + code + "\n}"
+
+	used := make(map[string]bool)
+	node, err := parser.ParseFile(token.NewFileSet(), "temp.go", fileContent, 0)
+	if err == nil {
+		ast.Inspect(node, func(n ast.Node) bool {
+			if sel, ok := n.(*ast.SelectorExpr); ok {
+				if ident, ok := sel.X.(*ast.Ident); ok {
+					used[ident.Name] = true
+				}
+			}
+			return true
+		})
+	}
+
+	var filtered []string
+	for _, path := range paths {
+		name := PackageName(path)
+		// Fall back to string matching if the code does not parse
+		if used[name] || (err != nil && strings.Contains(code, name+".")) {
+			filtered = append(filtered, path)
+		}
+	}
+	return filtered
+}
+
+// PackageName guesses the name a package is referred to by from its import path,
+// skipping major version suffixes such as "math/rand/v2" and "gopkg.in/yaml.v3"
+func PackageName(path string) string {
+	elems := strings.Split(path, "/")
+	name := elems[len(elems)-1]
+	if isMajorVersion(name) && len(elems) > 1 {
+		name = elems[len(elems)-2]
+	}
+	if base, version, ok := strings.Cut(name, "."); ok && isMajorVersion(version) {
+		name = base
+	}
+	return name
+}
+
+// isMajorVersion reports whether s has the form "vN"
+func isMajorVersion(s string) bool {
+	if len(s) < 2 || s[0] != 'v' {
+		return false
+	}
+	for _, r := range s[1:] {
+		if r < '0' || r > '9' {
+			return false
+		}
+	}
+	return true
+}
+
+// DetectMissingImports returns package names that are used in selector
+// expressions (e.g., strings.ToUpper) of a Go file but neither imported
+// nor declared in the file
+func DetectMissingImports(source string) ([]string, error) {
+	fset := token.NewFileSet()
+	node, err := parser.ParseFile(fset, "source.go", source, 0)
+	if err != nil {
+		return nil, err
+	}
+
+	// Names that resolve to imports
+	imported := make(map[string]bool)
+	for _, imp := range node.Imports {
+		name := PackageName(strings.Trim(imp.Path.Value, `"`))
+		if imp.Name != nil {
+			name = imp.Name.Name
+		}
+		imported[name] = true
+	}
+
+	// Identifiers the parser could not resolve within the file
+	unresolved := make(map[*ast.Ident]bool)
+	for _, ident := range node.Unresolved {
+		unresolved[ident] = true
+	}
+
+	seen := make(map[string]bool)
+	var missing []string
+	ast.Inspect(node, func(n ast.Node) bool {
+		sel, ok := n.(*ast.SelectorExpr)
+		if !ok {
+			return true
+		}
+		ident, ok := sel.X.(*ast.Ident)
+		if !ok || !unresolved[ident] || imported[ident.Name] || seen[ident.Name] {
+			return true
+		}
+		seen[ident.Name] = true
+		missing = append(missing, ident.Name)
+		return true
+	})
+
+	sort.Strings(missing)
+	return missing, nil
+}
+
 // MergeImports merges new imports with existing imports, avoiding duplicates
 func MergeImports(existingImports []string, newImports []string) []string {
 	importSet := make(map[string]bool)
@@ -0,0 +1,133 @@
+package imports
+
+import (
+	"reflect"
+	"testing"
+)
+
+func TestDetectMissingImports(t *testing.T) {
+	tests := []struct {
+		name     string
+		source   string
+		expected []string
+	}{
+		{
+			name: "missing strings",
+			source: `package p
+
+func Upper(s string) string {
+	return strings.ToUpper(s)
+}
+`,
+			expected: []string{"strings"},
+		},
+		{
+			name: "imported with alias",
+			source: `package p
+
+import str "strings"
+
+func Upper(s string) string {
+	return str.ToUpper(s)
+}
+`,
+			expected: nil,
+		},
+		{
+			name: "local variables and types",
+			source: `package p
+
+import "fmt"
+
+type user struct{ name string }
+
+var current user
+
+func Name(u user) string {
+	return fmt.Sprint(u.name, current.name)
+}
+`,
+			expected: nil,
+		},
+		{
+			name: "multiple missing",
+			source: `package p
+
+func Run() {
+	fmt.Println(strings.Repeat("a", 3), fmt.Sprint(1))
+}
+`,
+			expected: []string{"fmt", "strings"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			missing, err := DetectMissingImports(tt.source)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if !reflect.DeepEqual(missing, tt.expected) {
+				t.Errorf("Expected %v, got %v", tt.expected, missing)
+			}
+		})
+	}
+}
+
+func TestPackageName(t *testing.T) {
+	tests := map[string]string{
+		"strings":                "strings",
+		"path/filepath":          "filepath",
+		"math/rand/v2":           "rand",
+		"gopkg.in/yaml.v3":       "yaml",
+		"github.com/foo/bar.baz": "bar.baz",
+		"github.com/foo/vendor":  "vendor",
+	}
+	for path, expected := range tests {
+		if got := PackageName(path); got != expected {
+			t.Errorf("PackageName(%q) = %q, expected %q", path, got, expected)
+		}
+	}
+}
+
+func TestFilterUsedImports(t *testing.T) {
+	tests := []struct {
+		name     string
+		code     string
+		paths    []string
+		expected []string
+	}{
+		{
+			name:     "used and unused",
+			code:     "return strings.ToUpper(s)",
+			paths:    []string{"strings", "fmt"},
+			expected: []string{"strings"},
+		},
+		{
+			name:     "major version suffix",
+			code:     "return rand.IntN(n)",
+			paths:    []string{"math/rand/v2"},
+			expected: []string{"math/rand/v2"},
+		},
+		{
+			name:     "name only in a string",
+			code:     `return "fmt.Sprintf"`,
+			paths:    []string{"fmt"},
+			expected: nil,
+		},
+		{
+			name:     "code that does not parse",
+			code:     "return strings.ToUpper(s",
+			paths:    []string{"strings", "fmt"},
+			expected: []string{"strings"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			if got := FilterUsedImports(tt.code, tt.paths); !reflect.DeepEqual(got, tt.expected) {
+				t.Errorf("Expected %v, got %v", tt.expected, got)
+			}
+		})
+	}
+}
@@ -10,11 +10,14 @@ import (
 
 // ClientConfig represents the configuration for connecting to an AI provider
 type ClientConfig struct {
-	URL      string        // URL for the API endpoint (e.g., "http://localhost:11434/v1" for Ollama)
-	APIKey   string        // API key for providers that require authentication
-	Model    string        // Model to use
-	Timeout  time.Duration // Request timeout
-	Provider []string      // OpenRouter provider specification (e.g., ["Cerebras"])
+	URL        string        // URL for the API endpoint (e.g., "http://localhost:11434/v1" for Ollama)
+	APIKey     string        // API key for providers that require authentication
+	Model      string        // Model to use
+	Timeout    time.Duration // Request timeout
+	Provider   *ProviderSpec // OpenRouter provider routing (nil for default routing)
+	Transforms []string      // OpenRouter message transforms (e.g., ["middle-out"])
+	Stop       []string      // Sequences at which the model stops generating
+	Seed       *int          // Sampling seed for reproducible output (nil for none)
 }
 
 type Client struct {
@@ -48,6 +51,9 @@ func NewClient(clientConfig *ClientConfig, httpClient *http.Client, logger *slog
 		Temperature:  0.7,        // Default, will be overridden by phase
 		HTTPClient:   httpClient, // Can be nil, will be created if needed
 		ProviderSpec: clientConfig.Provider,
+		Transforms:   clientConfig.Transforms,
+		Stop:         clientConfig.Stop,
+		Seed:         clientConfig.Seed,
 		Logger:       logger,
 	}
 
@@ -68,6 +74,11 @@ func (c *Client) Generate(ctx context.Context, prompt string) (string, error) {
 	return c.provider.Generate(ctx, prompt, c.tools, c.toolExecutor)
 }
 
+// Usage returns the tokens consumed by all requests of this client
+func (c *Client) Usage() Usage {
+	return c.provider.Usage()
+}
+
 // GetProviderName returns the name of the current provider
 func (c *Client) GetProviderName() string {
 	return c.provider.Name()
@@ -89,6 +100,11 @@ func (c *Client) SetSystemPrompt(systemPrompt string) {
 	c.provider.SetSystemPrompt(systemPrompt)
 }
 
+// SetExamples sets example exchanges sent before each prompt
+func (c *Client) SetExamples(examples []Exchange) {
+	c.provider.SetExamples(examples)
+}
+
 // SetLogger sets the logger for the client
 func (c *Client) SetLogger(logger *slog.Logger) {
 	c.logger = logger
@@ -17,17 +17,12 @@ func (c *OpenAIClient) Generate(ctx context.Context, prompt string, tools []Tool
 
 	// System prompt is set but not logged
 
-	// Build initial messages with system prompt
-	messages := []OpenAIMessage{
-		{
-			Role:    "system",
-			Content: c.systemPrompt,
-		},
-		{
-			Role:    "user",
-			Content: prompt,
-		},
+	// Build initial messages with system prompt, followed by few-shot examples
+	messages := []OpenAIMessage{SystemMessage(c.systemPrompt)}
+	for _, example := range c.examples {
+		messages = append(messages, UserMessage(example.Prompt), AssistantMessage(example.Response))
 	}
+	messages = append(messages, UserMessage(prompt))
 
 	// Maximum rounds of tool calls to prevent infinite loops
 	const maxRounds = 30
@@ -48,6 +43,9 @@ func (c *OpenAIClient) Generate(ctx context.Context, prompt string, tools []Tool
 			ToolChoice:        "auto",
 			ParallelToolCalls: true,
 			Provider:          c.providerSpec,
+			Transforms:        c.transforms,
+			Stop:              c.stop,
+			Seed:              c.seed,
 		}
 
 		// Make API call
@@ -57,6 +55,8 @@ func (c *OpenAIClient) Generate(ctx context.Context, prompt string, tools []Tool
 		if err != nil {
 			return "", err
 		}
+		c.usage.PromptTokens += resp.Usage.PromptTokens
+		c.usage.CompletionTokens += resp.Usage.CompletionTokens
 
 		if len(resp.Choices) == 0 {
 			return "", fmt.Errorf("no response choices returned")
@@ -97,10 +97,7 @@ func (c *OpenAIClient) Generate(ctx context.Context, prompt string, tools []Tool
 
 			// If result tool exists but wasn't called yet, prompt the AI to use it
 			if hasResultTool && !resultToolCalled && round < maxRounds-1 { // Leave one round for the final attempt
-				messages = append(messages, OpenAIMessage{
-					Role:    "user",
-					Content: "Please complete the task by calling the result() tool with the appropriate data. The result() tool is required to finalize this phase.",
-				})
+				messages = append(messages, UserMessage("Please complete the task by calling the result() tool with the appropriate data. The result() tool is required to finalize this phase."))
 				continue
 			}
 
@@ -1,6 +1,9 @@
 package llm
 
-import "context"
+import (
+	"context"
+	"time"
+)
 
 // Provider represents an AI service provider
 type Provider interface {
@@ -15,6 +18,27 @@ type Provider interface {
 
 	// SetSystemPrompt sets the system prompt
 	SetSystemPrompt(systemPrompt string)
+
+	// SetExamples sets example exchanges sent before the prompt
+	SetExamples(examples []Exchange)
+
+	// Usage returns the tokens consumed by all requests so far
+	Usage() Usage
+}
+
+// Exchange is an example prompt and the expected response, used for few-shot prompting
+type Exchange struct {
+	Prompt   string
+	Response string
+}
+
+// Usage holds token counts reported by the API and request statistics
+type Usage struct {
+	PromptTokens     int
+	CompletionTokens int
+	Requests         int           // Requests sent, including failed ones
+	RateLimited      int           // Requests rejected with 429 Too Many Requests
+	RequestTime      time.Duration // Time spent waiting for responses
 }
 
 // ToolExecutor executes tool calls
@@ -17,10 +17,15 @@ type OpenAIClient struct {
 	apiKey             string
 	baseURL            string
 	model              string
-	currentTemperature float32 // Current temperature to use
-	systemPrompt       string  // Current system prompt
+	currentTemperature float32    // Current temperature to use
+	systemPrompt       string     // Current system prompt
+	examples           []Exchange // Few-shot examples sent before the prompt
 	httpClient         *http.Client
 	providerSpec       *ProviderSpec // OpenRouter-specific provider routing
+	transforms         []string      // OpenRouter message transforms
+	stop               []string      // Stop sequences
+	seed               *int          // Sampling seed
+	usage              Usage         // Tokens consumed by all requests
 	logger             *slog.Logger
 }
 
@@ -32,12 +37,17 @@ type OpenAIRequest struct {
 	Tools             []Tool          `json:"tools,omitempty"`
 	ToolChoice        any             `json:"tool_choice,omitempty"`
 	ParallelToolCalls bool            `json:"parallel_tool_calls,omitempty"`
-	Provider          *ProviderSpec   `json:"provider,omitempty"` // OpenRouter provider specification
+	Provider          *ProviderSpec   `json:"provider,omitempty"`   // OpenRouter provider specification
+	Transforms        []string        `json:"transforms,omitempty"` // OpenRouter message transforms
+	Stop              []string        `json:"stop,omitempty"`       // Sequences at which generation stops
+	Seed              *int            `json:"seed,omitempty"`       // Sampling seed for providers that support it
 }
 
 // ProviderSpec allows specifying provider routing for OpenRouter
 type ProviderSpec struct {
-	Only []string `json:"only,omitempty"` // List of providers to use (e.g., ["Cerebras"])
+	Only           []string `json:"only,omitempty"`            // List of providers to use (e.g., ["Cerebras"])
+	Order          []string `json:"order,omitempty"`           // Providers to try first, in order
+	AllowFallbacks *bool    `json:"allow_fallbacks,omitempty"` // Whether other providers may be used when preferred ones fail
 }
 
 // OpenAIMessage represents a message in the chat
@@ -49,6 +59,34 @@ type OpenAIMessage struct {
 	ToolCallID string     `json:"tool_call_id,omitempty"`
 }
 
+// Message roles
+const (
+	RoleSystem    = "system"
+	RoleUser      = "user"
+	RoleAssistant = "assistant"
+	RoleTool      = "tool"
+)
+
+// SystemMessage creates a system message
+func SystemMessage(content string) OpenAIMessage {
+	return OpenAIMessage{Role: RoleSystem, Content: content}
+}
+
+// UserMessage creates a user message
+func UserMessage(content string) OpenAIMessage {
+	return OpenAIMessage{Role: RoleUser, Content: content}
+}
+
+// AssistantMessage creates an assistant message, e.g. for few-shot examples
+func AssistantMessage(content string) OpenAIMessage {
+	return OpenAIMessage{Role: RoleAssistant, Content: content}
+}
+
+// ToolMessage creates a message with the result of a tool call
+func ToolMessage(content, toolCallID string) OpenAIMessage {
+	return OpenAIMessage{Role: RoleTool, Content: content, ToolCallID: toolCallID}
+}
+
 // OpenAIResponse represents a chat completion response
 type OpenAIResponse struct {
 	ID      string `json:"id"`
@@ -76,7 +114,10 @@ type OpenAIClientOptions struct {
 	Temperature  float32
 	SystemPrompt string
 	HTTPClient   *http.Client
-	ProviderSpec []string // For OpenRouter provider routing
+	ProviderSpec *ProviderSpec // For OpenRouter provider routing
+	Transforms   []string      // For OpenRouter message transforms
+	Stop         []string      // Stop sequences
+	Seed         *int          // Sampling seed (nil for none)
 	Logger       *slog.Logger
 }
 
@@ -115,26 +156,19 @@ func NewOpenAIClientWithOptions(opts *OpenAIClientOptions) (*OpenAIClient, error
 		currentTemperature: opts.Temperature,
 		systemPrompt:       opts.SystemPrompt,
 		httpClient:         httpClient,
+		providerSpec:       opts.ProviderSpec,
+		transforms:         opts.Transforms,
+		stop:               opts.Stop,
+		seed:               opts.Seed,
 		logger:             opts.Logger,
 	}
 
-	// Set provider spec if provided
-	if len(opts.ProviderSpec) > 0 {
-		client.providerSpec = &ProviderSpec{
-			Only: opts.ProviderSpec,
-		}
-	}
-
 	return client, nil
 }
 
 // SetProviderSpec sets OpenRouter provider routing specification
-func (c *OpenAIClient) SetProviderSpec(providers []string) {
-	if len(providers) > 0 {
-		c.providerSpec = &ProviderSpec{
-			Only: providers,
-		}
-	}
+func (c *OpenAIClient) SetProviderSpec(spec *ProviderSpec) {
+	c.providerSpec = spec
 }
 
 // SetTemperature sets the temperature for generation
@@ -148,6 +182,16 @@ func (c *OpenAIClient) SetSystemPrompt(systemPrompt string) {
 	// Logging is deferred to Generate() where we have access to the context
 }
 
+// SetExamples sets example exchanges sent before the prompt
+func (c *OpenAIClient) SetExamples(examples []Exchange) {
+	c.examples = examples
+}
+
+// Usage returns the tokens consumed by all requests so far
+func (c *OpenAIClient) Usage() Usage {
+	return c.usage
+}
+
 // Name returns the provider name
 func (c *OpenAIClient) Name() string {
 	// Return a simple name based on the model being used
@@ -174,6 +218,12 @@ func (c *OpenAIClient) makeRequest(ctx context.Context, req OpenAIRequest) (*Ope
 	httpReq.Header.Set("HTTP-Referer", "https://github.com/rail44/mantra")
 	httpReq.Header.Set("X-Title", "mantra")
 
+	start := time.Now()
+	c.usage.Requests++
+	defer func() {
+		c.usage.RequestTime += time.Since(start)
+	}()
+
 	resp, err := c.httpClient.Do(httpReq)
 	if err != nil {
 		return nil, fmt.Errorf("request failed: %w", err)
@@ -181,6 +231,9 @@ func (c *OpenAIClient) makeRequest(ctx context.Context, req OpenAIRequest) (*Ope
 	defer resp.Body.Close()
 
 	if resp.StatusCode != http.StatusOK {
+		if resp.StatusCode == http.StatusTooManyRequests {
+			c.usage.RateLimited++
+		}
 		body, _ := io.ReadAll(resp.Body)
 		return nil, fmt.Errorf("API request failed with status %d: %s", resp.StatusCode, string(body))
 	}
@@ -0,0 +1,31 @@
+package llm
+
+import (
+	"encoding/json"
+	"testing"
+)
+
+func TestMessageConstructors(t *testing.T) {
+	tests := []struct {
+		name     string
+		message  OpenAIMessage
+		expected string
+	}{
+		{name: "system", message: SystemMessage("be terse"), expected: `{"role":"system","content":"be terse"}`},
+		{name: "user", message: UserMessage("hi"), expected: `{"role":"user","content":"hi"}`},
+		{name: "assistant", message: AssistantMessage("hello"), expected: `{"role":"assistant","content":"hello"}`},
+		{name: "tool", message: ToolMessage("{}", "call_1"), expected: `{"role":"tool","content":"{}","tool_call_id":"call_1"}`},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			data, err := json.Marshal(tt.message)
+			if err != nil {
+				t.Fatal(err)
+			}
+			if string(data) != tt.expected {
+				t.Errorf("Expected %s, got %s", tt.expected, data)
+			}
+		})
+	}
+}
@@ -58,11 +58,7 @@ func (c *OpenAIClient) executeToolsParallel(ctx context.Context, toolCalls []Too
 				results <- toolResult{
 					index:      index,
 					toolCallID: tc.ID,
-					message: OpenAIMessage{
-						Role:       "tool",
-						Content:    errorMsg,
-						ToolCallID: tc.ID,
-					},
+					message: ToolMessage(errorMsg, tc.ID),
 				}
 				return nil
 			}
@@ -134,11 +130,7 @@ func (c *OpenAIClient) executeToolsParallel(ctx context.Context, toolCalls []Too
 				toolCallID: tc.ID,
 				duration:   elapsed,
 				isTerminal: isTerminal,
-				message: OpenAIMessage{
-					Role:       "tool",
-					Content:    resultContent,
-					ToolCallID: tc.ID,
-				},
+				message: ToolMessage(resultContent, tc.ID),
 			}
 
 			return nil
@@ -0,0 +1,22 @@
+package log
+
+import (
+	"crypto/rand"
+	"fmt"
+)
+
+// NewCorrelationID returns a random UUID v4 used to correlate all log
+// records of a single target generation
+func NewCorrelationID() string {
+	var b [16]byte
+	if _, err := rand.Read(b[:]); err != nil {
+		// crypto/rand never fails on supported platforms
+		panic(fmt.Sprintf("failed to generate correlation ID: %v", err))
+	}
+
+	// Set version (4) and variant (RFC 4122) bits
+	b[6] = (b[6] & 0x0f) | 0x40
+	b[8] = (b[8] & 0x3f) | 0x80
+
+	return fmt.Sprintf("%x-%x-%x-%x-%x", b[0:4], b[4:6], b[6:8], b[8:10], b[10:16])
+}
@@ -0,0 +1,54 @@
+package log
+
+import (
+	"context"
+	"log/slog"
+	"regexp"
+	"testing"
+)
+
+func TestNewCorrelationID(t *testing.T) {
+	uuidPattern := regexp.MustCompile(`^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$`)
+
+	seen := make(map[string]bool)
+	for i := 0; i < 100; i++ {
+		id := NewCorrelationID()
+		if !uuidPattern.MatchString(id) {
+			t.Fatalf("Correlation ID %q is not a UUID v4", id)
+		}
+		if seen[id] {
+			t.Fatalf("Duplicate correlation ID %q", id)
+		}
+		seen[id] = true
+	}
+}
+
+func TestCallbackHandler_PropagatesGenerationID(t *testing.T) {
+	var records []slog.Record
+	handler := NewCallbackHandler(func(record slog.Record) {
+		records = append(records, record)
+	}).WithAttrs([]slog.Attr{
+		slog.String("generationID", "test-id"),
+	})
+
+	// Loggers derived with With() must keep the generation ID
+	logger := slog.New(handler).With(slog.String("phase", "implementation"))
+	logger.Log(context.Background(), slog.LevelError, "failed")
+
+	if len(records) != 1 {
+		t.Fatalf("Expected 1 record, got %d", len(records))
+	}
+
+	attrs := make(map[string]string)
+	records[0].Attrs(func(a slog.Attr) bool {
+		attrs[a.Key] = a.Value.String()
+		return true
+	})
+
+	if attrs["generationID"] != "test-id" {
+		t.Errorf("Expected generationID attribute, got %v", attrs)
+	}
+	if attrs["phase"] != "implementation" {
+		t.Errorf("Expected phase attribute, got %v", attrs)
+	}
+}
@@ -113,6 +113,8 @@ func (h *Handler) Handle(ctx context.Context, r slog.Record) error {
 			totalTargets = int(a.Value.Int64())
 		case "targetName":
 			targetName = a.Value.String()
+		case "generationID":
+			// Only useful for structured output; target info already identifies the line
 		default:
 			formattedMsg += fmt.Sprintf(" %s=%v", a.Key, a.Value.Any())
 		}
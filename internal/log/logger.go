@@ -2,6 +2,7 @@ package log
 
 import (
 	"fmt"
+	"io"
 	"log/slog"
 	"os"
 	"strings"
@@ -11,13 +12,27 @@ import (
 // It can be changed dynamically using Level.Set(level).
 var Level = new(slog.LevelVar) // Info by default
 
+// EnvLogFormat selects the log format: "json" for one JSON object per line,
+// e.g. for log collectors, or text (the default)
+const EnvLogFormat = "MANTRA_LOG_FORMAT"
+
 func init() {
 	// Set the default slog handler to our custom handler
-	handler := NewHandler(os.Stderr)
+	handler := NewFormatHandler(os.Stderr, os.Getenv(EnvLogFormat))
 	logger := slog.New(handler)
 	slog.SetDefault(logger)
 }
 
+// NewFormatHandler creates the handler for the given log format. In JSON
+// format, all attributes of a record, such as targetName and generationID,
+// become keys of its JSON object.
+func NewFormatHandler(output io.Writer, format string) slog.Handler {
+	if strings.EqualFold(format, "json") {
+		return slog.NewJSONHandler(output, &slog.HandlerOptions{Level: Level})
+	}
+	return NewHandler(output)
+}
+
 // ParseLevel converts a string to slog.Level
 func ParseLevel(s string) (slog.Level, error) {
 	switch strings.ToLower(s) {
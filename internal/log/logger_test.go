@@ -0,0 +1,50 @@
+package log
+
+import (
+	"bytes"
+	"context"
+	"encoding/json"
+	"log/slog"
+	"strings"
+	"testing"
+)
+
+func TestNewFormatHandler_JSON(t *testing.T) {
+	var buf bytes.Buffer
+	output := NewFormatHandler(&buf, "json")
+
+	// Target records reach the output handler through the callback handler
+	handler := NewCallbackHandler(func(record slog.Record) {
+		output.Handle(context.Background(), record)
+	}).WithAttrs([]slog.Attr{
+		slog.String("targetName", "GetUser"),
+		slog.String("generationID", "test-id"),
+	})
+	slog.New(handler).Info("Generating...", slog.String("phase", "implementation"))
+
+	var entry map[string]any
+	if err := json.Unmarshal(buf.Bytes(), &entry); err != nil {
+		t.Fatalf("Expected a JSON log line, got %q: %v", buf.String(), err)
+	}
+	expected := map[string]string{
+		"level":        "INFO",
+		"msg":          "Generating...",
+		"targetName":   "GetUser",
+		"generationID": "test-id",
+		"phase":        "implementation",
+	}
+	for key, value := range expected {
+		if entry[key] != value {
+			t.Errorf("Expected %s=%q, got %v", key, value, entry[key])
+		}
+	}
+}
+
+func TestNewFormatHandler_Text(t *testing.T) {
+	var buf bytes.Buffer
+	slog.New(NewFormatHandler(&buf, "")).Warn("slow response", slog.Int("seconds", 3))
+
+	if got := buf.String(); !strings.HasPrefix(got, "[WARN] slow response seconds=3") {
+		t.Errorf("Expected text output, got %q", got)
+	}
+}
@@ -0,0 +1,21 @@
+package parser
+
+import "strings"
+
+// HasCRLF reports whether content uses CRLF line endings
+func HasCRLF(content string) bool {
+	return strings.Contains(content, "\r\n")
+}
+
+// NormalizeLineEndings converts CRLF line endings to LF
+func NormalizeLineEndings(content string) string {
+	return strings.ReplaceAll(content, "\r\n", "\n")
+}
+
+// RestoreLineEndings converts LF line endings back to CRLF when crlf is set
+func RestoreLineEndings(content string, crlf bool) string {
+	if !crlf {
+		return content
+	}
+	return strings.ReplaceAll(NormalizeLineEndings(content), "\n", "\r\n")
+}
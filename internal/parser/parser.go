@@ -4,10 +4,15 @@ import (
 	"fmt"
 	"go/ast"
 	"go/parser"
+	"go/scanner"
 	"go/token"
+	"log/slog"
 	"os"
+	"path/filepath"
+	"strconv"
 	"strings"
 	"time"
+	"unicode"
 
 	"github.com/rail44/mantra/internal/analysis"
 )
@@ -23,9 +28,25 @@ type FileInfo struct {
 	PackageName   string    // Package name from package declaration
 	Imports       []Import  // All import statements
 	Targets       []*Target // Generation targets
+	Skipped       []string  // Names of targets disabled with // mantra:skip
 	FilePath      string    // Source file path
-	SourceContent string    // Full source file content
+	SourceContent string    // Full source file content (line endings normalized to LF)
 	SourceLines   []string  // Source content split by lines
+	CRLF          bool      // Whether the file on disk uses CRLF line endings
+
+	// InferredPackage is set when the file has no package clause and
+	// PackageName was derived from the directory name. SourceContent and
+	// positions still refer to the file as is; see ParsableContent.
+	InferredPackage bool
+}
+
+// ParsableContent returns the source content with the inferred package clause
+// of a fragment, so that it can be parsed as a Go file
+func (f *FileInfo) ParsableContent() string {
+	if f.InferredPackage {
+		return packageClauseFor(f.PackageName) + f.SourceContent
+	}
+	return f.SourceContent
 }
 
 // Import represents an import statement
@@ -43,11 +64,18 @@ type FailureReason struct {
 
 // GenerationResult represents the result of generating implementation for a target
 type GenerationResult struct {
-	Target         *Target        // The target function that was processed
-	Success        bool           // Whether generation succeeded
-	Implementation string         // Generated implementation code (when Success=true)
-	FailureReason  *FailureReason // Detailed failure information (when Success=false)
-	Duration       time.Duration  // Time taken for generation
+	Target           *Target        // The target function that was processed
+	Success          bool           // Whether generation succeeded
+	Implementation   string         // Generated implementation code (when Success=true)
+	Imports          []string       // Import paths declared by the AI (when Success=true)
+	FailureReason    *FailureReason // Detailed failure information (when Success=false)
+	Duration         time.Duration  // Time taken for generation
+	Model            string         // Model used for generation
+	PromptTokens     int            // Prompt tokens consumed across all phases
+	CompletionTokens int            // Completion tokens consumed across all phases
+	Requests         int            // LLM requests sent across all phases
+	RateLimited      int            // LLM requests rejected by rate limiting
+	RequestTime      time.Duration  // Time spent waiting for LLM responses
 }
 
 // Target represents a function or method to generate
@@ -57,9 +85,12 @@ type Target struct {
 	Params      []Param        // Function parameters
 	Returns     []Return       // Return values
 	Instruction string         // Content from // mantra: comment
+	Options     *TargetOptions // Inline overrides from // mantra[key=value]: (nil if none)
+	Test        bool           // Whether to generate a table-driven test (// mantra:test)
+	Closure     bool           // Whether the target is a function literal assigned to a var
 	FilePath    string         // Source file path
 	HasPanic    bool           // Whether function contains panic("not implemented")
-	FuncDecl    *ast.FuncDecl  // AST node for the function declaration
+	FuncDecl    *ast.FuncDecl  // AST node for the function declaration (synthesized for closures)
 	TokenSet    *token.FileSet // Token file set for position information
 	// Generation result fields (set during processing)
 	Implementation   string         // Generated implementation (temporary storage)
@@ -67,6 +98,13 @@ type Target struct {
 	FailureReason    *FailureReason // Detailed failure information (when GenerationFailed=true)
 }
 
+// TargetOptions holds per-target overrides given inline, e.g.
+// "// mantra[temperature=0, model=gpt-4o]: instruction"
+type TargetOptions struct {
+	Model       string   // Model to use instead of the configured one
+	Temperature *float32 // Temperature to use for all phases (nil if not set)
+}
+
 // Receiver represents method receiver
 type Receiver struct {
 	Name string // Variable name (e.g., "r", "s")
@@ -84,25 +122,55 @@ type Return struct {
 	Type string // Return type
 }
 
-// ParseFileInfo parses a Go file and returns comprehensive file information
+// ParseFileInfo parses a Go file with the default instruction prefix
 func ParseFileInfo(filePath string) (*FileInfo, error) {
+	return ParseFileInfoWithPrefix(filePath, "")
+}
+
+// ParseFileInfoWithPrefix parses a Go file and returns comprehensive file information.
+// Instruction comments start with prefix, e.g. "// ai:"; an empty prefix uses
+// DefaultInstructionPrefix.
+func ParseFileInfoWithPrefix(filePath, prefix string) (*FileInfo, error) {
+	marker := directiveMarker(prefix)
+
 	// Read source file content
 	sourceContent, err := os.ReadFile(filePath)
 	if err != nil {
 		return nil, fmt.Errorf("failed to read file: %w", err)
 	}
 
+	// Work on LF content so that line-based processing is not affected by \r
+	crlf := HasCRLF(string(sourceContent))
+	content := NormalizeLineEndings(string(sourceContent))
+
+	// Fragments without a package clause get one inferred from the directory.
+	// The clause is only added for parsing, positions refer to the file as is.
+	var clause string
+	if !hasPackageClause(content) {
+		packageName := inferPackageName(filePath)
+		slog.Warn("no package clause found, inferring package from directory",
+			slog.String("file", filepath.Base(filePath)),
+			slog.String("package", packageName))
+		clause = packageClauseFor(packageName)
+	}
+
 	fset := token.NewFileSet()
-	node, err := parser.ParseFile(fset, filePath, sourceContent, parser.ParseComments)
+	node, err := parser.ParseFile(fset, filePath, clause+content, parser.ParseComments)
 	if err != nil {
-		return nil, fmt.Errorf("failed to parse file: %w", err)
+		return nil, newParseError(filePath, node, err, marker, clause)
+	}
+	if clause != "" {
+		fset = skipClauseFileSet(filePath, clause, content)
 	}
 
 	fileInfo := &FileInfo{
 		PackageName:   node.Name.Name,
 		FilePath:      filePath,
-		SourceContent: string(sourceContent),
-		SourceLines:   strings.Split(string(sourceContent), "\n"),
+		SourceContent: content,
+		SourceLines:   strings.Split(content, "\n"),
+		CRLF:          crlf,
+
+		InferredPackage: clause != "",
 	}
 
 	// Parse imports
@@ -116,16 +184,139 @@ func ParseFileInfo(filePath string) (*FileInfo, error) {
 		fileInfo.Imports = append(fileInfo.Imports, importInfo)
 	}
 
+	// Instructions may also come from a sidecar file
+	sidecar, err := loadSidecar(filePath)
+	if err != nil {
+		return nil, err
+	}
+
 	// Parse targets using existing logic
-	targets, err := parseTargetsFromNode(node, fset, filePath)
+	targets, skipped, err := parseTargetsFromNode(node, fset, filePath, sidecar, marker)
 	if err != nil {
 		return nil, err
 	}
 	fileInfo.Targets = targets
+	fileInfo.Skipped = skipped
 
 	return fileInfo, nil
 }
 
+// PackageClause returns the package name of content, a Go file at filePath.
+// Fragments without a package clause get the name inferred from the
+// directory of filePath, which is reported by inferred.
+func PackageClause(filePath string, content []byte) (name string, inferred bool, err error) {
+	if !hasPackageClause(string(content)) {
+		return inferPackageName(filePath), true, nil
+	}
+
+	file, err := parser.ParseFile(token.NewFileSet(), filePath, content, parser.PackageClauseOnly)
+	if err != nil {
+		return "", false, err
+	}
+	return file.Name.Name, false, nil
+}
+
+// packageClauseFor returns the package clause added to fragments of package name
+func packageClauseFor(name string) string {
+	return fmt.Sprintf("package %s\n\n", name)
+}
+
+// skipClauseFileSet returns a file set that resolves the positions of a parse of
+// clause+content, started on a new file set, to content. The positions of the
+// clause fall into a placeholder file.
+func skipClauseFileSet(filePath, clause, content string) *token.FileSet {
+	fset := token.NewFileSet()
+	fset.AddFile("", -1, len(clause)-1) // The next file starts after len(clause) positions
+	file := fset.AddFile(filePath, -1, len(content))
+	file.SetLinesForContent([]byte(content))
+	return fset
+}
+
+// hasPackageClause reports whether the first token of the source is "package"
+func hasPackageClause(content string) bool {
+	fset := token.NewFileSet()
+	file := fset.AddFile("", fset.Base(), len(content))
+
+	var s scanner.Scanner
+	s.Init(file, []byte(content), nil, 0) // Comments are skipped
+	_, tok, _ := s.Scan()
+	return tok == token.PACKAGE
+}
+
+// inferPackageName derives a package name from the directory of filePath
+func inferPackageName(filePath string) string {
+	dir := filepath.Base(filepath.Dir(filePath))
+	if absPath, err := filepath.Abs(filePath); err == nil {
+		dir = filepath.Base(filepath.Dir(absPath))
+	}
+
+	name := strings.Map(func(r rune) rune {
+		if unicode.IsLetter(r) || unicode.IsDigit(r) || r == '_' {
+			return unicode.ToLower(r)
+		}
+		return '_'
+	}, dir)
+
+	if name == "" || name == "_" || unicode.IsDigit(rune(name[0])) || token.IsKeyword(name) {
+		return "main"
+	}
+	return name
+}
+
+// ParseError describes a syntax error that prevented a file from being processed.
+// All mantra targets in the file are skipped until the error is fixed.
+type ParseError struct {
+	FilePath            string
+	Offset              int      // Byte offset of the first error
+	Line                int      // Line of the first error
+	Column              int      // Column of the first error
+	Message             string   // Message of the first error
+	SkippedInstructions []string // Mantra instructions found in the file
+	Err                 error    // Underlying parser error
+}
+
+func (e *ParseError) Error() string {
+	msg := fmt.Sprintf("syntax error at line %d:%d (offset %d): %s", e.Line, e.Column, e.Offset, e.Message)
+	if len(e.SkippedInstructions) > 0 {
+		msg += fmt.Sprintf(" (%d mantra targets skipped)", len(e.SkippedInstructions))
+	}
+	return msg
+}
+
+func (e *ParseError) Unwrap() error {
+	return e.Err
+}
+
+// newParseError builds a ParseError from a parser error and the partial AST.
+// clause is the package clause added to a fragment before parsing, if any.
+func newParseError(filePath string, node *ast.File, err error, marker, clause string) *ParseError {
+	parseErr := &ParseError{
+		FilePath: filePath,
+		Message:  err.Error(),
+		Err:      err,
+	}
+
+	if list, ok := err.(scanner.ErrorList); ok && len(list) > 0 {
+		parseErr.Offset = list[0].Pos.Offset - len(clause)
+		parseErr.Line = list[0].Pos.Line - strings.Count(clause, "\n")
+		parseErr.Column = list[0].Pos.Column
+		parseErr.Message = list[0].Msg
+	}
+
+	// The partial AST still carries all comments, including mantra instructions
+	if node != nil {
+		for _, commentGroup := range node.Comments {
+			for _, comment := range commentGroup.List {
+				if instruction, _, ok := parseMantraDirective(strings.TrimSpace(comment.Text), marker); ok {
+					parseErr.SkippedInstructions = append(parseErr.SkippedInstructions, instruction)
+				}
+			}
+		}
+	}
+
+	return parseErr
+}
+
 // ParseFile parses a Go file and returns all generation targets (backwards compatibility)
 func ParseFile(filePath string) ([]*Target, error) {
 	fileInfo, err := ParseFileInfo(filePath)
@@ -135,23 +326,46 @@ func ParseFile(filePath string) ([]*Target, error) {
 	return fileInfo.Targets, nil
 }
 
-// parseTargetsFromNode extracts targets from parsed AST node
-func parseTargetsFromNode(node *ast.File, fset *token.FileSet, filePath string) ([]*Target, error) {
+// parseTargetsFromNode extracts targets from parsed AST node.
+// Functions without an inline comment take their instruction from sidecar, if any.
+// Targets disabled with // mantra:skip are returned by name separately.
+// Directives start with marker, e.g. "// mantra".
+func parseTargetsFromNode(node *ast.File, fset *token.FileSet, filePath string, sidecar map[string]string, marker string) ([]*Target, []string, error) {
 	var targets []*Target
+	var skipped []string
+
+	// mantraComment holds a parsed // mantra: comment group
+	type mantraComment struct {
+		instruction string
+		options     *TargetOptions
+		test        bool
+		skip        bool
+	}
 
 	// Map to store mantra comments by position
-	mantraComments := make(map[token.Pos]string)
+	mantraComments := make(map[token.Pos]mantraComment)
 
 	// First pass: collect all // mantra: comments
 	for _, commentGroup := range node.Comments {
 		var mantraInstruction strings.Builder
+		var options *TargetOptions
 		foundMantra := false
+		isTest := false
+		skip := false
 
 		for _, comment := range commentGroup.List {
 			text := strings.TrimSpace(comment.Text)
-			if strings.HasPrefix(text, "// mantra:") {
+			if isSkipDirective(text, marker) {
+				skip = true
+			} else if instruction, ok := parseTestDirective(text, marker); ok {
+				foundMantra = true
+				isTest = true
+				mantraInstruction.WriteString(instruction)
+			} else if instruction, opts, ok := parseMantraDirective(text, marker); ok {
 				foundMantra = true
-				instruction := strings.TrimSpace(strings.TrimPrefix(text, "// mantra:"))
+				if opts != nil {
+					options = opts
+				}
 				mantraInstruction.WriteString(instruction)
 			} else if foundMantra && strings.HasPrefix(text, "//") {
 				// Continuation of mantra comment
@@ -165,101 +379,266 @@ func parseTargetsFromNode(node *ast.File, fset *token.FileSet, filePath string)
 
 		if foundMantra {
 			// Store comment with its end position
-			mantraComments[commentGroup.End()] = mantraInstruction.String()
+			mantraComments[commentGroup.End()] = mantraComment{
+				instruction: mantraInstruction.String(),
+				options:     options,
+				test:        isTest,
+				skip:        skip,
+			}
 		}
 	}
 
-	// Second pass: find functions with mantra comments
-	ast.Inspect(node, func(n ast.Node) bool {
-		switch x := n.(type) {
+	// A mantra comment must follow the end of the preceding declaration,
+	// so that short functions do not pick up the comment of their predecessor
+	precedingEnd := make(map[ast.Decl]token.Pos)
+	for i := 1; i < len(node.Decls); i++ {
+		precedingEnd[node.Decls[i]] = node.Decls[i-1].End()
+	}
+
+	// Second pass: find functions and closures with mantra comments
+	usedSidecarKeys := make(map[string]bool)
+	for _, decl := range node.Decls {
+		var funcDecl *ast.FuncDecl
+		closure := false
+		switch x := decl.(type) {
 		case *ast.FuncDecl:
-			// Check if there's a mantra comment immediately before this function
-			var instruction string
-			var found bool
-
-			// Look for mantra comment right before function
-			for pos, instr := range mantraComments {
-				if pos < x.Pos() && x.Pos()-pos < maxCommentGap {
-					instruction = instr
-					found = true
-					break
-				}
+			funcDecl = x
+		case *ast.GenDecl:
+			// var name = func(...) { ... }
+			name, lit := ClosureLiteral(x)
+			if lit == nil {
+				continue
 			}
+			funcDecl = &ast.FuncDecl{Doc: x.Doc, Name: name, Type: lit.Type, Body: lit.Body}
+			closure = true
+		default:
+			continue
+		}
+
+		// Look for mantra comment right before the declaration
+		var comment mantraComment
+		var found bool
+		for pos, c := range mantraComments {
+			if pos < decl.Pos() && decl.Pos()-pos < maxCommentGap && pos > precedingEnd[decl] {
+				comment = c
+				found = true
+				break
+			}
+		}
 
+		// Inline comments take precedence over the sidecar
+		key := sidecarKey(funcDecl.Name.Name, receiverOf(funcDecl))
+		if instruction, ok := sidecar[key]; ok {
+			usedSidecarKeys[key] = true
 			if !found {
-				return true
+				comment = mantraComment{instruction: instruction}
+				found = true
 			}
+		}
 
-			// Check if function contains panic("not implemented")
-			hasPanic := containsNotImplementedPanic(x.Body)
+		if !found {
+			continue
+		}
 
-			target := &Target{
-				Name:        x.Name.Name,
-				Instruction: instruction,
-				FilePath:    filePath,
-				HasPanic:    hasPanic,
-				FuncDecl:    x,
-				TokenSet:    fset,
-			}
+		if comment.skip {
+			skipped = append(skipped, funcDecl.Name.Name)
+			continue
+		}
 
-			// Parse receiver for methods
-			if x.Recv != nil && len(x.Recv.List) > 0 {
-				recv := x.Recv.List[0]
-				target.Receiver = &Receiver{
-					Type: analysis.ExtractTypeString(recv.Type),
-				}
-				if len(recv.Names) > 0 {
-					target.Receiver.Name = recv.Names[0].Name
-				}
-			}
+		// Check if function contains panic("not implemented")
+		hasPanic := containsNotImplementedPanic(funcDecl.Body)
+
+		target := &Target{
+			Name:        funcDecl.Name.Name,
+			Instruction: comment.instruction,
+			Options:     comment.options,
+			Test:        comment.test,
+			Closure:     closure,
+			FilePath:    filePath,
+			HasPanic:    hasPanic,
+			FuncDecl:    funcDecl,
+			TokenSet:    fset,
+		}
 
-			// Parse parameters
-			if x.Type.Params != nil {
-				for _, field := range x.Type.Params.List {
-					paramType := analysis.ExtractTypeString(field.Type)
-					if len(field.Names) == 0 {
-						// Unnamed parameter
+		// Parse receiver for methods
+		target.Receiver = receiverOf(funcDecl)
+
+		// Parse parameters
+		if funcDecl.Type.Params != nil {
+			for _, field := range funcDecl.Type.Params.List {
+				paramType := analysis.ExtractTypeString(field.Type)
+				if len(field.Names) == 0 {
+					// Unnamed parameter
+					target.Params = append(target.Params, Param{
+						Type: paramType,
+					})
+				} else {
+					// Named parameters
+					for _, name := range field.Names {
 						target.Params = append(target.Params, Param{
+							Name: name.Name,
 							Type: paramType,
 						})
-					} else {
-						// Named parameters
-						for _, name := range field.Names {
-							target.Params = append(target.Params, Param{
-								Name: name.Name,
-								Type: paramType,
-							})
-						}
 					}
 				}
 			}
+		}
 
-			// Parse return values
-			if x.Type.Results != nil {
-				for _, field := range x.Type.Results.List {
-					retType := analysis.ExtractTypeString(field.Type)
-					// Return values can have multiple types in one field
-					if len(field.Names) == 0 {
+		// Parse return values
+		if funcDecl.Type.Results != nil {
+			for _, field := range funcDecl.Type.Results.List {
+				retType := analysis.ExtractTypeString(field.Type)
+				// Return values can have multiple types in one field
+				if len(field.Names) == 0 {
+					target.Returns = append(target.Returns, Return{
+						Type: retType,
+					})
+				} else {
+					// Named returns (rare but possible)
+					for range field.Names {
 						target.Returns = append(target.Returns, Return{
 							Type: retType,
 						})
-					} else {
-						// Named returns (rare but possible)
-						for range field.Names {
-							target.Returns = append(target.Returns, Return{
-								Type: retType,
-							})
-						}
 					}
 				}
 			}
+		}
+
+		targets = append(targets, target)
+	}
+
+	warnUnusedSidecarKeys(filePath, sidecar, usedSidecarKeys)
+
+	return targets, skipped, nil
+}
+
+// receiverOf returns the receiver of a method, or nil for functions
+func receiverOf(funcDecl *ast.FuncDecl) *Receiver {
+	if funcDecl.Recv == nil || len(funcDecl.Recv.List) == 0 {
+		return nil
+	}
+	recv := funcDecl.Recv.List[0]
+	receiver := &Receiver{
+		Type: analysis.ExtractTypeString(recv.Type),
+	}
+	if len(recv.Names) > 0 {
+		receiver.Name = recv.Names[0].Name
+	}
+	return receiver
+}
+
+// DeclDisplayName returns the name of a function declaration as shown by
+// Target.GetDisplayName, e.g. "(*Repo).Get" for methods
+func DeclDisplayName(funcDecl *ast.FuncDecl) string {
+	target := &Target{Name: funcDecl.Name.Name, Receiver: receiverOf(funcDecl)}
+	return target.GetDisplayName()
+}
+
+// ClosureLiteral returns the name and function literal of a declaration of
+// the form "var name = func(...) { ... }", or nil if decl has another form
+func ClosureLiteral(decl *ast.GenDecl) (*ast.Ident, *ast.FuncLit) {
+	if decl.Tok != token.VAR || len(decl.Specs) != 1 {
+		return nil, nil
+	}
+	spec, ok := decl.Specs[0].(*ast.ValueSpec)
+	if !ok || len(spec.Names) != 1 || len(spec.Values) != 1 {
+		return nil, nil
+	}
+	lit, ok := spec.Values[0].(*ast.FuncLit)
+	if !ok {
+		return nil, nil
+	}
+	return spec.Names[0], lit
+}
+
+// DefaultInstructionPrefix marks instruction comments unless another prefix is configured
+const DefaultInstructionPrefix = "// mantra:"
+
+// directiveMarker returns the instruction prefix without its colon, e.g.
+// "// ai" for "// ai:". Option blocks, skip and test directives use the same
+// marker ("// ai[...]:", "// ai:skip", "// ai:test"). An empty prefix uses the default.
+func directiveMarker(prefix string) string {
+	if prefix == "" {
+		prefix = DefaultInstructionPrefix
+	}
+	return strings.TrimSuffix(prefix, ":")
+}
+
+// parseMantraDirective parses the first line of a mantra comment.
+// It accepts both "// mantra: instruction" and "// mantra[key=value,...]: instruction"
+// and returns the instruction text with any option block stripped.
+func parseMantraDirective(text, marker string) (string, *TargetOptions, bool) {
+	if rest, ok := strings.CutPrefix(text, marker+":"); ok {
+		return strings.TrimSpace(rest), nil, true
+	}
 
-			targets = append(targets, target)
+	rest, ok := strings.CutPrefix(text, marker+"[")
+	if !ok {
+		return "", nil, false
+	}
+
+	end := strings.Index(rest, "]:")
+	if end == -1 {
+		return "", nil, false
+	}
+
+	options := parseTargetOptions(rest[:end])
+	return strings.TrimSpace(rest[end+2:]), options, true
+}
+
+// isSkipDirective reports whether a comment line is "// mantra:skip", which
+// disables the target of the comment group without removing its instruction
+func isSkipDirective(text, marker string) bool {
+	rest, ok := strings.CutPrefix(text, marker+":skip")
+	return ok && (rest == "" || rest[0] == ' ')
+}
+
+// parseTestDirective parses a "// mantra:test" comment and returns its instruction,
+// which names the function to test, e.g. "// mantra:test Add with negative numbers"
+func parseTestDirective(text, marker string) (string, bool) {
+	rest, ok := strings.CutPrefix(text, marker+":test")
+	if !ok || (rest != "" && rest[0] != ' ' && rest[0] != ':') {
+		return "", false
+	}
+	return strings.TrimSpace(strings.TrimPrefix(rest, ":")), true
+}
+
+// parseTargetOptions parses a comma separated key=value list.
+// Unknown keys and invalid values are reported as warnings and ignored.
+func parseTargetOptions(spec string) *TargetOptions {
+	options := &TargetOptions{}
+
+	for _, pair := range strings.Split(spec, ",") {
+		pair = strings.TrimSpace(pair)
+		if pair == "" {
+			continue
 		}
-		return true
-	})
 
-	return targets, nil
+		key, value, ok := strings.Cut(pair, "=")
+		if !ok {
+			slog.Warn("ignoring malformed mantra option", slog.String("option", pair))
+			continue
+		}
+		key = strings.TrimSpace(key)
+		value = strings.TrimSpace(value)
+
+		switch key {
+		case "model":
+			options.Model = value
+		case "temperature":
+			temperature, err := strconv.ParseFloat(value, 32)
+			if err != nil {
+				slog.Warn("ignoring invalid mantra temperature", slog.String("value", value))
+				continue
+			}
+			t := float32(temperature)
+			options.Temperature = &t
+		default:
+			slog.Warn("ignoring unknown mantra option", slog.String("key", key))
+		}
+	}
+
+	return options
 }
 
 // containsNotImplementedPanic checks if function body contains panic("not implemented")
@@ -292,7 +671,11 @@ func containsNotImplementedPanic(body *ast.BlockStmt) bool {
 func (t *Target) GetFunctionSignature() string {
 	var sig strings.Builder
 
-	sig.WriteString("func ")
+	if t.Closure {
+		sig.WriteString("var " + t.Name + " = func")
+	} else {
+		sig.WriteString("func ")
+	}
 
 	// Add receiver if it's a method
 	if t.Receiver != nil {
@@ -305,7 +688,9 @@ func (t *Target) GetFunctionSignature() string {
 		sig.WriteString(") ")
 	}
 
-	sig.WriteString(t.Name)
+	if !t.Closure {
+		sig.WriteString(t.Name)
+	}
 	sig.WriteString("(")
 
 	// Add parameters
@@ -342,6 +727,36 @@ func (t *Target) GetFunctionSignature() string {
 	return sig.String()
 }
 
+// GetParameterSummary lists the parameters and return types one by one, e.g.
+// "Parameters: a int, b int; Returns: int". Grouped parameters such as
+// "a, b int" are spelled out. Empty for functions without both.
+func (t *Target) GetParameterSummary() string {
+	var parts []string
+
+	if len(t.Params) > 0 {
+		params := make([]string, len(t.Params))
+		for i, param := range t.Params {
+			params[i] = strings.TrimSpace(param.Name + " " + param.Type)
+		}
+		parts = append(parts, "Parameters: "+strings.Join(params, ", "))
+	}
+
+	if len(t.Returns) > 0 {
+		returns := make([]string, len(t.Returns))
+		for i, ret := range t.Returns {
+			returns[i] = ret.Type
+		}
+		parts = append(parts, "Returns: "+strings.Join(returns, ", "))
+	}
+
+	return strings.Join(parts, "; ")
+}
+
+// InTestFile reports whether the target is declared in a _test.go file
+func (t *Target) InTestFile() bool {
+	return strings.HasSuffix(t.FilePath, "_test.go")
+}
+
 // GetDisplayName returns a display name for the target (e.g., "(*Repository).GetUser" for methods)
 func (t *Target) GetDisplayName() string {
 	if t.Receiver != nil {
@@ -1,8 +1,11 @@
 package parser
 
 import (
+	"errors"
 	"os"
 	"path/filepath"
+	"reflect"
+	"strings"
 	"testing"
 )
 
@@ -239,3 +242,660 @@ func TestGetFunctionSignature(t *testing.T) {
 		})
 	}
 }
+
+func TestParseTargetOptions(t *testing.T) {
+	tempDir := t.TempDir()
+	testFile := filepath.Join(tempDir, "options.go")
+
+	testContent := `package test
+
+// mantra[temperature=0, model=gpt-4o, unknown=1]: compute the checksum
+// over all bytes
+func Checksum(data []byte) uint32 {
+	panic("not implemented")
+}
+
+// mantra: plain instruction
+func Plain() {
+	panic("not implemented")
+}
+`
+
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	targets, err := ParseFile(testFile)
+	if err != nil {
+		t.Fatalf("ParseFile failed: %v", err)
+	}
+
+	if len(targets) != 2 {
+		t.Fatalf("Expected 2 targets, got %d", len(targets))
+	}
+
+	checksum := targets[0]
+	if checksum.Instruction != "compute the checksum\nover all bytes" {
+		t.Errorf("Unexpected instruction: %q", checksum.Instruction)
+	}
+	if checksum.Options == nil {
+		t.Fatal("Expected options to be parsed")
+	}
+	if checksum.Options.Model != "gpt-4o" {
+		t.Errorf("Expected model gpt-4o, got %q", checksum.Options.Model)
+	}
+	if checksum.Options.Temperature == nil || *checksum.Options.Temperature != 0 {
+		t.Errorf("Expected temperature 0, got %v", checksum.Options.Temperature)
+	}
+
+	plain := targets[1]
+	if plain.Options != nil {
+		t.Errorf("Expected no options for plain instruction, got %+v", plain.Options)
+	}
+	if plain.Instruction != "plain instruction" {
+		t.Errorf("Unexpected instruction: %q", plain.Instruction)
+	}
+}
+
+func TestParseTestDirective(t *testing.T) {
+	tempDir := t.TempDir()
+	testFile := filepath.Join(tempDir, "math_test.go")
+
+	testContent := `package math
+
+import "testing"
+
+// mantra:test Add including negative numbers
+func TestAdd(t *testing.T) {
+	panic("not implemented")
+}
+
+// mantra: regular instruction
+func helper() int {
+	panic("not implemented")
+}
+`
+
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	targets, err := ParseFile(testFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(targets) != 2 {
+		t.Fatalf("Expected 2 targets, got %d", len(targets))
+	}
+
+	for _, target := range targets {
+		switch target.Name {
+		case "TestAdd":
+			if !target.Test {
+				t.Error("Expected TestAdd to be a test target")
+			}
+			if target.Instruction != "Add including negative numbers" {
+				t.Errorf("Unexpected instruction: %q", target.Instruction)
+			}
+		case "helper":
+			if target.Test {
+				t.Error("Expected helper not to be a test target")
+			}
+		}
+	}
+
+	for _, text := range []string{"// mantra:testing", "// mantra: test Add"} {
+		if _, ok := parseTestDirective(text, directiveMarker("")); ok {
+			t.Errorf("Expected %q not to be a test directive", text)
+		}
+	}
+}
+
+func TestParseFileInfo_MissingPackageClause(t *testing.T) {
+	dir := filepath.Join(t.TempDir(), "my-fragments")
+	if err := os.Mkdir(dir, 0755); err != nil {
+		t.Fatal(err)
+	}
+	testFile := filepath.Join(dir, "snippet.go")
+
+	testContent := `// A snippet without package clause
+
+// mantra: add two numbers
+func Add(a, b int) int {
+	panic("not implemented")
+}
+`
+
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := ParseFileInfo(testFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	if !fileInfo.InferredPackage {
+		t.Error("Expected package to be inferred")
+	}
+	if fileInfo.PackageName != "my_fragments" {
+		t.Errorf("Expected package my_fragments, got %q", fileInfo.PackageName)
+	}
+	if len(fileInfo.Targets) != 1 || fileInfo.Targets[0].Name != "Add" {
+		t.Fatalf("Expected target Add, got %+v", fileInfo.Targets)
+	}
+
+	// Content and positions refer to the file as is
+	if fileInfo.SourceContent != testContent {
+		t.Errorf("Expected the source content unchanged, got %q", fileInfo.SourceContent)
+	}
+	target := fileInfo.Targets[0]
+	position := target.TokenSet.Position(target.FuncDecl.Pos())
+	if position.Line != 4 || position.Offset != strings.Index(testContent, "func Add") {
+		t.Errorf("Expected Add at line 4, offset %d, got %+v", strings.Index(testContent, "func Add"), position)
+	}
+	if !strings.HasPrefix(fileInfo.ParsableContent(), "package my_fragments\n") {
+		t.Errorf("Expected the parsable content to start with the package clause, got %q", fileInfo.ParsableContent())
+	}
+}
+
+func TestPackageClause(t *testing.T) {
+	dir := filepath.Join(t.TempDir(), "my-fragments")
+	tests := []struct {
+		name         string
+		content      string
+		expected     string
+		wantInferred bool
+		wantErr      bool
+	}{
+		{name: "package clause", content: "// Package shapes\npackage shapes\n", expected: "shapes"},
+		{name: "fragment", content: "func Add(a, b int) int {\n\treturn a + b\n}\n", expected: "my_fragments", wantInferred: true},
+		{name: "broken package clause", content: "package 1\n", wantErr: true},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			name, inferred, err := PackageClause(filepath.Join(dir, "stdin.go"), []byte(tt.content))
+			if (err != nil) != tt.wantErr {
+				t.Fatalf("PackageClause() error = %v, wantErr %v", err, tt.wantErr)
+			}
+			if name != tt.expected || inferred != tt.wantInferred {
+				t.Errorf("PackageClause() = %q, %v, expected %q, %v", name, inferred, tt.expected, tt.wantInferred)
+			}
+		})
+	}
+}
+
+func TestGetParameterSummary(t *testing.T) {
+	testFile := filepath.Join(t.TempDir(), "math.go")
+	testContent := `package math
+
+// mantra: add a and b
+func Add(a, b int) int {
+	panic("not implemented")
+}
+
+// mantra: write the sum to w
+func Write(w io.Writer, _ string, values ...int) (n int, err error) {
+	panic("not implemented")
+}
+
+// mantra: do nothing
+func Noop() {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	targets, err := ParseFile(testFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	expected := []string{
+		"Parameters: a int, b int; Returns: int",
+		"Parameters: w io.Writer, _ string, values ...int; Returns: int, error",
+		"",
+	}
+	if len(targets) != len(expected) {
+		t.Fatalf("Expected %d targets, got %d", len(expected), len(targets))
+	}
+	for i, target := range targets {
+		if got := target.GetParameterSummary(); got != expected[i] {
+			t.Errorf("%s: expected %q, got %q", target.Name, expected[i], got)
+		}
+	}
+}
+
+func TestParseFileInfo_GenericReceiver(t *testing.T) {
+	testFile := filepath.Join(t.TempDir(), "stack.go")
+	testContent := `package stack
+
+type Stack[T any] struct {
+	items []T
+}
+
+// mantra: push the item on top of the stack
+func (s *Stack[T]) Push(item T) {
+	panic("not implemented")
+}
+
+type Pair[K comparable, V any] struct {
+	Key   K
+	Value V
+}
+
+// mantra: swap key and value
+func (p Pair[K, V]) Swap() Pair[V, K] {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := ParseFileInfo(testFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(fileInfo.Targets) != 2 {
+		t.Fatalf("Expected 2 targets, got %d", len(fileInfo.Targets))
+	}
+
+	if got := fileInfo.Targets[0].Receiver.Type; got != "*Stack[T]" {
+		t.Errorf("Expected receiver type *Stack[T], got %s", got)
+	}
+	if got := fileInfo.Targets[1].GetFunctionSignature(); got != "func (p Pair[K, V]) Swap() Pair[V, K]" {
+		t.Errorf("Unexpected signature %s", got)
+	}
+}
+
+func TestParseFileInfo_Sidecar(t *testing.T) {
+	testFile := filepath.Join(t.TempDir(), "user.go")
+	testContent := `package user
+
+type Repository struct{}
+
+func GetUser(id string) string {
+	panic("not implemented")
+}
+
+// mantra: inline instruction
+func (r *Repository) Save(name string) error {
+	panic("not implemented")
+}
+
+func Helper() {}
+`
+	sidecarContent := `GetUser = "fetch the user by ID"
+"Repository.Save" = "sidecar instruction"
+`
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(testFile+SidecarSuffix, []byte(sidecarContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := ParseFileInfo(testFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	instructions := make(map[string]string)
+	for _, target := range fileInfo.Targets {
+		instructions[target.GetDisplayName()] = target.Instruction
+	}
+	want := map[string]string{
+		"GetUser":            "fetch the user by ID",
+		"(*Repository).Save": "inline instruction",
+	}
+	if !reflect.DeepEqual(instructions, want) {
+		t.Errorf("Expected %v, got %v", want, instructions)
+	}
+}
+
+func TestParseFileInfo_Skip(t *testing.T) {
+	testFile := filepath.Join(t.TempDir(), "skip.go")
+	testContent := `package skip
+
+// mantra: add two numbers
+// mantra:skip
+func Add(a, b int) int {
+	panic("not implemented")
+}
+
+// mantra:skip until the API is settled
+// mantra: subtract b from a
+func Sub(a, b int) int {
+	panic("not implemented")
+}
+
+// mantra: multiply two numbers
+func Mul(a, b int) int {
+	panic("not implemented")
+}
+
+// mantra: skipped numbers are ignored
+func Filter(numbers []int) []int {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := ParseFileInfo(testFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	var names []string
+	for _, target := range fileInfo.Targets {
+		names = append(names, target.Name)
+	}
+	if !reflect.DeepEqual(names, []string{"Mul", "Filter"}) {
+		t.Errorf("Expected targets [Mul Filter], got %v", names)
+	}
+	if !reflect.DeepEqual(fileInfo.Skipped, []string{"Add", "Sub"}) {
+		t.Errorf("Expected skipped [Add Sub], got %v", fileInfo.Skipped)
+	}
+}
+
+func TestParseFileInfo_InstructionPrefix(t *testing.T) {
+	testFile := filepath.Join(t.TempDir(), "prefix.go")
+	testContent := `package prefix
+
+// ai: add two numbers
+func Add(a, b int) int {
+	panic("not implemented")
+}
+
+// ai[temperature=0]: subtract b from a
+func Sub(a, b int) int {
+	panic("not implemented")
+}
+
+// ai: multiply two numbers
+// ai:skip
+func Mul(a, b int) int {
+	panic("not implemented")
+}
+
+// mantra: not an instruction with a custom prefix
+func Div(a, b int) int {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	fileInfo, err := ParseFileInfoWithPrefix(testFile, "// ai:")
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	if len(fileInfo.Targets) != 2 || fileInfo.Targets[0].Name != "Add" || fileInfo.Targets[1].Name != "Sub" {
+		t.Fatalf("Expected targets Add and Sub, got %+v", fileInfo.Targets)
+	}
+	if fileInfo.Targets[0].Instruction != "add two numbers" {
+		t.Errorf("Expected instruction without prefix, got %q", fileInfo.Targets[0].Instruction)
+	}
+	if opts := fileInfo.Targets[1].Options; opts == nil || opts.Temperature == nil || *opts.Temperature != 0 {
+		t.Errorf("Expected temperature option for Sub, got %+v", opts)
+	}
+	if !reflect.DeepEqual(fileInfo.Skipped, []string{"Mul"}) {
+		t.Errorf("Expected Mul to be skipped, got %v", fileInfo.Skipped)
+	}
+
+	// The prefix only applies to the parse it is passed to
+	defaultInfo, err := ParseFileInfo(testFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(defaultInfo.Targets) != 1 || defaultInfo.Targets[0].Name != "Div" {
+		t.Errorf("Expected only Div with the default prefix, got %+v", defaultInfo.Targets)
+	}
+}
+
+func TestParseFileInfo_SyntaxError(t *testing.T) {
+	tempDir := t.TempDir()
+	testFile := filepath.Join(tempDir, "broken.go")
+
+	testContent := `package broken
+
+// mantra: add two numbers
+func Add(a, b int) int {
+	panic("not implemented")
+}
+
+func Broken( {
+}
+
+// mantra: multiply two numbers
+func Mul(a, b int) int {
+	panic("not implemented")
+}
+`
+
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	_, err := ParseFileInfo(testFile)
+	if err == nil {
+		t.Fatal("Expected syntax error")
+	}
+
+	var parseErr *ParseError
+	if !errors.As(err, &parseErr) {
+		t.Fatalf("Expected *ParseError, got %T", err)
+	}
+
+	if parseErr.Line != 8 {
+		t.Errorf("Expected error on line 8, got %d", parseErr.Line)
+	}
+	if brokenStart := strings.Index(testContent, "func Broken"); parseErr.Offset < brokenStart {
+		t.Errorf("Expected error offset after %d, got %d", brokenStart, parseErr.Offset)
+	}
+
+	expected := []string{"add two numbers", "multiply two numbers"}
+	if len(parseErr.SkippedInstructions) != len(expected) {
+		t.Fatalf("Expected %d skipped instructions, got %v", len(expected), parseErr.SkippedInstructions)
+	}
+	for i, instruction := range expected {
+		if parseErr.SkippedInstructions[i] != instruction {
+			t.Errorf("Skipped instruction %d: expected %q, got %q", i, instruction, parseErr.SkippedInstructions[i])
+		}
+	}
+}
+
+func TestParseTestFile(t *testing.T) {
+	tempDir := t.TempDir()
+	sourceFile := filepath.Join(tempDir, "math.go")
+	testFile := filepath.Join(tempDir, "math_test.go")
+
+	sourceContent := `package math
+
+// mantra: add two numbers
+func Add(a, b int) int {
+	panic("not implemented")
+}
+
+// mantra: subtract b from a
+func Sub(a, b int) int {
+	panic("not implemented")
+}
+`
+
+	testContent := `package math
+
+import "testing"
+
+func TestAdd(t *testing.T) {
+	if got := Add(1, 2); got != 3 {
+		t.Errorf("Add(1, 2) = %d, want 3", got)
+	}
+}
+
+func TestHelperShape(t *testing.B) {
+	Add(1, 1)
+}
+
+func helper() int {
+	return Add(0, 0)
+}
+`
+
+	if err := os.WriteFile(sourceFile, []byte(sourceContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(testFile, []byte(testContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	tests, err := ParseTestFile(testFile)
+	if err != nil {
+		t.Fatalf("ParseTestFile failed: %v", err)
+	}
+
+	if len(tests) != 1 {
+		t.Fatalf("Expected 1 test function, got %d", len(tests))
+	}
+	if tests[0].FunctionName != "TestAdd" {
+		t.Errorf("Expected TestAdd, got %s", tests[0].FunctionName)
+	}
+	if !strings.Contains(tests[0].Body, "Add(1, 2)") {
+		t.Errorf("Expected body to contain the call, got %q", tests[0].Body)
+	}
+
+	targets, err := ParseFile(sourceFile)
+	if err != nil {
+		t.Fatalf("ParseFile failed: %v", err)
+	}
+
+	for _, target := range targets {
+		found, err := FindTestsForTarget(target)
+		if err != nil {
+			t.Fatalf("FindTestsForTarget failed: %v", err)
+		}
+
+		switch target.Name {
+		case "Add":
+			if len(found) != 1 || found[0].FunctionName != "TestAdd" {
+				t.Errorf("Expected TestAdd for Add, got %+v", found)
+			}
+		case "Sub":
+			if len(found) != 0 {
+				t.Errorf("Expected no tests for Sub, got %+v", found)
+			}
+		}
+	}
+}
+
+func TestFindTestsForTarget_Calls(t *testing.T) {
+	sourceContent := `package cache
+
+type Cache struct{}
+
+// mantra: look up key in the default cache
+func Get(key string) string {
+	panic("not implemented")
+}
+
+// mantra: look up key
+func (c *Cache) Get(key string) string {
+	panic("not implemented")
+}
+`
+
+	tests := []struct {
+		name        string
+		testContent string
+		function    []string // Tests found for the function Get
+		method      []string // Tests found for the method Cache.Get
+	}{
+		{
+			name: "internal test package",
+			testContent: `package cache
+
+import (
+	"net/http"
+	"testing"
+)
+
+func TestGet(t *testing.T) {
+	Get("a")
+}
+
+func TestCacheGet(t *testing.T) {
+	c := &Cache{}
+	c.Get("a")
+}
+
+func TestHTTPGet(t *testing.T) {
+	http.Get("http://localhost")
+}
+`,
+			function: []string{"TestGet"},
+			method:   []string{"TestCacheGet"},
+		},
+		{
+			name: "external test package",
+			testContent: `package cache_test
+
+import (
+	"testing"
+
+	"example.com/cache"
+)
+
+func TestGet(t *testing.T) {
+	cache.Get("a")
+}
+`,
+			function: []string{"TestGet"},
+			method:   nil,
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			tempDir := t.TempDir()
+			sourceFile := filepath.Join(tempDir, "cache.go")
+			if err := os.WriteFile(sourceFile, []byte(sourceContent), 0644); err != nil {
+				t.Fatal(err)
+			}
+			if err := os.WriteFile(filepath.Join(tempDir, "cache_test.go"), []byte(tt.testContent), 0644); err != nil {
+				t.Fatal(err)
+			}
+
+			targets, err := ParseFile(sourceFile)
+			if err != nil {
+				t.Fatal(err)
+			}
+
+			for _, target := range targets {
+				found, err := FindTestsForTarget(target)
+				if err != nil {
+					t.Fatalf("FindTestsForTarget failed: %v", err)
+				}
+				var names []string
+				for _, test := range found {
+					names = append(names, test.FunctionName)
+				}
+
+				expected := tt.function
+				if target.Receiver != nil {
+					expected = tt.method
+				}
+				if !reflect.DeepEqual(names, expected) {
+					t.Errorf("Expected %v for %s, got %v", expected, target.GetDisplayName(), names)
+				}
+			}
+		})
+	}
+}
@@ -0,0 +1,107 @@
+package parser_test
+
+import (
+	"fmt"
+	"math/rand"
+	"os"
+	"path/filepath"
+	"reflect"
+	"strings"
+	"testing"
+	"testing/quick"
+
+	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// goSource is a randomly generated, syntactically valid Go file
+type goSource struct {
+	Content string
+	Targets int // Number of functions with a mantra comment
+}
+
+var (
+	paramTypes  = []string{"int", "string", "[]byte", "*User", "map[string]int", "context.Context"}
+	resultTypes = []string{"", "error", "int", "(string, error)", "(*User, error)"}
+	filler      = []string{"", "// A regular comment\n", "var counter int\n", "type Alias = string\n", "const limit = 10\n"}
+)
+
+// Generate implements quick.Generator using a simplified Go grammar
+func (goSource) Generate(r *rand.Rand, size int) reflect.Value {
+	var b strings.Builder
+	b.WriteString("package generated\n\nimport \"context\"\n\nvar _ context.Context\n\ntype User struct{}\n\n")
+
+	functions := r.Intn(16)
+	targets := 0
+	for i := 0; i < functions; i++ {
+		b.WriteString(filler[r.Intn(len(filler))])
+
+		// At most 10 annotated functions
+		if targets < 10 && r.Intn(2) == 0 {
+			fmt.Fprintf(&b, "// mantra: Implement step %d\n", i)
+			if r.Intn(3) == 0 {
+				b.WriteString("// with an additional line of instruction\n")
+			}
+			targets++
+		}
+
+		receiver := ""
+		if r.Intn(3) == 0 {
+			receiver = "(u *User) "
+		}
+		var params []string
+		paramCount := r.Intn(4)
+		for p := 0; p < paramCount; p++ {
+			params = append(params, fmt.Sprintf("p%d %s", p, paramTypes[r.Intn(len(paramTypes))]))
+		}
+		fmt.Fprintf(&b, "func %sFunc%d(%s) %s {\n\tpanic(\"not implemented\")\n}\n\n",
+			receiver, i, strings.Join(params, ", "), resultTypes[r.Intn(len(resultTypes))])
+	}
+
+	return reflect.ValueOf(goSource{Content: b.String(), Targets: targets})
+}
+
+func TestParseFileInfo_Properties(t *testing.T) {
+	dir := t.TempDir()
+
+	property := func(src goSource) bool {
+		path := filepath.Join(dir, "source.go")
+		if err := os.WriteFile(path, []byte(src.Content), 0644); err != nil {
+			t.Fatal(err)
+		}
+
+		fileInfo, err := parser.ParseFileInfo(path)
+		if err != nil {
+			t.Logf("parse failed: %v\n%s", err, src.Content)
+			return false
+		}
+
+		if len(fileInfo.Targets) != src.Targets {
+			t.Logf("expected %d targets, got %d\n%s", src.Targets, len(fileInfo.Targets), src.Content)
+			return false
+		}
+
+		// A second parse of the same source yields the same checksums
+		reparsed, err := parser.ParseFileInfo(path)
+		if err != nil || len(reparsed.Targets) != len(fileInfo.Targets) {
+			t.Logf("second parse differs: %v\n%s", err, src.Content)
+			return false
+		}
+
+		for i, target := range fileInfo.Targets {
+			if target.GetFunctionSignature() == "" {
+				t.Logf("empty signature for %s", target.Name)
+				return false
+			}
+			if checksum.Calculate(target) != checksum.Calculate(reparsed.Targets[i]) {
+				t.Logf("checksum of %s is not deterministic", target.Name)
+				return false
+			}
+		}
+		return true
+	}
+
+	if err := quick.Check(property, &quick.Config{MaxCount: 200}); err != nil {
+		t.Error(err)
+	}
+}
@@ -0,0 +1,73 @@
+package parser
+
+import (
+	"errors"
+	"fmt"
+	"io/fs"
+	"log/slog"
+	"os"
+	"path/filepath"
+	"sort"
+	"strings"
+
+	"github.com/BurntSushi/toml"
+
+	"github.com/rail44/mantra/internal/analysis"
+)
+
+// SidecarSuffix is appended to the name of a source file to find its
+// instruction sidecar, e.g. "user.go.mantra" for "user.go"
+const SidecarSuffix = ".mantra"
+
+// loadSidecar reads the instructions of the sidecar of filePath, keyed by
+// function name ("Name", or "Type.Name" for methods). The sidecar is a TOML
+// file of string values:
+//
+//	GetUser = "Fetch the user by ID from the repository"
+//	"Repository.Save" = "Insert or update the user"
+//
+// A missing sidecar yields no instructions.
+func loadSidecar(filePath string) (map[string]string, error) {
+	path := filePath + SidecarSuffix
+	data, err := os.ReadFile(path)
+	if errors.Is(err, fs.ErrNotExist) {
+		return nil, nil
+	}
+	if err != nil {
+		return nil, fmt.Errorf("failed to read instruction sidecar: %w", err)
+	}
+
+	var instructions map[string]string
+	if err := toml.Unmarshal(data, &instructions); err != nil {
+		return nil, fmt.Errorf("failed to parse instruction sidecar %s: %w", filepath.Base(path), err)
+	}
+	for key, instruction := range instructions {
+		instructions[key] = strings.TrimSpace(instruction)
+	}
+	return instructions, nil
+}
+
+// sidecarKey returns the key of a function in an instruction sidecar
+func sidecarKey(name string, receiver *Receiver) string {
+	if receiver == nil {
+		return name
+	}
+	return analysis.CleanTypeName(receiver.Type) + "." + name
+}
+
+// warnUnusedSidecarKeys warns about sidecar instructions that match no function
+func warnUnusedSidecarKeys(filePath string, instructions map[string]string, used map[string]bool) {
+	var unused []string
+	for key := range instructions {
+		if !used[key] {
+			unused = append(unused, key)
+		}
+	}
+	if len(unused) == 0 {
+		return
+	}
+	sort.Strings(unused)
+	slog.Warn("instruction sidecar has entries without a matching function",
+		slog.String("file", filepath.Base(filePath)+SidecarSuffix),
+		slog.String("entries", strings.Join(unused, ", ")))
+}
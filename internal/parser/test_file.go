@@ -0,0 +1,169 @@
+package parser
+
+import (
+	"fmt"
+	"go/ast"
+	"go/parser"
+	"go/token"
+	"os"
+	"path"
+	"path/filepath"
+	"slices"
+	"strconv"
+	"strings"
+)
+
+// TestFunctionInfo describes a test function found in a _test.go file
+type TestFunctionInfo struct {
+	FunctionName string   // Test function name (e.g., "TestAdd")
+	Body         string   // Source text of the function body without braces
+	Calls        []string // Names of functions of the package under test called from the test
+	MethodCalls  []string // Names of methods called on values in the test
+}
+
+// CallsFunction reports whether the test calls a function of the package under
+// test with the given name, either unqualified or as pkg.Name from an external
+// test package
+func (t *TestFunctionInfo) CallsFunction(name string) bool {
+	return slices.Contains(t.Calls, name)
+}
+
+// CallsMethod reports whether the test calls a method with the given name on a value
+func (t *TestFunctionInfo) CallsMethod(name string) bool {
+	return slices.Contains(t.MethodCalls, name)
+}
+
+// ParseTestFile extracts all func TestXxx(t *testing.T) functions from a test file
+func ParseTestFile(testPath string) ([]TestFunctionInfo, error) {
+	content, err := os.ReadFile(testPath)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read test file: %w", err)
+	}
+
+	fset := token.NewFileSet()
+	node, err := parser.ParseFile(fset, testPath, content, 0)
+	if err != nil {
+		return nil, fmt.Errorf("failed to parse test file: %w", err)
+	}
+
+	// Package names that calls may be qualified with. External test packages
+	// refer to the package under test by its name.
+	imported := make(map[string]bool)
+	for _, imp := range node.Imports {
+		importPath, _ := strconv.Unquote(imp.Path.Value)
+		name := path.Base(importPath)
+		if imp.Name != nil {
+			name = imp.Name.Name
+		}
+		imported[name] = true
+	}
+	packageUnderTest := strings.TrimSuffix(node.Name.Name, "_test")
+
+	var tests []TestFunctionInfo
+	for _, decl := range node.Decls {
+		funcDecl, ok := decl.(*ast.FuncDecl)
+		if !ok || !isTestFunction(funcDecl) {
+			continue
+		}
+
+		lbrace := fset.Position(funcDecl.Body.Lbrace).Offset
+		rbrace := fset.Position(funcDecl.Body.Rbrace).Offset
+
+		calls, methodCalls := collectCalls(funcDecl.Body, imported, packageUnderTest)
+		tests = append(tests, TestFunctionInfo{
+			FunctionName: funcDecl.Name.Name,
+			Body:         strings.Trim(string(content[lbrace+1:rbrace]), "\n"),
+			Calls:        calls,
+			MethodCalls:  methodCalls,
+		})
+	}
+
+	return tests, nil
+}
+
+// FindTestsForTarget returns tests from the sibling _test.go file that call the target:
+// functions by their name, methods on any value. It returns nil if the sibling
+// test file does not exist.
+func FindTestsForTarget(target *Target) ([]TestFunctionInfo, error) {
+	testPath := strings.TrimSuffix(target.FilePath, ".go") + "_test.go"
+	if _, err := os.Stat(testPath); err != nil {
+		return nil, nil
+	}
+
+	tests, err := ParseTestFile(testPath)
+	if err != nil {
+		return nil, fmt.Errorf("failed to parse %s: %w", filepath.Base(testPath), err)
+	}
+
+	var matching []TestFunctionInfo
+	for _, test := range tests {
+		calls := test.CallsFunction(target.Name)
+		if target.Receiver != nil {
+			calls = test.CallsMethod(target.Name)
+		}
+		if calls {
+			matching = append(matching, test)
+		}
+	}
+
+	return matching, nil
+}
+
+// isTestFunction checks for the func TestXxx(t *testing.T) shape
+func isTestFunction(funcDecl *ast.FuncDecl) bool {
+	if funcDecl.Recv != nil || funcDecl.Body == nil {
+		return false
+	}
+	if !strings.HasPrefix(funcDecl.Name.Name, "Test") {
+		return false
+	}
+
+	params := funcDecl.Type.Params.List
+	if len(params) != 1 {
+		return false
+	}
+
+	star, ok := params[0].Type.(*ast.StarExpr)
+	if !ok {
+		return false
+	}
+	sel, ok := star.X.(*ast.SelectorExpr)
+	return ok && sel.Sel.Name == "T"
+}
+
+// collectCalls returns the names of the functions of packageUnderTest and of
+// the methods called within body. Calls qualified with another imported
+// package are ignored.
+func collectCalls(body *ast.BlockStmt, imported map[string]bool, packageUnderTest string) (calls, methodCalls []string) {
+	seenCalls := make(map[string]bool)
+	seenMethods := make(map[string]bool)
+
+	ast.Inspect(body, func(n ast.Node) bool {
+		call, ok := n.(*ast.CallExpr)
+		if !ok {
+			return true
+		}
+
+		switch fun := call.Fun.(type) {
+		case *ast.Ident:
+			if !seenCalls[fun.Name] {
+				seenCalls[fun.Name] = true
+				calls = append(calls, fun.Name)
+			}
+		case *ast.SelectorExpr:
+			name := fun.Sel.Name
+			if pkg, ok := fun.X.(*ast.Ident); ok && imported[pkg.Name] {
+				if pkg.Name == packageUnderTest && !seenCalls[name] {
+					seenCalls[name] = true
+					calls = append(calls, name)
+				}
+			} else if !seenMethods[name] {
+				seenMethods[name] = true
+				methodCalls = append(methodCalls, name)
+			}
+		}
+		return true
+	})
+
+	return calls, methodCalls
+}
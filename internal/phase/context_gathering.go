@@ -80,6 +80,7 @@ func (p *ContextGatheringPhase) SystemPrompt() string {
 ## Input Structure
 
 - <target>: The function signature to implement
+- <parameters>: The parameters and return types of the target, one by one
 - <context>: Initial context from function signature
 	- Receiver and parameter type definitions
 	- Implemented methods for each type (excluding the method being implemented)
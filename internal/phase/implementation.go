@@ -22,6 +22,11 @@ type ImplementationPhase struct {
 	completed   bool
 	mu          sync.Mutex
 	schema      schemas.ResultSchema
+	basePrompt  string // Replaces the built-in system prompt if set
+	structured  bool   // Whether the result may declare required imports
+	test        bool   // Whether the target is a table-driven test
+	testFile    bool   // Whether the target is declared in a _test.go file
+	comments    bool   // Whether inline comments are asked for
 }
 
 // NewImplementationPhase creates a new implementation phase
@@ -51,6 +56,35 @@ func NewImplementationPhase(temperature float32, projectRoot string, logger *slo
 	return phase
 }
 
+// SetStructuredOutput enables declaring required imports alongside the code
+func (p *ImplementationPhase) SetStructuredOutput(enabled bool) {
+	p.structured = enabled
+	if s, ok := p.schema.(*implementationResultSchema); ok {
+		s.allowImports = enabled
+	}
+}
+
+// SetBasePrompt replaces the built-in system prompt. The sections for tests,
+// comments and declared imports are still appended.
+func (p *ImplementationPhase) SetBasePrompt(prompt string) {
+	p.basePrompt = prompt
+}
+
+// SetTestTarget switches the system prompt to table-driven test generation
+func (p *ImplementationPhase) SetTestTarget(enabled bool) {
+	p.test = enabled
+}
+
+// SetTestFile adds testing idioms to the system prompt for targets in _test.go files
+func (p *ImplementationPhase) SetTestFile(enabled bool) {
+	p.testFile = enabled
+}
+
+// SetAllowComments asks for inline comments explaining the implementation
+func (p *ImplementationPhase) SetAllowComments(enabled bool) {
+	p.comments = enabled
+}
+
 // storeResult stores the result from the result tool
 func (p *ImplementationPhase) storeResult(result any) error {
 	p.mu.Lock()
@@ -77,10 +111,78 @@ func (p *ImplementationPhase) Tools() []tools.Tool {
 
 // SystemPrompt returns the system prompt for implementation
 func (p *ImplementationPhase) SystemPrompt() string {
-	return `You are an expert Go developer. Your task: generate ONLY the code that replaces <IMPLEMENT_HERE>.
+	systemPrompt := implementationSystemPrompt
+	if p.basePrompt != "" {
+		systemPrompt = p.basePrompt
+	}
+	if p.test {
+		systemPrompt += testTargetPrompt
+	}
+	if p.testFile {
+		systemPrompt += testFilePrompt
+	}
+	if p.comments {
+		systemPrompt += commentsPrompt
+	}
+	if p.structured {
+		systemPrompt += structuredOutputPrompt
+	}
+	return systemPrompt
+}
+
+// testTargetPrompt is appended to the system prompt for // mantra:test targets
+const testTargetPrompt = `
+
+## Writing Tests
+
+The target is a Go test function. <instruction> names the function to test and
+"Functions under test" in <context> shows its signature. Write a table-driven test:
+
+- Declare the cases as a slice of structs: tests := []struct{ name string; ...; want ... }{...}
+- Cover normal cases, edge cases and error cases
+- Iterate with for _, tt := range tests and run each case with t.Run(tt.name, ...)
+- Report mismatches with t.Errorf including the input, the result and the expectation`
+
+// testFilePrompt is appended to the system prompt for targets in _test.go files
+const testFilePrompt = `
+
+## Test Code
+
+The target is declared in a _test.go file. Follow Go testing idioms:
+
+- Helpers taking a *testing.T or testing.TB call t.Helper() first
+- Fail setup with t.Fatal or t.Fatalf instead of returning errors
+- Report assertion failures with t.Errorf so that the remaining checks still run
+- Cover several cases with a table of test cases and t.Run`
+
+// commentsPrompt is appended to the system prompt when allow_comments is set
+const commentsPrompt = `
+
+## Comments
+
+Explain non-obvious steps with brief // comments inside the code. Do not restate
+what a line obviously does, and do not add a comment above the function.`
+
+// structuredOutputPrompt is appended to the system prompt when imports can be declared
+const structuredOutputPrompt = `
+
+## Declaring Imports
+
+When your code uses packages that are not listed in <context>, add an "imports"
+array with their import paths to the successful result:
+
+{
+  "success": true,
+  "code": "...",
+  "imports": ["encoding/json", "github.com/google/uuid"]
+}`
+
+// implementationSystemPrompt is the default system prompt for implementation
+const implementationSystemPrompt = `You are an expert Go developer. Your task: generate ONLY the code that replaces <IMPLEMENT_HERE>.
 
 ## Input Structure
 - <target>: The function signature to implement
+- <parameters>: The parameters and return types of the target, one by one
 - <context>: Initial context from function signature
 	- Receiver and parameter type definitions
 	- Implemented methods for each type (excluding the method being implemented)
@@ -127,7 +229,6 @@ Call result() with JSON containing:
 - ALWAYS call the result() tool to complete the phase
 - Use success: false when you cannot gather enough context
 - Provide clear error messages to help diagnose issues`
-}
 
 // PromptBuilder returns a prompt builder configured for implementation
 func (p *ImplementationPhase) PromptBuilder() *prompt.Builder {
@@ -167,10 +268,25 @@ func (p *ImplementationPhase) ResultSchema() schemas.ResultSchema {
 }
 
 // implementationResultSchema defines the schema for implementation phase results
-type implementationResultSchema struct{}
+type implementationResultSchema struct {
+	allowImports bool // Whether the "imports" field is accepted
+}
+
+// importsProperty is the schema of the optional "imports" field
+const importsProperty = `
+			"imports": {
+				"type": "array",
+				"items": {"type": "string"},
+				"description": "Import paths required by the code"
+			},`
 
 // Schema returns the JSON schema for implementation results
 func (s *implementationResultSchema) Schema() json.RawMessage {
+	imports := ""
+	if s.allowImports {
+		imports = importsProperty
+	}
+
 	return json.RawMessage(`{
 		"type": "object",
 		"properties": {
@@ -181,7 +297,7 @@ func (s *implementationResultSchema) Schema() json.RawMessage {
 			"code": {
 				"type": "string",
 				"description": "The generated Go code implementation"
-			},
+			},` + imports + `
 			"error": {
 				"type": "object",
 				"properties": {
@@ -257,6 +373,22 @@ func (s *implementationResultSchema) Validate(data any) error {
 		return fmt.Errorf("code cannot be empty")
 	}
 
+	// Validate declared imports
+	if imports, ok := dataMap["imports"]; ok {
+		if !s.allowImports {
+			return fmt.Errorf("imports field is not supported")
+		}
+		list, ok := imports.([]any)
+		if !ok {
+			return fmt.Errorf("imports must be an array, got %T", imports)
+		}
+		for _, imp := range list {
+			if _, ok := imp.(string); !ok {
+				return fmt.Errorf("imports must contain only strings, got %T", imp)
+			}
+		}
+	}
+
 	return nil
 }
 
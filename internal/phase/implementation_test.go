@@ -0,0 +1,53 @@
+package phase
+
+import (
+	"log/slog"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestImplementationPhase_TestFilePrompt(t *testing.T) {
+	tests := []struct {
+		file     string
+		expected bool
+	}{
+		{file: "/pkg/foo_test.go", expected: true},
+		{file: "/pkg/foo.go", expected: false},
+		{file: "/pkg/test.go", expected: false},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.file, func(t *testing.T) {
+			target := &parser.Target{Name: "newFixture", FilePath: tt.file}
+			implPhase := NewRunner(nil, slog.Default()).newImplementationPhase(target, "/pkg")
+
+			if got := strings.Contains(implPhase.SystemPrompt(), "t.Helper()"); got != tt.expected {
+				t.Errorf("Expected test idioms in prompt: %v, got %v", tt.expected, got)
+			}
+		})
+	}
+}
+
+func TestImplementationPhase_BasePrompt(t *testing.T) {
+	runner := NewRunner(nil, slog.Default())
+	runner.SetSystemPromptOverride("You write Go for a payments team.")
+	runner.SetStructuredOutput(true)
+	runner.SetAllowComments(true)
+	target := &parser.Target{Name: "newFixture", FilePath: "/pkg/foo_test.go"}
+	implPhase := runner.newImplementationPhase(target, "/pkg")
+
+	systemPrompt := implPhase.SystemPrompt()
+	if !strings.HasPrefix(systemPrompt, "You write Go for a payments team.") {
+		t.Errorf("Expected prompt to start with the override, got %q", systemPrompt)
+	}
+	if strings.Contains(systemPrompt, implementationSystemPrompt) {
+		t.Error("Expected built-in prompt to be replaced")
+	}
+	for _, section := range []string{structuredOutputPrompt, testFilePrompt, commentsPrompt} {
+		if !strings.Contains(systemPrompt, section) {
+			t.Errorf("Expected section to be kept with the override: %q", section)
+		}
+	}
+}
@@ -11,6 +11,7 @@ import (
 	"github.com/rail44/mantra/internal/formatter"
 	"github.com/rail44/mantra/internal/llm"
 	"github.com/rail44/mantra/internal/parser"
+	"github.com/rail44/mantra/internal/prompt"
 	"github.com/rail44/mantra/internal/tools"
 )
 
@@ -26,6 +27,18 @@ type Runner struct {
 	client      *llm.Client
 	logger      *slog.Logger
 	phaseLogger *slog.Logger // Current phase-aware logger
+	temperature *float32     // Overrides phase temperatures when set
+
+	promptOptions    prompt.Options // Project-level prompt settings
+	systemPrompt     string         // Overrides the implementation system prompt when set
+	structuredOutput bool           // Lets the implementation declare required imports
+	allowComments    bool           // Asks the implementation for inline comments
+}
+
+// ImplementationOutput holds the result of the implementation phase
+type ImplementationOutput struct {
+	Code    string   // Generated function body
+	Imports []string // Import paths declared alongside the code (structured output only)
 }
 
 // NewRunner creates a new phase runner
@@ -36,6 +49,32 @@ func NewRunner(client *llm.Client, logger *slog.Logger) *Runner {
 	}
 }
 
+// SetTemperatureOverride makes all phases use the given temperature
+// instead of their defaults
+func (r *Runner) SetTemperatureOverride(temperature float32) {
+	r.temperature = &temperature
+}
+
+// SetPromptOptions sets project-level options used to build prompts
+func (r *Runner) SetPromptOptions(options prompt.Options) {
+	r.promptOptions = options
+}
+
+// SetSystemPromptOverride replaces the built-in part of the implementation phase system prompt
+func (r *Runner) SetSystemPromptOverride(systemPrompt string) {
+	r.systemPrompt = systemPrompt
+}
+
+// SetStructuredOutput lets the implementation phase declare required imports
+func (r *Runner) SetStructuredOutput(enabled bool) {
+	r.structuredOutput = enabled
+}
+
+// SetAllowComments asks the implementation phase for inline comments
+func (r *Runner) SetAllowComments(enabled bool) {
+	r.allowComments = enabled
+}
+
 // ExecuteContextGathering executes the context gathering phase
 func (r *Runner) ExecuteContextGathering(ctx context.Context, target *parser.Target, fileContent string, destDir string) (map[string]any, *parser.FailureReason) {
 	// Context is passed through for cancellation
@@ -55,6 +94,7 @@ func (r *Runner) ExecuteContextGathering(ctx context.Context, target *parser.Tar
 
 	// Build prompt
 	contextPromptBuilder := contextPhase.PromptBuilder()
+	contextPromptBuilder.SetOptions(r.promptOptions)
 	initialPrompt, err := contextPromptBuilder.BuildForTarget(target, fileContent)
 	if err != nil {
 		r.logger.Error("Failed to build prompt", "error", err.Error())
@@ -82,24 +122,25 @@ func (r *Runner) ExecuteContextGathering(ctx context.Context, target *parser.Tar
 }
 
 // ExecuteImplementation executes the implementation phase
-func (r *Runner) ExecuteImplementation(ctx context.Context, target *parser.Target, fileContent string, fileInfo *parser.FileInfo, projectRoot string, contextResult map[string]any) (string, *parser.FailureReason) {
+func (r *Runner) ExecuteImplementation(ctx context.Context, target *parser.Target, fileContent string, fileInfo *parser.FileInfo, projectRoot string, contextResult map[string]any) (*ImplementationOutput, *parser.FailureReason) {
 	// Context is passed through for cancellation
 
 	// Setup phase
-	implPhase := NewImplementationPhase(0.2, projectRoot, r.logger)
-	implPhase.Reset() // Ensure clean state
+	implPhase := r.newImplementationPhase(target, projectRoot)
 
 	// Create tool context for static analysis
 	toolContext := tools.NewContext(fileInfo, target, projectRoot)
 	r.configureClientForPhase(implPhase, toolContext)
+	r.client.SetExamples(prompt.ExampleExchanges(r.promptOptions))
 
 	// Build prompt with context
 	contextResultMarkdown := formatter.FormatContextAsMarkdown(contextResult)
 	implPromptBuilder := implPhase.PromptBuilderWithContext(contextResultMarkdown)
+	implPromptBuilder.SetOptions(r.promptOptions)
 	implPrompt, err := implPromptBuilder.BuildForTarget(target, fileContent)
 	if err != nil {
 		r.logger.Error("Failed to build implementation prompt", "error", err.Error())
-		return "", &parser.FailureReason{
+		return nil, &parser.FailureReason{
 			Phase:   "implementation",
 			Message: "Failed to build implementation prompt: " + err.Error(),
 			Context: "Error occurred while incorporating context from phase 1",
@@ -111,7 +152,7 @@ func (r *Runner) ExecuteImplementation(ctx context.Context, target *parser.Targe
 	_, err = r.client.Generate(ctx, implPrompt)
 	if err != nil {
 		r.logger.Error("Implementation failed", "error", err.Error())
-		return "", &parser.FailureReason{
+		return nil, &parser.FailureReason{
 			Phase:   "implementation",
 			Message: "AI implementation generation failed: " + err.Error(),
 			Context: "May be due to complex requirements or AI service issues",
@@ -121,28 +162,49 @@ func (r *Runner) ExecuteImplementation(ctx context.Context, target *parser.Targe
 	// Process result
 	result, failureReason := r.processResult(implPhase, "implementation")
 	if failureReason != nil {
-		return "", failureReason
+		return nil, failureReason
 	}
 
 	// Extract implementation code
 	if result != nil {
 		if code, hasCode := result["code"].(string); hasCode {
-			return code, nil
+			output := &ImplementationOutput{Code: code}
+			if imports, ok := result["imports"].([]any); ok {
+				for _, imp := range imports {
+					if path, ok := imp.(string); ok && path != "" {
+						output.Imports = append(output.Imports, path)
+					}
+				}
+			}
+			return output, nil
 		}
-		return "", &parser.FailureReason{
+		return nil, &parser.FailureReason{
 			Phase:   "implementation",
 			Message: "Missing code field in successful result",
 			Context: "The result() tool was called with success=true but no code was provided",
 		}
 	}
 
-	return "", &parser.FailureReason{
+	return nil, &parser.FailureReason{
 		Phase:   "implementation",
 		Message: "No result from implementation phase",
 		Context: "Unexpected state",
 	}
 }
 
+// newImplementationPhase sets up the implementation phase for target with the
+// runner's settings
+func (r *Runner) newImplementationPhase(target *parser.Target, projectRoot string) *ImplementationPhase {
+	implPhase := NewImplementationPhase(0.2, projectRoot, r.logger)
+	implPhase.Reset() // Ensure clean state
+	implPhase.SetStructuredOutput(r.structuredOutput)
+	implPhase.SetAllowComments(r.allowComments)
+	implPhase.SetTestTarget(target.Test)
+	implPhase.SetTestFile(target.InTestFile())
+	implPhase.SetBasePrompt(r.systemPrompt)
+	return implPhase
+}
+
 // processResult processes the result from a phase
 func (r *Runner) processResult(p Phase, phaseName string) (map[string]any, *parser.FailureReason) {
 	phaseResult, completed := p.Result()
@@ -203,8 +265,13 @@ func (r *Runner) processResult(p Phase, phaseName string) (map[string]any, *pars
 
 // configureClientForPhase configures the AI client with phase-specific settings
 func (r *Runner) configureClientForPhase(p Phase, toolContext *tools.Context) {
-	r.client.SetTemperature(p.Temperature())
+	temperature := p.Temperature()
+	if r.temperature != nil {
+		temperature = *r.temperature
+	}
+	r.client.SetTemperature(temperature)
 	r.client.SetSystemPrompt(p.SystemPrompt())
+	r.client.SetExamples(nil)
 
 	// Create and store phase-aware logger
 	r.phaseLogger = r.logger.With(slog.String("phase", p.Name()))
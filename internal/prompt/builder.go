@@ -3,8 +3,10 @@ package prompt
 import (
 	"fmt"
 	"log/slog"
+	"path/filepath"
 	"strings"
 
+	"github.com/rail44/mantra/internal/checksum"
 	"github.com/rail44/mantra/internal/context"
 	"github.com/rail44/mantra/internal/parser"
 )
@@ -13,6 +15,7 @@ import (
 type Builder struct {
 	useTools          bool
 	additionalContext string
+	options           Options
 	logger            *slog.Logger
 }
 
@@ -33,6 +36,11 @@ func (b *Builder) WithAdditionalContext(context string) *Builder {
 	return b
 }
 
+// SetOptions sets project-level prompt options
+func (b *Builder) SetOptions(options Options) {
+	b.options = options
+}
+
 // SetUseTools enables or disables tool usage instructions in prompts
 func (b *Builder) SetUseTools(useTools bool) {
 	b.useTools = useTools
@@ -40,80 +48,111 @@ func (b *Builder) SetUseTools(useTools bool) {
 
 // BuildForTarget creates a prompt for a specific generation target
 func (b *Builder) BuildForTarget(target *parser.Target, fileContent string) (string, error) {
-	// Use function-focused context extraction for reliable type information
-	ctx, err := context.ExtractFunctionContext(target.FilePath, target)
+	ctx, err := b.extractContext(target)
 	if err != nil {
-		b.logger.Error("context extraction failed", slog.String("error", err.Error()))
-		return "", fmt.Errorf("context extraction failed: %w", err)
+		return "", err
+	}
+
+	if b.options.Template != nil {
+		return b.buildPromptFromTemplate(ctx, target)
 	}
 
 	return b.buildPromptWithContext(ctx, target), nil
 }
 
-// buildPromptWithContext builds a prompt using the extracted context
-func (b *Builder) buildPromptWithContext(ctx *context.RelevantContext, target *parser.Target) string {
-	var prompt strings.Builder
+// TypeInfoForTarget returns the type information that prompts for the target
+// include, without building the rest of the prompt
+func (b *Builder) TypeInfoForTarget(target *parser.Target) (string, error) {
+	ctx, err := b.extractContext(target)
+	if err != nil {
+		return "", err
+	}
+	return b.buildTypeInfo(ctx), nil
+}
 
-	// DevStral最適化：XMLタグで構造化
-	prompt.WriteString("<context>\n")
+// extractContext collects the context of the target used to build prompts
+func (b *Builder) extractContext(target *parser.Target) (*context.RelevantContext, error) {
+	if ctx, ok := b.options.Contexts[checksum.Calculate(target)]; ok {
+		return ctx, nil
+	}
 
-	// All imports are treated as available packages for the AI
-	if len(ctx.Imports) > 0 {
-		prompt.WriteString("Available packages:\n")
-		for _, imp := range ctx.Imports {
-			identifier := imp.GetIdentifier()
-
-			// For blank imports, we still show them as available packages
-			// The AI doesn't need to know about the blank import detail
-			if imp.Path == identifier {
-				// Standard library or simple package
-				prompt.WriteString(fmt.Sprintf("- %s\n", imp.Path))
-			} else if imp.Alias != "" && imp.Alias != "_" && imp.Alias != identifier {
-				// Custom alias (excluding blank imports)
-				prompt.WriteString(fmt.Sprintf("- %s \"%s\"\n", imp.Alias, imp.Path))
-			} else {
-				// Package with different identifier
-				prompt.WriteString(fmt.Sprintf("- %s \"%s\"\n", identifier, imp.Path))
-			}
-		}
-		prompt.WriteString("\n")
+	// Use function-focused context extraction for reliable type information
+	ctx, err := context.ExtractFunctionContext(target.FilePath, target)
+	if err != nil {
+		b.logger.Error("context extraction failed", slog.String("error", err.Error()))
+		return nil, fmt.Errorf("context extraction failed: %w", err)
 	}
 
-	// 関数シグネチャに関連する型情報を優先的に表示
-	if len(ctx.Types) > 0 {
-		prompt.WriteString("Available types:\n")
-		for typeName, typeDef := range ctx.Types {
-			prompt.WriteString(fmt.Sprintf("```go\n%s\n```\n", typeDef))
-
-			// Include methods for this type if available
-			if methods, exists := ctx.Methods[typeName]; exists && len(methods) > 0 {
-				prompt.WriteString("\nMethods:\n")
-				for _, method := range methods {
-					prompt.WriteString(fmt.Sprintf("- %s\n", method.Signature))
-				}
-			}
-			prompt.WriteString("\n")
+	if b.options.PackageContext {
+		api, err := context.ExtractPackageAPI(filepath.Dir(target.FilePath), target.FilePath)
+		if err != nil {
+			b.logger.Warn("package API extraction failed", slog.String("error", err.Error()))
+		} else {
+			ctx.PackageAPI = api
+		}
+
+		constants, err := context.ExtractConstantsAndVariables(filepath.Dir(target.FilePath))
+		if err != nil {
+			b.logger.Warn("constant extraction failed", slog.String("error", err.Error()))
+		} else {
+			ctx.Constants = constants
 		}
 	}
 
-	prompt.WriteString("</context>\n\n")
+	return ctx, nil
+}
 
-	prompt.WriteString("<target>\n")
-	prompt.WriteString(fmt.Sprintf("```go\n%s {\n    <IMPLEMENT_HERE>\n}\n```\n", target.GetFunctionSignature()))
-	prompt.WriteString("</target>\n\n")
+// buildPromptFromTemplate renders the user-provided prompt template
+func (b *Builder) buildPromptFromTemplate(ctx *context.RelevantContext, target *parser.Target) (string, error) {
+	rendered, err := RenderTemplate(b.options.Template, TemplateVars{
+		Package:     ctx.PackageName,
+		Signature:   target.GetFunctionSignature(),
+		Instruction: target.Instruction,
+		TypeInfo:    b.buildTypeInfo(ctx),
+	})
+	if err != nil {
+		return "", err
+	}
 
-	prompt.WriteString("<instruction>\n")
-	prompt.WriteString(fmt.Sprintf("%s\n", target.Instruction))
-	prompt.WriteString("</instruction>\n")
+	// Parameters, patterns, hints and context from previous phases are not
+	// part of the template variables
+	rendered += parametersSection(target)
+	if b.options.PatternExamples {
+		rendered += patternsSection(selectPatterns(target))
+	}
+	rendered += hintsSection(target, ctx.Imports) + additionalContextSection(b.additionalContext)
+
+	return rendered, nil
+}
 
-	// Add additional context if provided
-	if b.additionalContext != "" {
-		prompt.WriteString("\n<additional_context>\n")
-		prompt.WriteString(b.additionalContext)
-		prompt.WriteString("\n</additional_context>\n")
+// buildPromptWithContext builds a prompt using the extracted context
+func (b *Builder) buildPromptWithContext(ctx *context.RelevantContext, target *parser.Target) string {
+	var prompt strings.Builder
+
+	// DevStral最適化：XMLタグで構造化
+	prompt.WriteString("<context>\n")
+	prompt.WriteString(b.buildTypeInfo(ctx))
+	prompt.WriteString(testsSection(ctx.Tests))
+	if b.options.PatternExamples {
+		prompt.WriteString(patternsSection(selectPatterns(target)))
 	}
+	prompt.WriteString("</context>\n\n")
 
-	fullPrompt := prompt.String()
+	prompt.WriteString(targetSection(target))
+	prompt.WriteString(parametersSection(target))
+	prompt.WriteString(instructionSection(target))
+	prompt.WriteString(hintsSection(target, ctx.Imports))
+	prompt.WriteString(additionalContextSection(b.additionalContext))
+
+	return prompt.String()
+}
 
-	return fullPrompt
+// buildTypeInfo renders packages and type definitions available to the target
+func (b *Builder) buildTypeInfo(ctx *context.RelevantContext) string {
+	return packagesSection(ctx.Imports) +
+		typesSection(ctx.Types, ctx.Methods, ctx.TypeRoles) +
+		testedFunctionsSection(ctx.TestedFunctions) +
+		errorTypesSection(ctx.ErrorTypes) +
+		packageAPISection(ctx.PackageAPI, b.options.MaxContextTokens) +
+		constantsSection(ctx.Constants)
 }
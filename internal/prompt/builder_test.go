@@ -0,0 +1,105 @@
+package prompt
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/context"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func newTestTarget() *parser.Target {
+	return &parser.Target{
+		Name:        "Add",
+		Instruction: "add two numbers",
+		Params: []parser.Param{
+			{Name: "a", Type: "int"},
+			{Name: "b", Type: "int"},
+		},
+		Returns: []parser.Return{{Type: "int"}},
+	}
+}
+
+func TestBuildPromptWithContext_ExistingTests(t *testing.T) {
+	ctx := &context.RelevantContext{
+		Types: map[string]string{},
+		Tests: []parser.TestFunctionInfo{
+			{
+				FunctionName: "TestAdd",
+				Body:         "\tif Add(1, 2) != 3 {\n\t\tt.Fail()\n\t}",
+				Calls:        []string{"Add", "Fail"},
+			},
+		},
+	}
+
+	prompt := NewBuilder(nil).buildPromptWithContext(ctx, newTestTarget())
+
+	if !strings.Contains(prompt, "Existing tests:") {
+		t.Errorf("Expected existing tests section, got:\n%s", prompt)
+	}
+	if !strings.Contains(prompt, "func TestAdd(t *testing.T) {\n\tif Add(1, 2) != 3 {") {
+		t.Errorf("Expected TestAdd body in prompt, got:\n%s", prompt)
+	}
+}
+
+func TestBuildPromptWithContext_PackageAPI(t *testing.T) {
+	tempDir := t.TempDir()
+	targetFile := filepath.Join(tempDir, "math.go")
+	otherFile := filepath.Join(tempDir, "types.go")
+
+	if err := os.WriteFile(targetFile, []byte("package math\n\nfunc Add(a, b int) int {\n\tpanic(\"not implemented\")\n}\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+	otherContent := `package math
+
+// Vector is a 2D vector
+type Vector struct {
+	X, Y int
+}
+
+// Scale multiplies v by n
+func Scale(v Vector, n int) Vector {
+	return Vector{v.X * n, v.Y * n}
+}
+
+func unexported() {}
+`
+	if err := os.WriteFile(otherFile, []byte(otherContent), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	api, err := context.ExtractPackageAPI(tempDir, targetFile)
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	builder := NewBuilder(nil)
+	builder.SetOptions(Options{PackageContext: true})
+	ctx := &context.RelevantContext{
+		Types:      map[string]string{},
+		PackageAPI: api,
+	}
+	prompt := builder.buildPromptWithContext(ctx, newTestTarget())
+
+	if !strings.Contains(prompt, "// Package API:") {
+		t.Fatalf("Expected package API section, got:\n%s", prompt)
+	}
+	if !strings.Contains(prompt, "func Scale(v Vector, n int) Vector\n") {
+		t.Errorf("Expected Scale signature without body, got:\n%s", prompt)
+	}
+	if !strings.Contains(prompt, "type Vector struct") {
+		t.Errorf("Expected Vector type, got:\n%s", prompt)
+	}
+	if strings.Contains(prompt, "unexported") || strings.Contains(prompt, "v.X * n") {
+		t.Errorf("Expected only exported signatures, got:\n%s", prompt)
+	}
+
+	// A small budget truncates the section
+	builder.SetOptions(Options{PackageContext: true, MaxContextTokens: 5})
+	prompt = builder.buildPromptWithContext(ctx, newTestTarget())
+	if !strings.Contains(prompt, "more declarations omitted") {
+		t.Errorf("Expected truncated package API, got:\n%s", prompt)
+	}
+}
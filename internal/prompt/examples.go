@@ -0,0 +1,52 @@
+package prompt
+
+import (
+	"fmt"
+
+	"github.com/rail44/mantra/internal/llm"
+)
+
+// Example is an example implementation used for few-shot prompting
+type Example struct {
+	Signature   string
+	Instruction string
+	Body        string
+}
+
+// ExampleExchanges converts the configured examples into prompt/response pairs.
+// Duplicates are dropped, and examples stop once MaxExamples or
+// MaxContextTokens would be exceeded.
+func ExampleExchanges(options Options) []llm.Exchange {
+	var exchanges []llm.Exchange
+	seen := make(map[Example]bool)
+	tokens := 0
+
+	for _, example := range options.Examples {
+		if options.MaxExamples > 0 && len(exchanges) >= options.MaxExamples {
+			break
+		}
+		if seen[example] {
+			continue
+		}
+		seen[example] = true
+
+		exchange := llm.Exchange{
+			Prompt:   examplePrompt(example),
+			Response: fmt.Sprintf("```go\n%s\n```", example.Body),
+		}
+
+		tokens += estimateTokens(exchange.Prompt) + estimateTokens(exchange.Response)
+		if options.MaxContextTokens > 0 && tokens > options.MaxContextTokens {
+			break
+		}
+		exchanges = append(exchanges, exchange)
+	}
+
+	return exchanges
+}
+
+// examplePrompt renders an example in the same layout as the target and instruction sections
+func examplePrompt(example Example) string {
+	return fmt.Sprintf("<target>\n```go\n%s {\n    <IMPLEMENT_HERE>\n}\n```\n</target>\n\n<instruction>\n%s\n</instruction>\n",
+		example.Signature, example.Instruction)
+}
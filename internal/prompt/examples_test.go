@@ -0,0 +1,46 @@
+package prompt
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestExampleExchanges(t *testing.T) {
+	first := Example{
+		Signature:   "func Add(a, b int) int",
+		Instruction: "Add two numbers",
+		Body:        "return a + b",
+	}
+	second := Example{
+		Signature:   "func Sub(a, b int) int",
+		Instruction: "Subtract b from a",
+		Body:        "return a - b",
+	}
+
+	t.Run("deduplicates", func(t *testing.T) {
+		exchanges := ExampleExchanges(Options{Examples: []Example{first, first, second}})
+		if len(exchanges) != 2 {
+			t.Fatalf("Expected 2 exchanges, got %d", len(exchanges))
+		}
+		if !strings.Contains(exchanges[0].Prompt, first.Signature) || !strings.Contains(exchanges[0].Prompt, first.Instruction) {
+			t.Errorf("Expected prompt to contain signature and instruction, got:\n%s", exchanges[0].Prompt)
+		}
+		if !strings.Contains(exchanges[0].Response, first.Body) {
+			t.Errorf("Expected response to contain body, got:\n%s", exchanges[0].Response)
+		}
+	})
+
+	t.Run("limits count", func(t *testing.T) {
+		exchanges := ExampleExchanges(Options{Examples: []Example{first, second}, MaxExamples: 1})
+		if len(exchanges) != 1 {
+			t.Errorf("Expected 1 exchange, got %d", len(exchanges))
+		}
+	})
+
+	t.Run("limits tokens", func(t *testing.T) {
+		exchanges := ExampleExchanges(Options{Examples: []Example{first, second}, MaxContextTokens: 1})
+		if len(exchanges) != 0 {
+			t.Errorf("Expected no exchanges within budget, got %d", len(exchanges))
+		}
+	})
+}
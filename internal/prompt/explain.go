@@ -0,0 +1,22 @@
+package prompt
+
+import (
+	"fmt"
+	"strings"
+
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// ExplainSystemPrompt is the system prompt for explaining generated code
+const ExplainSystemPrompt = `You are an expert Go developer reviewing generated code.
+Explain briefly how the implementation fulfills the instruction, and point out
+assumptions or edge cases it does not handle. Answer in plain text, in a few sentences.`
+
+// BuildExplainPrompt creates a prompt asking why the body implements the target the way it does
+func BuildExplainPrompt(target *parser.Target, body string) string {
+	var sb strings.Builder
+	sb.WriteString(instructionSection(target))
+	sb.WriteString(fmt.Sprintf("\n<implementation>\n```go\n%s {\n%s\n}\n```\n</implementation>\n",
+		target.GetFunctionSignature(), strings.TrimRight(body, "\n")))
+	return sb.String()
+}
@@ -0,0 +1,22 @@
+package prompt
+
+import (
+	"testing"
+
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestBuildExplainPrompt(t *testing.T) {
+	target := &parser.Target{
+		Name:        "Add",
+		Instruction: "add two numbers",
+		Params:      []parser.Param{{Name: "a", Type: "int"}, {Name: "b", Type: "int"}},
+		Returns:     []parser.Return{{Type: "int"}},
+	}
+
+	expected := "<instruction>\nadd two numbers\n</instruction>\n" +
+		"\n<implementation>\n```go\nfunc Add(a int, b int) int {\n\treturn a + b\n}\n```\n</implementation>\n"
+	if got := BuildExplainPrompt(target, "\treturn a + b\n"); got != expected {
+		t.Errorf("Expected %q, got %q", expected, got)
+	}
+}
@@ -0,0 +1,73 @@
+package prompt
+
+import (
+	_ "embed"
+	"encoding/json"
+	"strings"
+
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// maxPatterns limits how many pattern examples are added to a prompt
+const maxPatterns = 2
+
+// Pattern is an example of a common Go code pattern
+type Pattern struct {
+	Name     string   `json:"name"`
+	Keywords []string `json:"keywords"` // Selects the pattern when a type of the signature contains one
+	Example  string   `json:"example"`
+}
+
+//go:embed patterns.json
+var patternsJSON []byte
+
+// patternLibrary is the built-in pattern catalogue, most specific patterns first
+var patternLibrary = mustLoadPatterns(patternsJSON)
+
+// mustLoadPatterns parses the embedded catalogue, which is fixed at build time
+func mustLoadPatterns(data []byte) []Pattern {
+	var patterns []Pattern
+	if err := json.Unmarshal(data, &patterns); err != nil {
+		panic("invalid patterns.json: " + err.Error())
+	}
+	return patterns
+}
+
+// selectPatterns returns up to maxPatterns patterns whose keywords appear in
+// the receiver, parameter or return types of the target
+func selectPatterns(target *parser.Target) []Pattern {
+	var types []string
+	if target.Receiver != nil {
+		types = append(types, target.Receiver.Type)
+	}
+	for _, param := range target.Params {
+		types = append(types, param.Type)
+	}
+	for _, ret := range target.Returns {
+		types = append(types, ret.Type)
+	}
+	signatureTypes := strings.Join(types, "\n")
+
+	var selected []Pattern
+	for _, pattern := range patternLibrary {
+		if len(selected) >= maxPatterns {
+			break
+		}
+		for _, keyword := range pattern.Keywords {
+			if strings.Contains(signatureTypes, keyword) {
+				selected = append(selected, pattern)
+				break
+			}
+		}
+	}
+	return selected
+}
+
+// patternsSection shows examples of the patterns relevant to the target
+func patternsSection(patterns []Pattern) string {
+	var sb strings.Builder
+	for _, pattern := range patterns {
+		sb.WriteString("// Example pattern: " + pattern.Name + "\n```go\n" + pattern.Example + "\n```\n\n")
+	}
+	return sb.String()
+}
@@ -0,0 +1,71 @@
+package prompt
+
+import (
+	"reflect"
+	"testing"
+
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestSelectPatterns(t *testing.T) {
+	tests := []struct {
+		name     string
+		target   *parser.Target
+		expected []string
+	}{
+		{
+			name: "HTTP handler",
+			target: &parser.Target{
+				Receiver: &parser.Receiver{Name: "h", Type: "*Handler"},
+				Params:   []parser.Param{{Name: "w", Type: "http.ResponseWriter"}, {Name: "r", Type: "*http.Request"}},
+			},
+			expected: []string{"http_handler"},
+		},
+		{
+			name: "at most two patterns",
+			target: &parser.Target{
+				Params:  []parser.Param{{Name: "ctx", Type: "context.Context"}, {Name: "db", Type: "*sql.DB"}},
+				Returns: []parser.Return{{Type: "[]string"}, {Type: "error"}},
+			},
+			expected: []string{"database_query", "context_cancellation"},
+		},
+		{
+			name: "channel and error",
+			target: &parser.Target{
+				Params:  []parser.Param{{Name: "jobs", Type: "chan Job"}},
+				Returns: []parser.Return{{Type: "error"}},
+			},
+			expected: []string{"goroutine_waitgroup", "error_wrapping"},
+		},
+		{
+			name: "no pattern",
+			target: &parser.Target{
+				Params:  []parser.Param{{Name: "errorCount", Type: "int"}},
+				Returns: []parser.Return{{Type: "string"}},
+			},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			var names []string
+			for _, pattern := range selectPatterns(tt.target) {
+				names = append(names, pattern.Name)
+			}
+			if !reflect.DeepEqual(names, tt.expected) {
+				t.Errorf("Expected %v, got %v", tt.expected, names)
+			}
+		})
+	}
+}
+
+func TestPatternLibrary(t *testing.T) {
+	if len(patternLibrary) == 0 {
+		t.Fatal("Expected the embedded pattern library to be loaded")
+	}
+	for _, pattern := range patternLibrary {
+		if pattern.Name == "" || len(pattern.Keywords) == 0 || pattern.Example == "" {
+			t.Errorf("Incomplete pattern %+v", pattern)
+		}
+	}
+}
@@ -0,0 +1,47 @@
+package prompt
+
+import (
+	"log/slog"
+	"sync"
+
+	"golang.org/x/sync/errgroup"
+
+	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/context"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// ContextCache holds the contexts of targets, keyed by target checksum
+type ContextCache map[string]*context.RelevantContext
+
+// PrefetchContexts extracts the contexts of all targets with up to concurrency
+// extractions at a time. Loading packages is cheap compared to LLM calls, so
+// doing it up front keeps it out of the time each generation waits.
+// Targets whose extraction fails are left out and extracted again when their
+// prompt is built, which reports the error.
+func (b *Builder) PrefetchContexts(targets []*parser.Target, concurrency int) ContextCache {
+	var mu sync.Mutex
+	cache := make(ContextCache, len(targets))
+
+	var g errgroup.Group
+	g.SetLimit(concurrency)
+	for _, target := range targets {
+		g.Go(func() error {
+			ctx, err := b.extractContext(target)
+			if err != nil {
+				b.logger.Debug("context prefetch failed",
+					slog.String("target", target.GetDisplayName()),
+					slog.String("error", err.Error()))
+				return nil
+			}
+
+			mu.Lock()
+			cache[checksum.Calculate(target)] = ctx
+			mu.Unlock()
+			return nil
+		})
+	}
+	g.Wait()
+
+	return cache
+}
@@ -0,0 +1,69 @@
+package prompt
+
+import (
+	"os"
+	"path/filepath"
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/checksum"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestPrefetchContexts(t *testing.T) {
+	tempDir := t.TempDir()
+	targetFile := filepath.Join(tempDir, "shapes.go")
+	source := `package shapes
+
+// Rect is a rectangle
+type Rect struct {
+	W, H int
+}
+
+// mantra: area of the rectangle
+func Area(r Rect) int {
+	panic("not implemented")
+}
+`
+	if err := os.WriteFile(targetFile, []byte(source), 0644); err != nil {
+		t.Fatal(err)
+	}
+	if err := os.WriteFile(filepath.Join(tempDir, "go.mod"), []byte("module shapes\n\ngo 1.21\n"), 0644); err != nil {
+		t.Fatal(err)
+	}
+
+	target := &parser.Target{
+		Name:        "Area",
+		Instruction: "area of the rectangle",
+		Params:      []parser.Param{{Name: "r", Type: "Rect"}},
+		Returns:     []parser.Return{{Type: "int"}},
+		FilePath:    targetFile,
+	}
+	missing := &parser.Target{
+		Name:     "Missing",
+		FilePath: filepath.Join(t.TempDir(), "missing.go"),
+	}
+
+	builder := NewBuilder(nil)
+	cache := builder.PrefetchContexts([]*parser.Target{target, missing}, 2)
+
+	if _, ok := cache[checksum.Calculate(target)]; !ok {
+		t.Fatalf("Expected context of Area to be prefetched, got %v", cache)
+	}
+	if len(cache) != 1 {
+		t.Errorf("Expected failed extraction to be left out, got %d entries", len(cache))
+	}
+
+	// Prompts are built from the prefetched context without loading the package again
+	if err := os.Remove(targetFile); err != nil {
+		t.Fatal(err)
+	}
+	builder.SetOptions(Options{Contexts: cache})
+	typeInfo, err := builder.TypeInfoForTarget(target)
+	if err != nil {
+		t.Fatalf("Expected prefetched context to be used, got %v", err)
+	}
+	if !strings.Contains(typeInfo, "type Rect struct") {
+		t.Errorf("Expected Rect in type info, got:\n%s", typeInfo)
+	}
+}
@@ -0,0 +1,225 @@
+package prompt
+
+import (
+	"fmt"
+	"sort"
+	"strings"
+
+	"github.com/rail44/mantra/internal/analysis"
+	"github.com/rail44/mantra/internal/context"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+// Each section renders one labelled part of the prompt.
+// Sections return an empty string when there is nothing to show.
+
+// packagesSection lists the packages imported by the target file.
+// All imports are treated as available packages for the AI.
+func packagesSection(imports []*context.ImportInfo) string {
+	if len(imports) == 0 {
+		return ""
+	}
+
+	var sb strings.Builder
+	sb.WriteString("Available packages:\n")
+	for _, imp := range imports {
+		identifier := imp.GetIdentifier()
+
+		// For blank imports, we still show them as available packages
+		// The AI doesn't need to know about the blank import detail
+		if imp.Path == identifier {
+			// Standard library or simple package
+			sb.WriteString(fmt.Sprintf("- %s\n", imp.Path))
+		} else if imp.Alias != "" && imp.Alias != "_" && imp.Alias != identifier {
+			// Custom alias (excluding blank imports)
+			sb.WriteString(fmt.Sprintf("- %s \"%s\"\n", imp.Alias, imp.Path))
+		} else {
+			// Package with different identifier
+			sb.WriteString(fmt.Sprintf("- %s \"%s\"\n", identifier, imp.Path))
+		}
+	}
+	sb.WriteString("\n")
+	return sb.String()
+}
+
+// typesSection shows type definitions related to the function signature, with their methods
+func typesSection(types map[string]string, methods map[string][]analysis.MethodInfo, roles map[string]string) string {
+	if len(types) == 0 {
+		return ""
+	}
+
+	var sb strings.Builder
+	sb.WriteString("Available types:\n")
+	for _, typeName := range sortedKeys(types) {
+		// Label types of the target signature, e.g. to construct a valid return value
+		if role := roles[typeName]; role != "" {
+			sb.WriteString(fmt.Sprintf("%s (%s):\n", typeName, role))
+		}
+		sb.WriteString(fmt.Sprintf("```go\n%s\n```\n", types[typeName]))
+
+		// Include methods for this type if available
+		if typeMethods := methods[typeName]; len(typeMethods) > 0 {
+			sb.WriteString("\nMethods:\n")
+			for _, method := range typeMethods {
+				sb.WriteString(fmt.Sprintf("- %s\n", method.Signature))
+			}
+		}
+		sb.WriteString("\n")
+	}
+	return sb.String()
+}
+
+// testedFunctionsSection shows the functions exercised by a table-driven test target
+func testedFunctionsSection(signatures []string) string {
+	return codeBlocksSection("Functions under test:", signatures)
+}
+
+// errorTypesSection shows custom error types for targets returning error
+func errorTypesSection(errorTypes map[string]string) string {
+	var definitions []string
+	for _, name := range sortedKeys(errorTypes) {
+		definitions = append(definitions, errorTypes[name])
+	}
+	return codeBlocksSection("Available error types:", definitions)
+}
+
+// packageAPISection shows the exported API of the rest of the package,
+// truncated to roughly maxTokens (0 means no limit)
+func packageAPISection(declarations []string, maxTokens int) string {
+	if len(declarations) == 0 {
+		return ""
+	}
+
+	var sb strings.Builder
+	sb.WriteString("// Package API:\n```go\n")
+	tokens := 0
+	for i, decl := range declarations {
+		tokens += estimateTokens(decl)
+		if maxTokens > 0 && tokens > maxTokens {
+			sb.WriteString(fmt.Sprintf("// ... %d more declarations omitted\n", len(declarations)-i))
+			break
+		}
+		sb.WriteString(decl + "\n")
+	}
+	sb.WriteString("```\n\n")
+	return sb.String()
+}
+
+// constantsSection shows package-level constants and variables
+func constantsSection(constants []context.ConstOrVar) string {
+	if len(constants) == 0 {
+		return ""
+	}
+
+	var sb strings.Builder
+	sb.WriteString("// Package constants and variables:\n```go\n")
+	for _, c := range constants {
+		sb.WriteString(c.String() + "\n")
+	}
+	sb.WriteString("```\n\n")
+	return sb.String()
+}
+
+// testsSection shows existing tests calling the target, which describe its expected behaviour
+func testsSection(tests []parser.TestFunctionInfo) string {
+	var blocks []string
+	for _, test := range tests {
+		blocks = append(blocks, fmt.Sprintf("func %s(t *testing.T) {\n%s\n}", test.FunctionName, test.Body))
+	}
+	return codeBlocksSection("Existing tests:", blocks)
+}
+
+// targetSection shows the signature to implement
+func targetSection(target *parser.Target) string {
+	return fmt.Sprintf("<target>\n```go\n%s {\n    <IMPLEMENT_HERE>\n}\n```\n</target>\n\n", target.GetFunctionSignature())
+}
+
+// parametersSection lists the parameters separately from the signature, so
+// that grouped parameters are not missed
+func parametersSection(target *parser.Target) string {
+	summary := target.GetParameterSummary()
+	if summary == "" {
+		return ""
+	}
+	return "<parameters>\n" + summary + "\n</parameters>\n\n"
+}
+
+// instructionSection shows the natural language instruction
+func instructionSection(target *parser.Target) string {
+	return fmt.Sprintf("<instruction>\n%s\n</instruction>\n", target.Instruction)
+}
+
+// contextHint is a standing instruction for targets that take a context.Context,
+// formatted with the name of the parameter
+const contextHint = "Propagate %s to all calls that accept a context.Context and respect its cancellation."
+
+// hintsSection adds standing instructions derived from the target signature,
+// so that users need not repeat them in every instruction. imports are the
+// imports of the target file, which resolve the name of the context package.
+func hintsSection(target *parser.Target, imports []*context.ImportInfo) string {
+	if len(target.Params) == 0 {
+		return ""
+	}
+	param := target.Params[0]
+	if param.Name == "" || param.Name == "_" || !isContextType(param.Type, imports) {
+		return ""
+	}
+	return "<hints>\n" + fmt.Sprintf(contextHint, param.Name) + "\n</hints>\n"
+}
+
+// isContextType reports whether typ denotes context.Context in a file with imports
+func isContextType(typ string, imports []*context.ImportInfo) bool {
+	for _, imp := range imports {
+		if imp.Path != "context" || imp.IsBlank {
+			continue
+		}
+		if imp.Alias == "." {
+			if typ == "Context" {
+				return true
+			}
+			continue
+		}
+		if typ == imp.GetIdentifier()+".Context" {
+			return true
+		}
+	}
+	return false
+}
+
+// additionalContextSection shows context from previous phases
+func additionalContextSection(additionalContext string) string {
+	if additionalContext == "" {
+		return ""
+	}
+	return "\n<additional_context>\n" + additionalContext + "\n</additional_context>\n"
+}
+
+// codeBlocksSection renders a title followed by one Go code block per entry
+func codeBlocksSection(title string, blocks []string) string {
+	if len(blocks) == 0 {
+		return ""
+	}
+
+	var sb strings.Builder
+	sb.WriteString(title + "\n")
+	for _, block := range blocks {
+		sb.WriteString(fmt.Sprintf("```go\n%s\n```\n", block))
+	}
+	sb.WriteString("\n")
+	return sb.String()
+}
+
+// sortedKeys returns the keys of m in sorted order for stable prompts
+func sortedKeys(m map[string]string) []string {
+	keys := make([]string, 0, len(m))
+	for key := range m {
+		keys = append(keys, key)
+	}
+	sort.Strings(keys)
+	return keys
+}
+
+// estimateTokens roughly estimates the number of tokens in text
+func estimateTokens(text string) int {
+	return (len(text) + 3) / 4
+}
@@ -0,0 +1,125 @@
+package prompt
+
+import (
+	"strings"
+	"testing"
+
+	"github.com/rail44/mantra/internal/analysis"
+	"github.com/rail44/mantra/internal/context"
+	"github.com/rail44/mantra/internal/parser"
+)
+
+func TestSections(t *testing.T) {
+	contextImport := []*context.ImportInfo{{Path: "context"}}
+
+	tests := []struct {
+		name  string
+		full  string
+		empty string
+		want  []string
+	}{
+		{
+			name:  "packages",
+			full:  packagesSection([]*context.ImportInfo{{Path: "strings"}}),
+			empty: packagesSection(nil),
+			want:  []string{"Available packages:\n", "- strings\n"},
+		},
+		{
+			name: "types",
+			full: typesSection(
+				map[string]string{"User": "type User struct{}"},
+				map[string][]analysis.MethodInfo{"User": {{Name: "Name", Signature: "func (u User) Name() string"}}},
+				map[string]string{"User": "return type"},
+			),
+			empty: typesSection(map[string]string{}, nil, nil),
+			want:  []string{"Available types:\n", "User (return type):\n```go\ntype User struct{}\n```\n", "Methods:\n- func (u User) Name() string\n"},
+		},
+		{
+			name:  "tested functions",
+			full:  testedFunctionsSection([]string{"func Add(a, b int) int"}),
+			empty: testedFunctionsSection(nil),
+			want:  []string{"Functions under test:\n", "```go\nfunc Add(a, b int) int\n```\n"},
+		},
+		{
+			name:  "error types",
+			full:  errorTypesSection(map[string]string{"NotFoundError": "type NotFoundError struct{}"}),
+			empty: errorTypesSection(map[string]string{}),
+			want:  []string{"Available error types:\n", "type NotFoundError struct{}"},
+		},
+		{
+			name:  "package API",
+			full:  packageAPISection([]string{"func Scale(v Vector, n int) Vector"}, 0),
+			empty: packageAPISection(nil, 0),
+			want:  []string{"// Package API:\n", "func Scale(v Vector, n int) Vector\n"},
+		},
+		{
+			name:  "existing tests",
+			full:  testsSection([]parser.TestFunctionInfo{{FunctionName: "TestAdd", Body: "\tAdd(1, 2)"}}),
+			empty: testsSection(nil),
+			want:  []string{"Existing tests:\n", "func TestAdd(t *testing.T) {\n\tAdd(1, 2)\n}"},
+		},
+		{
+			name:  "hints",
+			full:  hintsSection(&parser.Target{Params: []parser.Param{{Name: "ctx", Type: "context.Context"}, {Name: "id", Type: "string"}}}, contextImport),
+			empty: hintsSection(&parser.Target{Params: []parser.Param{{Name: "id", Type: "string"}, {Name: "ctx", Type: "context.Context"}}}, contextImport),
+			want:  []string{"<hints>\n", "Propagate ctx to all calls"},
+		},
+		{
+			name:  "hints with aliased context",
+			full:  hintsSection(&parser.Target{Params: []parser.Param{{Name: "c", Type: "stdctx.Context"}}}, []*context.ImportInfo{{Path: "context", Alias: "stdctx"}}),
+			empty: hintsSection(&parser.Target{Params: []parser.Param{{Name: "c", Type: "context.Context"}}}, []*context.ImportInfo{{Path: "context", Alias: "stdctx"}}),
+			want:  []string{"Propagate c to all calls"},
+		},
+		{
+			name:  "hints without a usable context name",
+			full:  hintsSection(&parser.Target{Params: []parser.Param{{Name: "ctx", Type: "context.Context"}}}, contextImport),
+			empty: hintsSection(&parser.Target{Params: []parser.Param{{Name: "_", Type: "context.Context"}}}, contextImport) + hintsSection(&parser.Target{Params: []parser.Param{{Type: "context.Context"}}}, contextImport),
+			want:  []string{"Propagate ctx to all calls"},
+		},
+		{
+			name:  "patterns",
+			full:  patternsSection([]Pattern{{Name: "error_wrapping", Example: "return fmt.Errorf(\"read: %w\", err)"}}),
+			empty: patternsSection(nil),
+			want:  []string{"// Example pattern: error_wrapping\n```go\nreturn fmt.Errorf(\"read: %w\", err)\n```\n"},
+		},
+		{
+			name:  "parameters",
+			full:  parametersSection(&parser.Target{Params: []parser.Param{{Name: "a", Type: "int"}, {Name: "b", Type: "int"}}}),
+			empty: parametersSection(&parser.Target{}),
+			want:  []string{"<parameters>\nParameters: a int, b int\n</parameters>\n"},
+		},
+		{
+			name:  "additional context",
+			full:  additionalContextSection("found helper"),
+			empty: additionalContextSection(""),
+			want:  []string{"<additional_context>\nfound helper\n</additional_context>"},
+		},
+	}
+
+	for _, tt := range tests {
+		t.Run(tt.name, func(t *testing.T) {
+			for _, want := range tt.want {
+				if !strings.Contains(tt.full, want) {
+					t.Errorf("Expected section to contain %q, got:\n%s", want, tt.full)
+				}
+			}
+			if tt.empty != "" {
+				t.Errorf("Expected empty section to be omitted, got:\n%s", tt.empty)
+			}
+		})
+	}
+}
+
+func TestBuildPromptWithContext_OmitsEmptySections(t *testing.T) {
+	ctx := &context.RelevantContext{Types: map[string]string{}}
+
+	prompt := NewBuilder(nil).buildPromptWithContext(ctx, newTestTarget())
+
+	expected := "<context>\n</context>\n\n" +
+		"<target>\n```go\nfunc Add(a int, b int) int {\n    <IMPLEMENT_HERE>\n}\n```\n</target>\n\n" +
+		"<parameters>\nParameters: a int, b int; Returns: int\n</parameters>\n\n" +
+		"<instruction>\nadd two numbers\n</instruction>\n"
+	if prompt != expected {
+		t.Errorf("Unexpected prompt:\n%s", prompt)
+	}
+}
@@ -0,0 +1,68 @@
+package prompt
+
+import (
+	"fmt"
+	"os"
+	"path/filepath"
+	"strings"
+	"text/template"
+)
+
+// Options holds project-level settings that shape prompt construction
+type Options struct {
+	Template         *template.Template // Custom prompt template (nil uses the built-in layout)
+	PackageContext   bool               // Include exported declarations of the whole package
+	MaxContextTokens int                // Approximate token limit for the package API section (0 means no limit)
+	Examples         []Example          // Few-shot examples for the implementation phase
+	MaxExamples      int                // Maximum number of examples to send (0 means all)
+	PatternExamples  bool               // Include built-in examples of patterns matching the signature
+	Contexts         ContextCache       // Contexts extracted ahead of generation (nil extracts on demand)
+}
+
+// LoadTemplate reads and parses a prompt template file.
+//
+// Templates use Go text/template syntax and can reference the variables
+// {{.package}}, {{.signature}}, {{.instruction}} and {{.type_info}}.
+// Referencing any other variable is an error at render time.
+func LoadTemplate(path string) (*template.Template, error) {
+	data, err := os.ReadFile(path)
+	if err != nil {
+		return nil, fmt.Errorf("failed to read prompt template: %w", err)
+	}
+
+	return ParseTemplate(filepath.Base(path), string(data))
+}
+
+// ParseTemplate parses prompt template text
+func ParseTemplate(name, text string) (*template.Template, error) {
+	tmpl, err := template.New(name).Option("missingkey=error").Parse(text)
+	if err != nil {
+		return nil, fmt.Errorf("failed to parse prompt template %s: %w", name, err)
+	}
+	return tmpl, nil
+}
+
+// TemplateVars holds the values available to prompt templates
+type TemplateVars struct {
+	Package     string
+	Signature   string
+	Instruction string
+	TypeInfo    string
+}
+
+// RenderTemplate renders a prompt template with the given variables
+func RenderTemplate(tmpl *template.Template, vars TemplateVars) (string, error) {
+	data := map[string]string{
+		"package":     vars.Package,
+		"signature":   vars.Signature,
+		"instruction": vars.Instruction,
+		"type_info":   vars.TypeInfo,
+	}
+
+	var buf strings.Builder
+	if err := tmpl.Execute(&buf, data); err != nil {
+		return "", fmt.Errorf("failed to render prompt template (available variables: package, signature, instruction, type_info): %w", err)
+	}
+
+	return buf.String(), nil
+}
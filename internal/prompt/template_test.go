@@ -0,0 +1,43 @@
+package prompt
+
+import (
+	"strings"
+	"testing"
+)
+
+func TestRenderTemplate(t *testing.T) {
+	tmpl, err := ParseTemplate("test", "package {{.package}}\n{{.signature}}\n{{.instruction}}\n{{.type_info}}")
+	if err != nil {
+		t.Fatalf("Failed to parse template: %v", err)
+	}
+
+	rendered, err := RenderTemplate(tmpl, TemplateVars{
+		Package:     "store",
+		Signature:   "func Find(key string) (*Item, error)",
+		Instruction: "Look up an item",
+		TypeInfo:    "type Item struct{}",
+	})
+	if err != nil {
+		t.Fatalf("Failed to render template: %v", err)
+	}
+
+	expected := "package store\nfunc Find(key string) (*Item, error)\nLook up an item\ntype Item struct{}"
+	if rendered != expected {
+		t.Errorf("Expected %q, got %q", expected, rendered)
+	}
+}
+
+func TestRenderTemplate_MissingVariable(t *testing.T) {
+	tmpl, err := ParseTemplate("test", "{{.signature}} {{.receiver}}")
+	if err != nil {
+		t.Fatalf("Failed to parse template: %v", err)
+	}
+
+	_, err = RenderTemplate(tmpl, TemplateVars{Signature: "func F()"})
+	if err == nil {
+		t.Fatal("Expected error for missing variable")
+	}
+	if !strings.Contains(err.Error(), "receiver") {
+		t.Errorf("Expected error to name the missing variable, got: %v", err)
+	}
+}
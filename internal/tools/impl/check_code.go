@@ -114,7 +114,7 @@ func (t *CheckCodeTool) Execute(ctx context.Context, params map[string]any) (any
 	}
 
 	// Replace function body using AST manipulation
-	modified, err := t.replaceViaAST(fileInfo.SourceContent, target, code)
+	modified, err := t.replaceViaAST(fileInfo.ParsableContent(), target, code)
 	if err != nil {
 		return nil, fmt.Errorf("failed to replace function body: %w", err)
 	}
@@ -202,7 +202,7 @@ func (t *CheckCodeTool) replaceViaAST(sourceContent string, target *pkgparser.Ta
 
 	ast.Inspect(file, func(n ast.Node) bool {
 		if fn, ok := n.(*ast.FuncDecl); ok {
-			if t.matchesTarget(fn, target) {
+			if !target.Closure && t.matchesTarget(fn, target) {
 				// Replace the function body
 				fn.Body = newBodyStmt
 				replacedFunc = fn
@@ -210,6 +210,15 @@ func (t *CheckCodeTool) replaceViaAST(sourceContent string, target *pkgparser.Ta
 				return false
 			}
 		}
+		if decl, ok := n.(*ast.GenDecl); ok && target.Closure {
+			if name, lit := pkgparser.ClosureLiteral(decl); lit != nil && name.Name == target.Name {
+				// Replace the function literal body
+				lit.Body = newBodyStmt
+				replacedFunc = &ast.FuncDecl{Name: name, Type: lit.Type, Body: lit.Body}
+				replaced = true
+				return false
+			}
+		}
 		return true
 	})
 
@@ -285,6 +294,12 @@ func (t *CheckCodeTool) createPositionMapper(pkg *packages.Package, modified *Mo
 					return false
 				}
 			}
+			if decl, ok := n.(*ast.GenDecl); ok {
+				if name, lit := pkgparser.ClosureLiteral(decl); lit != nil && name.Name == modified.TargetFunc.Name.Name {
+					targetFunc = &ast.FuncDecl{Name: name, Type: lit.Type, Body: lit.Body}
+					return false
+				}
+			}
 			return true
 		})
 	}
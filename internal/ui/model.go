@@ -36,6 +36,8 @@ type Model struct {
 	width      int
 	height     int
 	tuiEnabled bool
+	filesDone  int // Files whose targets have all finished
+	filesTotal int // Files with targets being generated
 }
 
 // newModel creates a new TUI model
@@ -103,6 +105,11 @@ func (m *Model) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	case addTargetMsg:
 		// Add new target
 		m.addTarget(msg.Name, msg.Index, msg.Total)
+
+	case fileProgressMsg:
+		// Update file progress
+		m.filesDone = msg.Done
+		m.filesTotal = msg.Total
 	}
 
 	return m, nil
@@ -251,6 +258,11 @@ func (m *Model) buildHeader(stats targetStats) string {
 		header += fmt.Sprintf(" | %s", stats.totalDuration.Round(time.Millisecond))
 	}
 
+	// Add file progress when targets span several files
+	if m.filesTotal > 1 {
+		header += fmt.Sprintf(" | files %d/%d", m.filesDone, m.filesTotal)
+	}
+
 	// Add status counts
 	if stats.failed > 0 {
 		header += fmt.Sprintf(" | FAILED: %d", stats.failed)
@@ -435,6 +447,11 @@ type addTargetMsg struct {
 	Total int
 }
 
+type fileProgressMsg struct {
+	Done  int
+	Total int
+}
+
 // formatLogMessage formats a log message with key structured attributes
 func (m *Model) formatLogMessage(record slog.Record) string {
 	msg := record.Message
@@ -444,7 +461,8 @@ func (m *Model) formatLogMessage(record slog.Record) string {
 	record.Attrs(func(a slog.Attr) bool {
 		// Skip internal/redundant attributes
 		if a.Key == "phase" || a.Key == "targetIndex" ||
-			a.Key == "totalTargets" || a.Key == "targetName" {
+			a.Key == "totalTargets" || a.Key == "targetName" ||
+			a.Key == "generationID" {
 			return true
 		}
 		// Format as key=value
@@ -119,7 +119,16 @@ func (p *Program) Fail(targetIndex int) {
 	// Plain mode output is handled by Handler
 }
 
-// Quit stops the TUI program
+// UpdateFileProgress reports how many files have all their targets finished
+func (p *Program) UpdateFileProgress(done, total int) {
+	p.teaProgram.Send(fileProgressMsg{
+		Done:  done,
+		Total: total,
+	})
+}
+
+// Quit stops the TUI program. Messages are delivered in order, so all updates
+// sent before Quit are applied, and the final state is rendered before Start returns.
 func (p *Program) Quit() {
 	p.teaProgram.Quit()
 }